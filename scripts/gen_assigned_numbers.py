@@ -0,0 +1,141 @@
+#!/usr/bin/env python3
+"""Regenerate src/bluetooth/assigned_numbers/table.rs from the Bluetooth SIG
+assigned numbers.
+
+Input is the `assigned_numbers/` directory of the SIG's public repository
+(https://bitbucket.org/bluetooth-SIG/public). By default it is the trimmed
+copy in scripts/assigned_numbers/, which keeps the entries VoidLink users
+commonly meet; the committed table is always this script's output for it.
+The files read, and the shape expected of each:
+
+  uuids/service_uuids.yaml          uuids: [{uuid: 0x180F, name: ...}, ...]
+  uuids/service_class.yaml          uuids: [{uuid: 0x110B, name: ...}, ...]
+  uuids/protocol_identifiers.yaml   uuids: [{uuid: 0x0100, name: ...}, ...]
+  uuids/characteristic_uuids.yaml  uuids: [{uuid: 0x2A19, name: ...}, ...]
+  uuids/descriptors.yaml            uuids: [{uuid: 0x2902, name: ...}, ...]
+  uuids/member_uuids.yaml           uuids: [{uuid: 0xFEAA, name: ...}, ...]
+  company_identifiers/company_identifiers.yaml
+                                    company_identifiers: [{value: 0x004C, name: ...}, ...]
+  core/appearance_values.yaml       appearance_values: [{category: 0x00F, name: ...,
+                                        subcategory: [{value: 0x01, name: ...}, ...]}, ...]
+
+Missing files are skipped with a warning, so a trimmed-down tree in the same
+layout works too. Requires PyYAML.
+
+Usage:
+  scripts/gen_assigned_numbers.py [path/to/public/assigned_numbers]
+      [-o src/bluetooth/assigned_numbers/table.rs] [--check]
+
+With --check nothing is written; the exit status is 1 when the output file
+differs from what the input generates.
+"""
+
+import argparse
+import pathlib
+import sys
+
+import yaml
+
+UUID_FILES = [
+    "uuids/protocol_identifiers.yaml",
+    "uuids/service_class.yaml",
+    "uuids/service_uuids.yaml",
+    "uuids/descriptors.yaml",
+    "uuids/characteristic_uuids.yaml",
+    "uuids/member_uuids.yaml",
+]
+
+
+def load(root, rel, key):
+    path = root / rel
+    if not path.exists():
+        print(f"warning: {path} not found, skipping", file=sys.stderr)
+        return []
+    with path.open(encoding="utf-8") as f:
+        return yaml.safe_load(f).get(key) or []
+
+
+def rust_str(s):
+    return '"' + s.strip().replace("\\", "\\\\").replace('"', '\\"') + '"'
+
+
+def table(name, doc, rows, width):
+    out = [f"/// {doc}", f"pub(super) static {name}: &[(u16, &str)] = &["]
+    for value, label in sorted(rows.items()):
+        out.append(f"    (0x{value:0{width}X}, {rust_str(label)}),")
+    out.append("];")
+    return "\n".join(out)
+
+
+def main():
+    ap = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    ap.add_argument(
+        "assigned_numbers",
+        type=pathlib.Path,
+        nargs="?",
+        default=pathlib.Path(__file__).parent / "assigned_numbers",
+    )
+    ap.add_argument(
+        "-o",
+        "--output",
+        type=pathlib.Path,
+        default=pathlib.Path("src/bluetooth/assigned_numbers/table.rs"),
+    )
+    ap.add_argument(
+        "--check",
+        action="store_true",
+        help="fail if the output file is not up to date instead of writing it",
+    )
+    args = ap.parse_args()
+    root = args.assigned_numbers
+
+    # 16-bit UUIDs from every registry share one table: the SIG allocates
+    # them from disjoint ranges, so a single lookup resolves any of them.
+    uuids = {}
+    for rel in UUID_FILES:
+        for entry in load(root, rel, "uuids"):
+            uuids.setdefault(int(entry["uuid"]), entry["name"])
+
+    companies = {
+        int(e["value"]): e["name"]
+        for e in load(root, "company_identifiers/company_identifiers.yaml", "company_identifiers")
+    }
+
+    categories = {}
+    subcategories = {}
+    for cat in load(root, "core/appearance_values.yaml", "appearance_values"):
+        cat_value = int(cat["category"])
+        categories[cat_value] = cat["name"]
+        for sub in cat.get("subcategory") or []:
+            subcategories[(cat_value << 6) | int(sub["value"])] = sub["name"]
+
+    parts = [
+        "// @generated by scripts/gen_assigned_numbers.py from the Bluetooth SIG",
+        "// assigned numbers. Do not edit by hand; re-run the script instead.",
+        "",
+        table("UUID16", "16-bit UUIDs: protocols, service classes, services, descriptors,\n/// characteristics and member services.", uuids, 4),
+        "",
+        table("COMPANIES", "Company identifiers (manufacturer-specific data keys).", companies, 4),
+        "",
+        table("APPEARANCE_CATEGORIES", "Appearance categories (bits 15..6 of the appearance value).", categories, 3),
+        "",
+        table("APPEARANCE_SUBCATEGORIES", "Full appearance values with a named subcategory.", subcategories, 4),
+        "",
+    ]
+    generated = "\n".join(parts)
+    if args.check:
+        current = args.output.read_text(encoding="utf-8") if args.output.exists() else ""
+        if current != generated:
+            print(f"{args.output} is out of date; re-run {sys.argv[0]}", file=sys.stderr)
+            sys.exit(1)
+        print(f"{args.output} is up to date")
+        return
+    args.output.write_text(generated, encoding="utf-8")
+    print(
+        f"wrote {args.output}: {len(uuids)} UUIDs, {len(companies)} companies, "
+        f"{len(categories)} appearance categories, {len(subcategories)} subcategories"
+    )
+
+
+if __name__ == "__main__":
+    main()
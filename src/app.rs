@@ -3,12 +3,26 @@
 //! `App` is the single source of truth for the entire TUI. It is only mutated
 //! from the main event loop — no `Arc<Mutex<>>` needed.
 
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use regex::Regex;
 use tokio::sync::mpsc;
 
+use crate::battery::{BatteryAlert, BatteryLevel, BatteryMonitor};
+use crate::bluetooth::assigned_numbers;
 use crate::bluetooth::types::*;
 use crate::config::{SearchMode, SortMode};
+use crate::dashboard::Dashboards;
+use crate::gatt_browser::{GattBrowser, GattView, LogKind, WriteForm, WriteFormat};
+use crate::gatt_export;
+use crate::history::HistoryStore;
+use crate::notify::{self, Urgency};
+use crate::recorder::{ExportFormat, SessionRecorder};
+use crate::telemetry::Telemetry;
 
 // ─── Input modes ────────────────────────────────────────────────────────────
 
@@ -23,6 +37,20 @@ pub enum InputMode {
     Dialog,
     /// `A` rename — keys go to the rename buffer.
     Rename,
+    /// `f` discovery filter dialog — keys edit the filter form.
+    FilterEdit,
+    /// `b` GATT browser — keys navigate the service tree.
+    Gatt,
+    /// `o` profile picker — keys select and toggle profiles.
+    Profiles,
+    /// `C` adapter settings — keys edit the adapter form.
+    AdapterSettings,
+    /// `P` admin policy — keys pick a service allowlist preset.
+    AdminPolicy,
+    /// `F` send file — keys go to the file path buffer.
+    SendFile,
+    /// `T` transfers list — keys select and cancel transfers.
+    Transfers,
 }
 
 // ─── Popup types ────────────────────────────────────────────────────────────
@@ -49,6 +77,40 @@ pub enum Popup {
     },
     /// Help overlay.
     Help,
+    /// Runtime discovery filter editor.
+    DiscoveryFilter(FilterForm),
+    /// Per-profile connect / disconnect dialog.
+    Profiles(ProfilePicker),
+    /// Adapter alias, discoverable and pairable settings.
+    AdapterSettings(AdapterForm),
+    /// Service allowlist and its presets. Holds the selected row: 0 is
+    /// "allow all", then the config presets.
+    AdminPolicy { cursor: usize },
+    /// OBEX file transfers, newest first, with the selected row.
+    Transfers { cursor: usize },
+}
+
+// ─── Toasts ─────────────────────────────────────────────────────────────────
+
+/// Most toasts shown at once; older ones are dropped first.
+const MAX_TOASTS: usize = 4;
+
+/// Severity of a toast, which picks its colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// A non-modal notification stacked in the corner. Unlike a `Popup` it never
+/// captures input and expires on its own.
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub title: String,
+    pub message: String,
+    pub level: ToastLevel,
+    pub expires: Instant,
 }
 
 impl Popup {
@@ -58,7 +120,12 @@ impl Popup {
             Popup::Error { slide, .. }
             | Popup::ConnectionResult { slide, .. }
             | Popup::PinDisplay { slide, .. } => Some(slide),
-            Popup::Help => None,
+            Popup::Help
+            | Popup::DiscoveryFilter(_)
+            | Popup::Profiles(_)
+            | Popup::AdapterSettings(_)
+            | Popup::AdminPolicy { .. }
+            | Popup::Transfers { .. } => None,
         }
     }
 
@@ -69,11 +136,292 @@ impl Popup {
             Popup::Error { slide, .. }
             | Popup::ConnectionResult { slide, .. }
             | Popup::PinDisplay { slide, .. } => *slide,
-            Popup::Help => 1.0,
+            Popup::Help
+            | Popup::DiscoveryFilter(_)
+            | Popup::Profiles(_)
+            | Popup::AdapterSettings(_)
+            | Popup::AdminPolicy { .. }
+            | Popup::Transfers { .. } => 1.0,
         }
     }
 }
 
+// ─── Discovery filter form ──────────────────────────────────────────────────
+
+/// Editable row in the discovery filter dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterField {
+    Transport,
+    Rssi,
+    Pathloss,
+    Uuids,
+    Duplicates,
+}
+
+impl FilterField {
+    pub const ALL: [FilterField; 5] = [
+        FilterField::Transport,
+        FilterField::Rssi,
+        FilterField::Pathloss,
+        FilterField::Uuids,
+        FilterField::Duplicates,
+    ];
+
+    fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|f| *f == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    fn prev(self) -> Self {
+        let idx = Self::ALL.iter().position(|f| *f == self).unwrap_or(0);
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// Draft state of the discovery filter dialog. Only sent to the worker when
+/// the user confirms with Enter.
+#[derive(Debug, Clone)]
+pub struct FilterForm {
+    /// Filter being edited (UUIDs live in `uuid_input` until applied).
+    pub draft: ScanFilter,
+    /// Focused row.
+    pub field: FilterField,
+    /// Comma/space separated UUID list as typed.
+    pub uuid_input: String,
+    /// Validation error from the last apply attempt (empty = none).
+    pub error: String,
+}
+
+impl FilterForm {
+    fn new(filter: &ScanFilter) -> Self {
+        let uuid_input = filter
+            .uuids
+            .iter()
+            .map(short_uuid)
+            .collect::<Vec<_>>()
+            .join(", ");
+        Self {
+            draft: filter.clone(),
+            field: FilterField::Transport,
+            uuid_input,
+            error: String::new(),
+        }
+    }
+
+    /// Nudge the focused value left (`-1`) or right (`+1`).
+    fn adjust(&mut self, dir: i16) {
+        match self.field {
+            FilterField::Transport => {
+                self.draft.transport = self.draft.transport.next();
+            }
+            FilterField::Rssi => {
+                let cur = self.draft.rssi.unwrap_or(-75);
+                self.draft.rssi = Some((cur + dir * 5).clamp(-127, 20));
+                // RSSI and pathloss are mutually exclusive in BlueZ.
+                self.draft.pathloss = None;
+            }
+            FilterField::Pathloss => {
+                let cur = self.draft.pathloss.unwrap_or(60) as i16;
+                self.draft.pathloss = Some((cur + dir * 5).clamp(0, 137) as u16);
+                self.draft.rssi = None;
+            }
+            FilterField::Duplicates => {
+                self.draft.duplicate_data = !self.draft.duplicate_data;
+            }
+            FilterField::Uuids => {}
+        }
+    }
+
+    /// Clear the focused value back to "unrestricted".
+    fn clear(&mut self) {
+        match self.field {
+            FilterField::Transport => self.draft.transport = ScanTransport::Auto,
+            FilterField::Rssi => self.draft.rssi = None,
+            FilterField::Pathloss => self.draft.pathloss = None,
+            FilterField::Uuids => {
+                self.uuid_input.pop();
+            }
+            FilterField::Duplicates => self.draft.duplicate_data = false,
+        }
+    }
+
+    /// Validate the UUID list and produce the final filter.
+    fn build(&self) -> Result<ScanFilter, String> {
+        let mut uuids = Vec::new();
+        for token in self
+            .uuid_input
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|t| !t.is_empty())
+        {
+            match parse_uuid(token) {
+                Some(uuid) => uuids.push(uuid),
+                None => return Err(format!("invalid UUID: {token}")),
+            }
+        }
+        Ok(ScanFilter {
+            uuids,
+            ..self.draft.clone()
+        })
+    }
+}
+
+// ─── Profile picker ─────────────────────────────────────────────────────────
+
+/// State of the per-profile connect dialog.
+#[derive(Debug, Clone)]
+pub struct ProfilePicker {
+    pub address: Address,
+    pub name: String,
+    /// `None` until the worker reports.
+    pub profiles: Option<Vec<ProfileStatus>>,
+    pub cursor: usize,
+    /// Profile with a connect / disconnect in flight.
+    pub pending: Option<Uuid>,
+    pub error: Option<String>,
+}
+
+impl ProfilePicker {
+    fn new(address: Address, name: String) -> Self {
+        Self {
+            address,
+            name,
+            profiles: None,
+            cursor: 0,
+            pending: None,
+            error: None,
+        }
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        let len = self.profiles.as_ref().map_or(0, Vec::len);
+        self.cursor = self
+            .cursor
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+    }
+
+    /// Connect the selected profile, or disconnect it if it is connected.
+    fn toggle(&mut self) -> Option<BtCommand> {
+        if self.pending.is_some() {
+            return None;
+        }
+        let profile = self.profiles.as_ref()?.get(self.cursor)?;
+        let uuid = profile.uuid;
+        self.pending = Some(uuid);
+        self.error = None;
+        Some(match profile.state {
+            ProfileState::Connected => BtCommand::DisconnectProfile(self.address, uuid),
+            ProfileState::Disconnected | ProfileState::Unknown => {
+                BtCommand::ConnectProfile(self.address, uuid)
+            }
+        })
+    }
+}
+
+// ─── Adapter settings ───────────────────────────────────────────────────────
+
+/// Editable row of the adapter settings view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdapterField {
+    Alias,
+    Discoverable,
+    DiscoverableTimeout,
+    Pairable,
+    PairableTimeout,
+}
+
+impl AdapterField {
+    pub const ALL: [AdapterField; 5] = [
+        AdapterField::Alias,
+        AdapterField::Discoverable,
+        AdapterField::DiscoverableTimeout,
+        AdapterField::Pairable,
+        AdapterField::PairableTimeout,
+    ];
+
+    fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|f| *f == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    fn prev(self) -> Self {
+        let idx = Self::ALL.iter().position(|f| *f == self).unwrap_or(0);
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// Timeout presets, in seconds (0 = never).
+const TIMEOUT_STEPS: [u32; 9] = [0, 30, 60, 120, 180, 300, 600, 1800, 3600];
+
+/// The next preset above (`dir > 0`) or below `current`.
+fn step_timeout(current: u32, dir: i8) -> u32 {
+    if dir > 0 {
+        TIMEOUT_STEPS
+            .into_iter()
+            .find(|&s| s > current)
+            .unwrap_or(current)
+    } else {
+        TIMEOUT_STEPS
+            .into_iter()
+            .rev()
+            .find(|&s| s < current)
+            .unwrap_or(0)
+    }
+}
+
+/// State of the adapter settings view. Toggles and timeouts apply
+/// immediately; the alias is sent on Enter.
+#[derive(Debug, Clone)]
+pub struct AdapterForm {
+    /// Focused row.
+    pub field: AdapterField,
+    /// Alias as typed.
+    pub alias_input: String,
+}
+
+impl AdapterForm {
+    fn new(adapter: &AdapterInfo) -> Self {
+        Self {
+            field: AdapterField::Alias,
+            alias_input: adapter.alias.clone(),
+        }
+    }
+
+    /// Change the focused setting: flip a flag or step a timeout.
+    fn adjust(&self, adapter: &AdapterInfo, dir: i8) -> Option<AdapterSetting> {
+        Some(match self.field {
+            AdapterField::Alias => return None,
+            AdapterField::Discoverable => AdapterSetting::Discoverable(!adapter.discoverable),
+            AdapterField::DiscoverableTimeout => {
+                AdapterSetting::DiscoverableTimeout(step_timeout(adapter.discoverable_timeout, dir))
+            }
+            AdapterField::Pairable => AdapterSetting::Pairable(!adapter.pairable),
+            AdapterField::PairableTimeout => {
+                AdapterSetting::PairableTimeout(step_timeout(adapter.pairable_timeout, dir))
+            }
+        })
+    }
+}
+
+/// When a discoverable / pairable window closes. BlueZ doesn't report the
+/// time left, so the countdown starts when we see the flag switch on or the
+/// timeout change.
+fn window_deadline(
+    was_on: bool,
+    old_timeout: u32,
+    old_deadline: Option<Instant>,
+    on: bool,
+    timeout: u32,
+) -> Option<Instant> {
+    if !on || timeout == 0 {
+        None
+    } else if was_on && old_timeout == timeout {
+        old_deadline
+    } else {
+        Some(Instant::now() + Duration::from_secs(timeout.into()))
+    }
+}
+
 // ─── Actions produced by input handling ─────────────────────────────────────
 
 /// Actions that the main loop should execute after processing input.
@@ -96,8 +444,24 @@ pub struct App {
     pub selected_index: usize,
     /// Current adapter snapshot.
     pub adapter: AdapterInfo,
+    /// When the adapter stops being discoverable (`None` = no timeout).
+    pub discoverable_until: Option<Instant>,
+    /// When the adapter stops being pairable (`None` = no timeout).
+    pub pairable_until: Option<Instant>,
+    /// Admin policy allowlist and affected devices (`None` = not read yet).
+    pub admin_policy: Option<AdminPolicy>,
+    /// Coordinated sets known to the adapter.
+    pub device_sets: Vec<DeviceSet>,
+    /// AVRCP media players of connected devices.
+    pub media_players: Vec<MediaPlayer>,
+    /// Audio transports of connected devices.
+    pub media_transports: Vec<MediaTransport>,
     /// Whether scanning is active.
     pub scanning: bool,
+    /// When the current scan stops on its own (`None` = until stopped).
+    pub scan_deadline: Option<Instant>,
+    /// When the next periodic scan starts (`None` = periodic mode off).
+    pub periodic_next: Option<Instant>,
     /// Current input mode.
     pub input_mode: InputMode,
     /// Active search query (when in Search mode).
@@ -116,14 +480,52 @@ pub struct App {
     pub dirty: bool,
     /// Active sort mode — cyclable at runtime.
     pub sort_mode: SortMode,
+    /// Only list devices of this category (`None` = all) — cyclable at runtime.
+    pub category_filter: Option<DeviceCategory>,
+    /// Only list blocked devices.
+    pub blocked_only: bool,
     /// Rename buffer (when in Rename mode).
     pub rename_buffer: String,
     /// Address of the device being renamed.
     pub rename_target: Option<Address>,
+    /// File path buffer (when in SendFile mode).
+    pub send_buffer: String,
+    /// Address of the device the file goes to.
+    pub send_target: Option<Address>,
+    /// OBEX file transfers this session, newest first.
+    pub transfers: Vec<Transfer>,
+    /// Discovery filter most recently sent to the worker.
+    pub discovery_filter: ScanFilter,
+    /// Persistent device history (`None` when disabled in config).
+    pub history: Option<HistoryStore>,
+    /// Per-device RSSI / battery time series for the detail charts.
+    pub telemetry: Telemetry,
+    /// Low-battery thresholds state per device.
+    pub battery: BatteryMonitor,
+    /// Standard GATT profile readings per connected device.
+    pub dashboards: Dashboards,
+    /// Active non-modal alerts, oldest first.
+    pub toasts: Vec<Toast>,
+    /// Watchlist (rule index, device) pairs currently in range.
+    pub watch_present: HashSet<(usize, Address)>,
+    /// Detail panel scroll offset, and the device it applies to.
+    detail_scroll: (Option<Address>, u16),
+    /// Largest useful scroll offset, written by the detail panel each frame
+    /// so scrolling stops at the last line.
+    pub detail_scroll_max: Cell<u16>,
+    /// Sightings captured while scanning, for survey export.
+    pub recorder: SessionRecorder,
+    /// `--record` target; exports go here instead of the data dir.
+    pub record_to: Option<(PathBuf, ExportFormat)>,
+    /// Open GATT browser, shown in place of the detail panel.
+    pub gatt: Option<GattBrowser>,
     /// Sender handle to the BT worker (retained for future use).
     pub _bt_cmd_tx: mpsc::Sender<BtCommand>,
     /// Cached filtered device count — updated every tick to avoid repeated alloc.
     cached_filter_count: usize,
+    /// Last once-per-second housekeeping pass (countdowns, "seen ago"
+    /// labels, stale purge).
+    last_second: Instant,
 }
 
 impl App {
@@ -133,7 +535,15 @@ impl App {
             devices: Vec::new(),
             selected_index: 0,
             adapter: AdapterInfo::default(),
+            discoverable_until: None,
+            pairable_until: None,
+            admin_policy: None,
+            device_sets: Vec::new(),
+            media_players: Vec::new(),
+            media_transports: Vec::new(),
             scanning: false,
+            scan_deadline: None,
+            periodic_next: None,
             input_mode: InputMode::Normal,
             search_query: String::new(),
             search_error: String::new(),
@@ -143,10 +553,31 @@ impl App {
             running: true,
             dirty: true,
             sort_mode,
+            category_filter: crate::config::get().general.category_filter,
+            blocked_only: false,
             rename_buffer: String::new(),
             rename_target: None,
+            send_buffer: String::new(),
+            send_target: None,
+            transfers: Vec::new(),
+            discovery_filter: crate::config::get().bluetooth.discovery.clone(),
+            history: crate::config::get()
+                .history
+                .enabled
+                .then(HistoryStore::load),
+            telemetry: Telemetry::default(),
+            battery: BatteryMonitor::default(),
+            dashboards: Dashboards::default(),
+            toasts: Vec::new(),
+            watch_present: HashSet::new(),
+            detail_scroll: (None, 0),
+            detail_scroll_max: Cell::new(0),
+            recorder: SessionRecorder::new(),
+            record_to: None,
+            gatt: None,
             _bt_cmd_tx: bt_cmd_tx,
             cached_filter_count: 0,
+            last_second: Instant::now(),
         }
     }
 
@@ -157,33 +588,106 @@ impl App {
     pub fn filtered_devices(&self) -> Vec<&DeviceInfo> {
         let cfg = crate::config::get();
         let hide_unnamed = cfg.general.hide_unnamed_devices;
+        let hide_stale = cfg.general.hide_stale_devices;
+        let stale_after = Duration::from_secs(cfg.general.stale_after_secs);
         let search_mode = cfg.general.search_mode;
 
         // Compile regex once per call if needed.
         let compiled_regex = self.compile_search_regex(search_mode);
 
-        self.devices
+        let devices = self
+            .devices
             .iter()
             .filter(|d| {
                 if hide_unnamed && d.name.is_none() {
                     return false;
                 }
+                if hide_stale && !d.paired && !d.trusted && d.is_stale(stale_after) {
+                    return false;
+                }
+                if self.category_filter.is_some_and(|c| d.kind.category() != c) {
+                    return false;
+                }
+                if self.blocked_only && !d.blocked {
+                    return false;
+                }
                 if self.search_query.is_empty() {
                     return true;
                 }
                 match &compiled_regex {
                     Some(re) => {
-                        re.is_match(d.display_name()) || re.is_match(&d.address.to_string())
+                        re.is_match(d.display_name())
+                            || re.is_match(&d.address.to_string())
+                            || resolved_names(d).any(|n| re.is_match(n))
                     }
                     None => {
                         // Plain substring match.
                         let query = self.search_query.to_lowercase();
                         d.display_name().to_lowercase().contains(&query)
                             || d.address.to_string().to_lowercase().contains(&query)
+                            || resolved_names(d).any(|n| n.to_lowercase().contains(&query))
                     }
                 }
             })
-            .collect()
+            .collect();
+        self.group_sets(devices)
+    }
+
+    /// Move members of a coordinated set up to sit right after the first
+    /// member in the list, so the set renders as one group.
+    fn group_sets<'a>(&self, devices: Vec<&'a DeviceInfo>) -> Vec<&'a DeviceInfo> {
+        if self.device_sets.is_empty() {
+            return devices;
+        }
+        let mut placed = vec![false; devices.len()];
+        let mut grouped = Vec::with_capacity(devices.len());
+        for i in 0..devices.len() {
+            if placed[i] {
+                continue;
+            }
+            placed[i] = true;
+            grouped.push(devices[i]);
+            let Some(set) = self.set_of(&devices[i].address) else {
+                continue;
+            };
+            for member in set.members.iter().filter(|m| **m != devices[i].address) {
+                if let Some(j) =
+                    (i + 1..devices.len()).find(|&j| !placed[j] && devices[j].address == *member)
+                {
+                    placed[j] = true;
+                    grouped.push(devices[j]);
+                }
+            }
+        }
+        grouped
+    }
+
+    /// The coordinated set `address` belongs to, if it has other members.
+    pub fn set_of(&self, address: &Address) -> Option<&DeviceSet> {
+        self.device_sets
+            .iter()
+            .find(|s| s.members.len() > 1 && s.members.contains(address))
+    }
+
+    /// Display name of a known device, or its address.
+    pub fn device_name(&self, address: &Address) -> String {
+        self.devices
+            .iter()
+            .find(|d| d.address == *address)
+            .map_or_else(|| address.to_string(), |d| d.display_name().to_string())
+    }
+
+    /// The media player of `address`, if it offers one.
+    pub fn media_player(&self, address: &Address) -> Option<&MediaPlayer> {
+        self.media_players.iter().find(|p| p.address == *address)
+    }
+
+    /// Audio transports of `address`.
+    pub fn audio_transports(&self, address: &Address) -> impl Iterator<Item = &MediaTransport> {
+        let address = *address;
+        self.media_transports
+            .iter()
+            .filter(move |t| t.address == address)
     }
 
     /// Compile the search query as a regex if the search mode requires it.
@@ -233,6 +737,32 @@ impl App {
         filtered.get(self.selected_index).copied()
     }
 
+    /// Members of the selected device's coordinated set, if it is in one.
+    fn selected_set_members(&self) -> Option<Vec<Address>> {
+        let device = self.selected_device()?;
+        self.set_of(&device.address).map(|s| s.members.clone())
+    }
+
+    /// Detail panel scroll offset for `address` (zero after the selection
+    /// moves to another device).
+    pub fn detail_scroll(&self, address: &Address) -> u16 {
+        match self.detail_scroll {
+            (Some(a), offset) if a == *address => offset,
+            _ => 0,
+        }
+    }
+
+    /// Scroll the detail panel by `delta` lines.
+    fn scroll_detail(&mut self, delta: i32) {
+        let Some(addr) = self.selected_device().map(|d| d.address) else {
+            return;
+        };
+        let current = i32::from(self.detail_scroll(&addr));
+        let max = i32::from(self.detail_scroll_max.get());
+        let next = (current + delta).clamp(0, max) as u16;
+        self.detail_scroll = (Some(addr), next);
+    }
+
     /// Clamp `selected_index` to valid bounds.
     fn clamp_selection(&mut self) {
         let len = self.filtered_devices().len();
@@ -277,10 +807,29 @@ impl App {
             }
         }
 
+        // Expire toasts.
+        let now = Instant::now();
+        let before = self.toasts.len();
+        self.toasts.retain(|t| t.expires > now);
+        if self.toasts.len() != before {
+            self.dirty = true;
+        }
+
         // Scanning spinner needs continuous redraws.
         if self.scanning {
             self.dirty = true;
         }
+
+        // Countdowns and "seen 3m ago" labels only change once per second.
+        if self.last_second.elapsed() >= Duration::from_secs(1) {
+            self.last_second = Instant::now();
+            self.purge_stale_devices();
+            if let Some(history) = &mut self.history {
+                let interval = crate::config::get().history.flush_interval_secs;
+                history.flush_if_due(Duration::from_secs(interval));
+            }
+            self.dirty = true;
+        }
     }
 
     // ── Bluetooth event handling ────────────────────────────────────────
@@ -288,35 +837,86 @@ impl App {
     /// Apply a Bluetooth event from the worker to the app state.
     pub fn handle_bt_event(&mut self, event: BtEvent) {
         self.dirty = true;
+        if let Some(history) = &mut self.history {
+            history.observe(&event);
+        }
         match event {
             BtEvent::AdapterState(info) => {
+                self.discoverable_until = window_deadline(
+                    self.adapter.discoverable,
+                    self.adapter.discoverable_timeout,
+                    self.discoverable_until,
+                    info.discoverable,
+                    info.discoverable_timeout,
+                );
+                self.pairable_until = window_deadline(
+                    self.adapter.pairable,
+                    self.adapter.pairable_timeout,
+                    self.pairable_until,
+                    info.pairable,
+                    info.pairable_timeout,
+                );
                 self.adapter = info;
             }
 
-            BtEvent::DeviceFound(info) => {
-                if let Some(existing) = self.devices.iter_mut().find(|d| d.address == info.address)
-                {
-                    *existing = info;
-                } else {
-                    self.devices.push(info);
-                }
-                self.sort_devices();
-                self.clamp_selection();
+            BtEvent::AdminPolicy(policy) => {
+                self.admin_policy = Some(policy);
             }
 
-            BtEvent::DeviceUpdated(info) => {
-                if let Some(existing) = self.devices.iter_mut().find(|d| d.address == info.address)
-                {
-                    *existing = info;
-                } else {
-                    self.devices.push(info);
+            BtEvent::DeviceSets(sets) => {
+                self.device_sets = sets;
+            }
+
+            BtEvent::MediaPlayers(players) => {
+                self.media_players = players;
+            }
+
+            BtEvent::MediaTransports(transports) => {
+                self.media_transports = transports;
+            }
+
+            BtEvent::TransferUpdated(transfer) => {
+                let previous = self.transfers.iter().position(|t| t.id == transfer.id);
+                let was_finished = previous.is_some_and(|i| self.transfers[i].status.is_finished());
+                if transfer.status.is_finished() && !was_finished {
+                    let name = self.device_name(&transfer.address);
+                    match transfer.status {
+                        TransferStatus::Complete => self.push_toast(
+                            "File sent",
+                            format!("{} → {name}", transfer.name),
+                            ToastLevel::Info,
+                        ),
+                        TransferStatus::Unknown => self.push_toast(
+                            "Transfer ended",
+                            format!("{} → {name}: outcome unknown", transfer.name),
+                            ToastLevel::Warning,
+                        ),
+                        _ => self.push_toast(
+                            "Transfer failed",
+                            format!("{} → {name}", transfer.name),
+                            ToastLevel::Warning,
+                        ),
+                    }
                 }
-                self.sort_devices();
-                self.clamp_selection();
+                match previous {
+                    Some(i) => self.transfers[i] = transfer,
+                    None => self.transfers.insert(0, transfer),
+                }
+            }
+
+            BtEvent::DeviceFound(info) | BtEvent::DeviceUpdated(info) => {
+                self.upsert_device(info);
             }
 
             BtEvent::DeviceRemoved(addr) => {
+                if self.gatt.as_ref().is_some_and(|g| g.address == addr) {
+                    // The worker ends any notify sessions on disconnect.
+                    let _ = self.close_gatt();
+                }
                 self.devices.retain(|d| d.address != addr);
+                self.telemetry.forget(&addr);
+                self.dashboards.forget(&addr);
+                self.battery.forget(&addr);
                 self.clamp_selection();
             }
 
@@ -324,6 +924,7 @@ impl App {
                 address,
                 success,
                 error,
+                ..
             } => {
                 let message = if success {
                     format!("Connected to {address}")
@@ -368,8 +969,154 @@ impl App {
                 self.popup_ttl = None;
             }
 
-            BtEvent::ScanningChanged(scanning) => {
+            BtEvent::ScanningChanged { scanning, deadline } => {
                 self.scanning = scanning;
+                self.scan_deadline = deadline;
+            }
+
+            BtEvent::PeriodicScanChanged { next } => {
+                self.periodic_next = next;
+            }
+
+            BtEvent::WatchEntered {
+                rule,
+                address,
+                name,
+                rssi,
+            } => {
+                self.watch_present.insert((rule, address));
+                let label = watch_label(rule);
+                let signal = rssi.map(|r| format!(" ({r} dBm)")).unwrap_or_default();
+                let message = format!("{name} is in range{signal}");
+                notify::send(&label, &message, Urgency::Normal);
+                self.push_toast(&label, message, ToastLevel::Info);
+            }
+
+            BtEvent::WatchLeft {
+                rule,
+                address,
+                name,
+            } => {
+                self.watch_present.remove(&(rule, address));
+                let label = watch_label(rule);
+                let message = format!("{name} left range");
+                notify::send(&label, &message, Urgency::Normal);
+                self.push_toast(&label, message, ToastLevel::Info);
+            }
+
+            BtEvent::GattDiscovered { address, services } => {
+                if let Some(browser) = self.gatt.as_mut().filter(|g| g.address == address) {
+                    gatt_export::store_cache(address, &browser.name, &services);
+                    browser.set_services(services);
+                }
+            }
+
+            BtEvent::GattSnapshot { address, services } => {
+                if let Some(browser) = self.gatt.as_mut().filter(|g| g.address == address) {
+                    browser.exporting = false;
+                    gatt_export::store_cache(address, &browser.name, &services);
+                    let export = gatt_export::GattExport::from_services(
+                        address,
+                        &browser.name,
+                        &services,
+                        true,
+                    );
+                    for c in services.iter().flat_map(|s| &s.characteristics) {
+                        if let Some(value) = &c.value {
+                            browser.push_value(c.handle, value, false);
+                        }
+                    }
+                    browser.set_snapshot(services);
+                    let path = gatt_export::export_path(address);
+                    match export.save(&path) {
+                        Ok(()) => self.push_toast(
+                            "GATT exported",
+                            path.display().to_string(),
+                            ToastLevel::Info,
+                        ),
+                        Err(e) => self.push_error(format!("GATT export failed: {e:#}")),
+                    }
+                }
+            }
+
+            BtEvent::GattFailed { address, error } => {
+                if let Some(browser) = self.gatt.as_mut().filter(|g| g.address == address) {
+                    browser.set_error(error);
+                }
+            }
+
+            BtEvent::GattValue {
+                target,
+                value,
+                notified,
+            } => {
+                if let Some(browser) = self.gatt.as_mut().filter(|g| g.address == target.address) {
+                    browser.push_value(target.characteristic, &value, notified);
+                }
+            }
+
+            BtEvent::GattWritten { target, len } => {
+                if let Some(browser) = self.gatt.as_mut().filter(|g| g.address == target.address) {
+                    let text = format!("Write acknowledged ({len} bytes)");
+                    browser.push_log(target.characteristic, LogKind::Info, text);
+                }
+            }
+
+            BtEvent::GattNotifying { target, active } => {
+                if let Some(browser) = self.gatt.as_mut().filter(|g| g.address == target.address) {
+                    browser.set_notifying(target.characteristic, active);
+                }
+            }
+
+            BtEvent::GattOpFailed { target, error } => {
+                if let Some(browser) = self.gatt.as_mut().filter(|g| g.address == target.address) {
+                    browser.push_log(target.characteristic, LogKind::Error, error);
+                }
+            }
+
+            BtEvent::ProfileStates { address, profiles } => {
+                if let Some(Popup::Profiles(picker)) = &mut self.active_popup {
+                    if picker.address == address {
+                        picker.profiles = Some(profiles);
+                        picker.pending = None;
+                        picker.move_cursor(0);
+                    }
+                }
+            }
+
+            BtEvent::ProfileOpFailed {
+                address,
+                uuid,
+                error,
+            } => match &mut self.active_popup {
+                Some(Popup::Profiles(picker)) if picker.address == address => {
+                    picker.error = Some(error);
+                    picker.pending = None;
+                }
+                _ => {
+                    let profile = assigned_numbers::describe_uuid(&uuid);
+                    self.push_error(format!("{profile}: {error}"));
+                }
+            },
+
+            BtEvent::ProfileReading { address, reading } => {
+                let level = match reading {
+                    ProfileReading::Battery(level) => Some(level),
+                    _ => None,
+                };
+                let previous = self.dashboards.battery(&address);
+                self.dashboards.observe(address, reading);
+                // Stand in for BlueZ's Battery1 on devices that lack it.
+                let device = self.devices.iter().find(|d| d.address == address);
+                if let (Some(level), Some(device)) = (level, device) {
+                    if device.battery.is_none() || device.battery == previous {
+                        let mut info = device.clone();
+                        info.battery = Some(level);
+                        // Not a sighting; upsert keeps the last-seen time.
+                        info.last_seen = None;
+                        self.upsert_device(info);
+                    }
+                }
             }
 
             BtEvent::Error(msg) => {
@@ -381,6 +1128,108 @@ impl App {
         }
     }
 
+    /// Insert or replace a device snapshot, keeping its last-seen time when
+    /// the update wasn't a sighting.
+    fn upsert_device(&mut self, mut info: DeviceInfo) {
+        if !info.connected {
+            self.dashboards.disconnected(&info.address);
+        } else if info.battery.is_none() {
+            info.battery = self.dashboards.battery(&info.address);
+        }
+        self.telemetry.record(&info);
+        if self.scanning && info.last_seen.is_some() {
+            self.recorder.record(&info);
+        }
+        if let Some(alert) = self.battery.observe(&info) {
+            self.battery_alert(alert);
+        }
+        if let Some(browser) = self.gatt.as_mut() {
+            if browser.address == info.address && !info.connected && browser.error.is_none() {
+                browser.set_error("Device disconnected".into());
+            }
+        }
+        if let Some(existing) = self.devices.iter_mut().find(|d| d.address == info.address) {
+            if info.last_seen.is_none() {
+                info.last_seen = existing.last_seen;
+            }
+            *existing = info;
+        } else {
+            self.devices.push(info);
+        }
+        self.sort_devices();
+        self.clamp_selection();
+    }
+
+    /// Surface a battery threshold crossing as a toast and external
+    /// notification.
+    fn battery_alert(&mut self, alert: BatteryAlert) {
+        let (title, level, urgency) = match alert.level {
+            BatteryLevel::Critical => ("Battery critical", ToastLevel::Critical, Urgency::Critical),
+            _ => ("Battery low", ToastLevel::Warning, Urgency::Normal),
+        };
+        let message = format!("{} is at {}%", alert.name, alert.percent);
+        notify::send(title, &message, urgency);
+        self.push_toast(title, message, level);
+    }
+
+    /// Export the recorded scan session to the `--record` path or a
+    /// timestamped file in the data dir.
+    fn export_session(&mut self) {
+        let (path, format) = match &self.record_to {
+            Some((path, format)) => (path.clone(), *format),
+            None => {
+                let format = crate::config::get().recorder.export_format;
+                (self.recorder.default_path(format), format)
+            }
+        };
+        match self.recorder.export(&path, format) {
+            Ok(()) => {
+                let message = format!(
+                    "{} devices → {}",
+                    self.recorder.device_count(),
+                    path.display()
+                );
+                self.push_toast("Session exported", message, ToastLevel::Info);
+            }
+            Err(e) => self.push_error(format!("Session export failed: {e:#}")),
+        }
+    }
+
+    /// Show a non-modal toast for `alert_duration_ms`.
+    pub fn push_toast(&mut self, title: &str, message: String, level: ToastLevel) {
+        let ttl = Duration::from_millis(crate::config::get().notifications.alert_duration_ms);
+        if self.toasts.len() == MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+        self.toasts.push(Toast {
+            title: title.to_string(),
+            message,
+            level,
+            expires: Instant::now() + ttl,
+        });
+        self.dirty = true;
+    }
+
+    /// Forget unpaired, untrusted devices that have been out of range for
+    /// longer than `purge_stale_after_secs`. Devices never seen this session
+    /// (BlueZ cache) are left alone.
+    fn purge_stale_devices(&mut self) {
+        let secs = crate::config::get().general.purge_stale_after_secs;
+        if secs == 0 {
+            return;
+        }
+        let limit = Duration::from_secs(secs);
+        let before = self.devices.len();
+        self.devices
+            .retain(|d| d.paired || d.trusted || d.age().map_or(true, |age| age < limit));
+        if self.devices.len() != before {
+            let devices = &self.devices;
+            self.telemetry
+                .retain(|addr| devices.iter().any(|d| &d.address == addr));
+            self.clamp_selection();
+        }
+    }
+
     /// Show a transient popup with timeout tuned to message severity.
     fn show_transient_popup(&mut self, popup: Popup) {
         let notif = &crate::config::get().notifications;
@@ -391,7 +1240,13 @@ impl App {
             Popup::ConnectionResult { success: false, .. } | Popup::Error { .. } => {
                 notif.error_duration_ms
             }
-            Popup::PinDisplay { .. } | Popup::Help => notif.success_duration_ms,
+            Popup::PinDisplay { .. }
+            | Popup::Help
+            | Popup::DiscoveryFilter(_)
+            | Popup::Profiles(_)
+            | Popup::AdapterSettings(_)
+            | Popup::AdminPolicy { .. }
+            | Popup::Transfers { .. } => notif.success_duration_ms,
         };
 
         self.active_popup = Some(popup);
@@ -417,8 +1272,16 @@ impl App {
                 });
             }
             SortMode::Address => {
-                self.devices.sort_by(|a, b| a.address.cmp(&b.address));
+                self.devices.sort_by_key(|d| d.address);
+            }
+            SortMode::LastSeen => {
+                // Most recently seen first; never-seen devices last.
+                self.devices
+                    .sort_by_key(|d| d.age().unwrap_or(Duration::MAX));
             }
+            SortMode::Type => self.devices.sort_by_cached_key(|d| {
+                (d.kind.category(), d.kind, d.display_name().to_lowercase())
+            }),
         }
     }
 
@@ -432,6 +1295,13 @@ impl App {
             InputMode::Search => self.handle_search_key(key),
             InputMode::Dialog => self.handle_dialog_key(key),
             InputMode::Rename => self.handle_rename_key(key),
+            InputMode::FilterEdit => self.handle_filter_key(key),
+            InputMode::Profiles => self.handle_profiles_key(key),
+            InputMode::AdapterSettings => self.handle_adapter_key(key),
+            InputMode::AdminPolicy => self.handle_policy_key(key),
+            InputMode::SendFile => self.handle_send_file_key(key),
+            InputMode::Transfers => self.handle_transfers_key(key),
+            InputMode::Gatt => self.handle_gatt_key(key),
         }
     }
 
@@ -473,6 +1343,16 @@ impl App {
                 AppAction::Consumed
             }
 
+            // ── Detail panel scroll ─────────────────────────────────────
+            c if c == kb.scroll_detail_down || c == KeyCode::PageDown => {
+                self.scroll_detail(if c == KeyCode::PageDown { 10 } else { 1 });
+                AppAction::Consumed
+            }
+            c if c == kb.scroll_detail_up || c == KeyCode::PageUp => {
+                self.scroll_detail(if c == KeyCode::PageUp { -10 } else { -1 });
+                AppAction::Consumed
+            }
+
             // ── Search ──────────────────────────────────────────────────
             c if c == kb.search => {
                 self.input_mode = InputMode::Search;
@@ -489,6 +1369,48 @@ impl App {
                 AppAction::Consumed
             }
 
+            // ── Discovery filter dialog ─────────────────────────────────
+            c if c == kb.discovery_filter => {
+                self.active_popup = Some(Popup::DiscoveryFilter(FilterForm::new(
+                    &self.discovery_filter,
+                )));
+                self.input_mode = InputMode::FilterEdit;
+                self.popup_ttl = None;
+                AppAction::Consumed
+            }
+
+            // ── Adapter settings ────────────────────────────────────────
+            c if c == kb.adapter_settings => {
+                self.active_popup = Some(Popup::AdapterSettings(AdapterForm::new(&self.adapter)));
+                self.input_mode = InputMode::AdapterSettings;
+                self.popup_ttl = None;
+                AppAction::Consumed
+            }
+
+            // ── Admin policy ────────────────────────────────────────────
+            c if c == kb.admin_policy => {
+                self.active_popup = Some(Popup::AdminPolicy { cursor: 0 });
+                self.input_mode = InputMode::AdminPolicy;
+                self.popup_ttl = None;
+                AppAction::BtCommand(BtCommand::QueryAdminPolicy)
+            }
+
+            // ── OBEX file transfer ──────────────────────────────────────
+            c if c == kb.send_file => {
+                if let Some(device) = self.selected_device() {
+                    self.send_target = Some(device.address);
+                    self.send_buffer.clear();
+                    self.input_mode = InputMode::SendFile;
+                }
+                AppAction::Consumed
+            }
+            c if c == kb.transfers => {
+                self.active_popup = Some(Popup::Transfers { cursor: 0 });
+                self.input_mode = InputMode::Transfers;
+                self.popup_ttl = None;
+                AppAction::Consumed
+            }
+
             // ── Sort mode cycle ─────────────────────────────────────────
             c if c == kb.cycle_sort => {
                 self.sort_mode = self.sort_mode.next();
@@ -497,6 +1419,31 @@ impl App {
                 AppAction::Consumed
             }
 
+            // ── GATT browser ────────────────────────────────────────────
+            c if c == kb.gatt_browser => self.open_gatt(),
+            c if c == kb.profiles => self.open_profiles(),
+
+            // ── Category filter cycle ───────────────────────────────────
+            c if c == kb.cycle_category => {
+                self.category_filter = match self.category_filter {
+                    None => DeviceCategory::ALL.first().copied(),
+                    Some(cur) => DeviceCategory::ALL
+                        .iter()
+                        .skip_while(|&&c| c != cur)
+                        .nth(1)
+                        .copied(),
+                };
+                self.clamp_selection();
+                AppAction::Consumed
+            }
+
+            // ── Blocked-only filter ─────────────────────────────────────
+            c if c == kb.blocked_filter => {
+                self.blocked_only = !self.blocked_only;
+                self.clamp_selection();
+                AppAction::Consumed
+            }
+
             // ── Rename device ───────────────────────────────────────────
             c if c == kb.rename => {
                 if let Some(device) = self.selected_device() {
@@ -525,9 +1472,28 @@ impl App {
                 }
             }
 
+            c if c == kb.toggle_periodic_scan => {
+                AppAction::BtCommand(BtCommand::SetPeriodicScan(self.periodic_next.is_none()))
+            }
+
+            // ── Scan session export ─────────────────────────────────────
+            c if c == kb.export_session => {
+                self.export_session();
+                AppAction::Consumed
+            }
+
             // ── Device actions ──────────────────────────────────────────
             c if c == kb.connect_toggle => {
-                if let Some(device) = self.selected_device() {
+                if let Some(members) = self.selected_set_members() {
+                    let all_connected = members
+                        .iter()
+                        .all(|a| self.devices.iter().any(|d| d.address == *a && d.connected));
+                    if all_connected {
+                        AppAction::BtCommand(BtCommand::DisconnectSet(members))
+                    } else {
+                        AppAction::BtCommand(BtCommand::ConnectSet(members))
+                    }
+                } else if let Some(device) = self.selected_device() {
                     let addr = device.address;
                     if device.connected {
                         AppAction::BtCommand(BtCommand::Disconnect(addr))
@@ -539,7 +1505,9 @@ impl App {
                 }
             }
             c if c == kb.disconnect => {
-                if let Some(device) = self.selected_device() {
+                if let Some(members) = self.selected_set_members() {
+                    AppAction::BtCommand(BtCommand::DisconnectSet(members))
+                } else if let Some(device) = self.selected_device() {
                     let addr = device.address;
                     AppAction::BtCommand(BtCommand::Disconnect(addr))
                 } else {
@@ -562,8 +1530,74 @@ impl App {
                     AppAction::Consumed
                 }
             }
-            c if c == kb.remove => {
+            c if c == kb.media_play_pause || c == kb.media_next || c == kb.media_previous => {
+                let Some(device) = self.selected_device() else {
+                    return AppAction::Consumed;
+                };
+                let Some(player) = self.media_player(&device.address) else {
+                    let name = device.display_name().to_string();
+                    self.push_error(format!("{name} has no media player"));
+                    return AppAction::Consumed;
+                };
+                let action = if c == kb.media_next {
+                    MediaControl::Next
+                } else if c == kb.media_previous {
+                    MediaControl::Previous
+                } else if player.status == PlaybackStatus::Playing {
+                    MediaControl::Pause
+                } else {
+                    MediaControl::Play
+                };
+                AppAction::BtCommand(BtCommand::MediaControl(player.path.clone(), action))
+            }
+            c if c == kb.volume_up || c == kb.volume_down => {
+                let Some(address) = self.selected_device().map(|d| d.address) else {
+                    return AppAction::Consumed;
+                };
+                let step: i32 = if c == kb.volume_up { 8 } else { -8 };
+                let transport = self
+                    .media_transports
+                    .iter_mut()
+                    .find(|t| t.address == address && t.volume.is_some());
+                match transport {
+                    Some(t) => {
+                        let current = i32::from(t.volume.unwrap_or_default());
+                        let volume = (current + step).clamp(0, i32::from(MAX_VOLUME)) as u16;
+                        // Shown right away; the transport confirms with a signal.
+                        t.volume = Some(volume);
+                        AppAction::BtCommand(BtCommand::SetTransportVolume(t.path.clone(), volume))
+                    }
+                    None => {
+                        self.push_error("No audio transport with absolute volume".into());
+                        AppAction::Consumed
+                    }
+                }
+            }
+            c if c == kb.wake_allowed => match self.selected_device() {
+                Some(device) => match device.wake_allowed {
+                    Some(allowed) => {
+                        AppAction::BtCommand(BtCommand::SetWakeAllowed(device.address, !allowed))
+                    }
+                    None => {
+                        let name = device.display_name().to_string();
+                        self.push_error(format!("{name} can't wake the system"));
+                        AppAction::Consumed
+                    }
+                },
+                None => AppAction::Consumed,
+            },
+            c if c == kb.block => {
                 if let Some(device) = self.selected_device() {
+                    let addr = device.address;
+                    AppAction::BtCommand(BtCommand::SetBlocked(addr, !device.blocked))
+                } else {
+                    AppAction::Consumed
+                }
+            }
+            c if c == kb.remove => {
+                if let Some(members) = self.selected_set_members() {
+                    AppAction::BtCommand(BtCommand::RemoveSet(members))
+                } else if let Some(device) = self.selected_device() {
                     let addr = device.address;
                     AppAction::BtCommand(BtCommand::RemoveDevice(addr))
                 } else {
@@ -663,6 +1697,461 @@ impl App {
         }
     }
 
+    fn handle_send_file_key(&mut self, key: KeyEvent) -> AppAction {
+        match key.code {
+            KeyCode::Esc => {
+                self.input_mode = InputMode::Normal;
+                self.send_buffer.clear();
+                self.send_target = None;
+                AppAction::Consumed
+            }
+            KeyCode::Enter => {
+                self.input_mode = InputMode::Normal;
+                let path = expand_home(self.send_buffer.trim());
+                self.send_buffer.clear();
+                let Some(addr) = self.send_target.take() else {
+                    return AppAction::Consumed;
+                };
+                // obexd opens the file from its own working directory, so
+                // it must get an absolute path.
+                let path = match std::fs::canonicalize(&path) {
+                    Ok(absolute) if absolute.is_file() => absolute,
+                    _ => {
+                        self.push_error(format!("Not a file: {}", path.display()));
+                        return AppAction::Consumed;
+                    }
+                };
+                let file = path.file_name().unwrap_or_default().to_string_lossy();
+                let message = format!("{file} → {}", self.device_name(&addr));
+                self.push_toast("Sending file", message, ToastLevel::Info);
+                AppAction::BtCommand(BtCommand::SendFile(addr, path))
+            }
+            KeyCode::Tab => {
+                if let Some(completed) = complete_path(&self.send_buffer) {
+                    self.send_buffer = completed;
+                }
+                AppAction::Consumed
+            }
+            KeyCode::Backspace => {
+                self.send_buffer.pop();
+                AppAction::Consumed
+            }
+            KeyCode::Char(c) => {
+                self.send_buffer.push(c);
+                AppAction::Consumed
+            }
+            _ => AppAction::Consumed,
+        }
+    }
+
+    fn handle_transfers_key(&mut self, key: KeyEvent) -> AppAction {
+        let kb = &crate::config::get().keys;
+        let Some(Popup::Transfers { cursor }) = &mut self.active_popup else {
+            self.input_mode = InputMode::Normal;
+            return AppAction::Consumed;
+        };
+
+        match key.code {
+            c if c == KeyCode::Esc || c == kb.quit || c == kb.transfers => {
+                self.active_popup = None;
+                self.input_mode = InputMode::Normal;
+            }
+            c if c == kb.nav_down || c == KeyCode::Down => {
+                *cursor = (*cursor + 1).min(self.transfers.len().saturating_sub(1));
+            }
+            c if c == kb.nav_up || c == KeyCode::Up => {
+                *cursor = cursor.saturating_sub(1);
+            }
+            KeyCode::Char('x') => {
+                if let Some(transfer) = self.transfers.get(*cursor) {
+                    if !transfer.status.is_finished() {
+                        return AppAction::BtCommand(BtCommand::CancelTransfer(
+                            transfer.id.clone(),
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+        AppAction::Consumed
+    }
+
+    fn handle_filter_key(&mut self, key: KeyEvent) -> AppAction {
+        let Some(Popup::DiscoveryFilter(form)) = &mut self.active_popup else {
+            self.input_mode = InputMode::Normal;
+            return AppAction::Consumed;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.active_popup = None;
+                self.input_mode = InputMode::Normal;
+                AppAction::Consumed
+            }
+            KeyCode::Enter => match form.build() {
+                Ok(filter) => {
+                    self.discovery_filter = filter.clone();
+                    self.active_popup = None;
+                    self.input_mode = InputMode::Normal;
+                    AppAction::BtCommand(BtCommand::SetDiscoveryFilter(filter))
+                }
+                Err(e) => {
+                    form.error = e;
+                    AppAction::Consumed
+                }
+            },
+            KeyCode::Down | KeyCode::Tab => {
+                form.field = form.field.next();
+                AppAction::Consumed
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                form.field = form.field.prev();
+                AppAction::Consumed
+            }
+            KeyCode::Left => {
+                form.adjust(-1);
+                AppAction::Consumed
+            }
+            KeyCode::Right => {
+                form.adjust(1);
+                AppAction::Consumed
+            }
+            KeyCode::Backspace | KeyCode::Delete => {
+                form.clear();
+                form.error.clear();
+                AppAction::Consumed
+            }
+            KeyCode::Char(c) if form.field == FilterField::Uuids => {
+                form.uuid_input.push(c);
+                form.error.clear();
+                AppAction::Consumed
+            }
+            KeyCode::Char(' ') => {
+                form.adjust(1);
+                AppAction::Consumed
+            }
+            _ => AppAction::Consumed,
+        }
+    }
+
+    fn handle_adapter_key(&mut self, key: KeyEvent) -> AppAction {
+        let Some(Popup::AdapterSettings(form)) = &mut self.active_popup else {
+            self.input_mode = InputMode::Normal;
+            return AppAction::Consumed;
+        };
+
+        let setting = match key.code {
+            KeyCode::Esc => {
+                self.active_popup = None;
+                self.input_mode = InputMode::Normal;
+                None
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                form.field = form.field.next();
+                None
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                form.field = form.field.prev();
+                None
+            }
+            KeyCode::Enter if form.field == AdapterField::Alias => {
+                Some(AdapterSetting::Alias(form.alias_input.trim().to_string()))
+            }
+            KeyCode::Backspace if form.field == AdapterField::Alias => {
+                form.alias_input.pop();
+                None
+            }
+            KeyCode::Char(c) if form.field == AdapterField::Alias => {
+                form.alias_input.push(c);
+                None
+            }
+            KeyCode::Left => form.adjust(&self.adapter, -1),
+            KeyCode::Right | KeyCode::Enter | KeyCode::Char(' ') => form.adjust(&self.adapter, 1),
+            _ => None,
+        };
+        match setting {
+            Some(setting) => AppAction::BtCommand(BtCommand::SetAdapter(setting)),
+            None => AppAction::Consumed,
+        }
+    }
+
+    fn handle_policy_key(&mut self, key: KeyEvent) -> AppAction {
+        let kb = &crate::config::get().keys;
+        let presets = &crate::config::get().admin_policy.presets;
+        let Some(Popup::AdminPolicy { cursor }) = &mut self.active_popup else {
+            self.input_mode = InputMode::Normal;
+            return AppAction::Consumed;
+        };
+
+        match key.code {
+            c if c == KeyCode::Esc || c == kb.quit || c == kb.admin_policy => {
+                self.active_popup = None;
+                self.input_mode = InputMode::Normal;
+            }
+            c if c == kb.nav_down || c == KeyCode::Down => {
+                *cursor = (*cursor + 1).min(presets.len());
+            }
+            c if c == kb.nav_up || c == KeyCode::Up => {
+                *cursor = cursor.saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                let uuids = match cursor.checked_sub(1) {
+                    Some(i) => presets[i].uuids.clone(),
+                    None => Vec::new(),
+                };
+                return AppAction::BtCommand(BtCommand::SetServiceAllowList(uuids));
+            }
+            c if c == kb.refresh => {
+                return AppAction::BtCommand(BtCommand::QueryAdminPolicy);
+            }
+            _ => {}
+        }
+        AppAction::Consumed
+    }
+
+    /// Open the profile picker for the selected device and ask the worker
+    /// for its profiles.
+    fn open_profiles(&mut self) -> AppAction {
+        let Some(device) = self.selected_device() else {
+            return AppAction::Consumed;
+        };
+        let address = device.address;
+        let picker = ProfilePicker::new(address, device.display_name().to_string());
+        self.active_popup = Some(Popup::Profiles(picker));
+        self.input_mode = InputMode::Profiles;
+        self.popup_ttl = None;
+        AppAction::BtCommand(BtCommand::QueryProfiles(address))
+    }
+
+    fn handle_profiles_key(&mut self, key: KeyEvent) -> AppAction {
+        let kb = &crate::config::get().keys;
+        let Some(Popup::Profiles(picker)) = &mut self.active_popup else {
+            self.input_mode = InputMode::Normal;
+            return AppAction::Consumed;
+        };
+
+        match key.code {
+            c if c == KeyCode::Esc || c == kb.quit || c == kb.profiles => {
+                self.active_popup = None;
+                self.input_mode = InputMode::Normal;
+            }
+            c if c == kb.nav_down || c == KeyCode::Down => picker.move_cursor(1),
+            c if c == kb.nav_up || c == KeyCode::Up => picker.move_cursor(-1),
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                if let Some(cmd) = picker.toggle() {
+                    return AppAction::BtCommand(cmd);
+                }
+            }
+            c if c == kb.refresh => {
+                return AppAction::BtCommand(BtCommand::QueryProfiles(picker.address));
+            }
+            _ => {}
+        }
+        AppAction::Consumed
+    }
+
+    /// Open the GATT browser for the selected device and ask the worker to
+    /// enumerate its database.
+    fn open_gatt(&mut self) -> AppAction {
+        let Some(device) = self.selected_device() else {
+            return AppAction::Consumed;
+        };
+        if !device.connected {
+            self.show_transient_popup(Popup::Error {
+                message: format!(
+                    "Connect {} to browse its GATT services",
+                    device.display_name()
+                ),
+                slide: 0.0,
+            });
+            return AppAction::Consumed;
+        }
+        let address = device.address;
+        let mut browser = GattBrowser::new(address, device.display_name().to_string());
+        if let Some(services) = gatt_export::load_cache(address) {
+            browser.set_cached(services);
+        }
+        self.gatt = Some(browser);
+        self.input_mode = InputMode::Gatt;
+        AppAction::BtCommand(BtCommand::DiscoverGatt(address))
+    }
+
+    /// Close the GATT browser, ending its notification sessions.
+    fn close_gatt(&mut self) -> AppAction {
+        let browser = self.gatt.take();
+        if self.input_mode == InputMode::Gatt {
+            self.input_mode = InputMode::Normal;
+        }
+        match browser {
+            Some(b) if b.any_notifying() => {
+                AppAction::BtCommand(BtCommand::GattUnsubscribeAll(b.address))
+            }
+            _ => AppAction::Consumed,
+        }
+    }
+
+    fn handle_gatt_key(&mut self, key: KeyEvent) -> AppAction {
+        let kb = &crate::config::get().keys;
+        let Some(browser) = self.gatt.as_mut() else {
+            self.input_mode = InputMode::Normal;
+            return AppAction::Consumed;
+        };
+
+        if browser.write.is_some() {
+            return Self::handle_gatt_write_key(browser, key);
+        }
+        if !matches!(browser.view, GattView::Tree) {
+            Self::handle_gatt_compare_key(browser, key);
+            return AppAction::Consumed;
+        }
+
+        match key.code {
+            KeyCode::Esc => return self.close_gatt(),
+            c if c == kb.gatt_browser || c == kb.quit => return self.close_gatt(),
+            KeyCode::Char('r') => {
+                if let Some((target, characteristic)) = browser.selected_characteristic() {
+                    if characteristic.props.read {
+                        return AppAction::BtCommand(BtCommand::GattRead(target));
+                    }
+                    let handle = target.characteristic;
+                    browser.push_log(handle, LogKind::Error, "Not readable".into());
+                }
+            }
+            KeyCode::Char('w') => {
+                if let Some((target, characteristic)) = browser.selected_characteristic() {
+                    let props = characteristic.props;
+                    if props.write || props.write_without_response {
+                        browser.write = Some(WriteForm {
+                            target,
+                            input: String::new(),
+                            format: WriteFormat::Hex,
+                            with_response: props.write,
+                            error: None,
+                        });
+                    } else {
+                        let handle = target.characteristic;
+                        browser.push_log(handle, LogKind::Error, "Not writable".into());
+                    }
+                }
+            }
+            KeyCode::Char('n') => {
+                if let Some((target, characteristic)) = browser.selected_characteristic() {
+                    let props = characteristic.props;
+                    let handle = target.characteristic;
+                    if browser.is_notifying(handle) {
+                        return AppAction::BtCommand(BtCommand::GattUnsubscribe(target));
+                    }
+                    if props.notify || props.indicate {
+                        return AppAction::BtCommand(BtCommand::GattSubscribe(target));
+                    }
+                    browser.push_log(handle, LogKind::Error, "Notify not supported".into());
+                }
+            }
+            KeyCode::Char('x') => {
+                if let Some((target, _)) = browser.selected_characteristic() {
+                    browser.clear_log(target.characteristic);
+                }
+            }
+            c if c == kb.export_session && browser.services.is_some() && !browser.exporting => {
+                browser.exporting = true;
+                return AppAction::BtCommand(BtCommand::SnapshotGatt(browser.address));
+            }
+            KeyCode::Char('D') => browser.open_picker(),
+            c if c == kb.nav_down || c == KeyCode::Down => browser.move_cursor(1),
+            c if c == kb.nav_up || c == KeyCode::Up => browser.move_cursor(-1),
+            KeyCode::PageDown => browser.move_cursor(10),
+            KeyCode::PageUp => browser.move_cursor(-10),
+            c if c == kb.jump_top => browser.jump(false),
+            c if c == kb.jump_bottom => browser.jump(true),
+            KeyCode::Enter | KeyCode::Char(' ') => browser.toggle(),
+            KeyCode::Right | KeyCode::Char('l') => browser.expand(),
+            KeyCode::Left | KeyCode::Char('h') => browser.collapse(),
+            KeyCode::Char('E') => browser.toggle_all(),
+            c if c == kb.refresh => {
+                // The worker drops the device's subscriptions before
+                // re-enumerating; the value logs go with them.
+                browser.reload();
+                return AppAction::BtCommand(BtCommand::DiscoverGatt(browser.address));
+            }
+            _ => {}
+        }
+        AppAction::Consumed
+    }
+
+    /// Keys in the export picker and comparison views.
+    fn handle_gatt_compare_key(browser: &mut GattBrowser, key: KeyEvent) {
+        let kb = &crate::config::get().keys;
+        let back = key.code == KeyCode::Esc || key.code == kb.quit;
+        match &mut browser.view {
+            GattView::Tree => {}
+            GattView::Picker(_) if back => browser.view = GattView::Tree,
+            GattView::Picker(picker) => match key.code {
+                c if c == kb.nav_down || c == KeyCode::Down => picker.move_cursor(1),
+                c if c == kb.nav_up || c == KeyCode::Up => picker.move_cursor(-1),
+                KeyCode::Char(' ') => picker.toggle_base(),
+                KeyCode::Enter => browser.compare_selected(),
+                _ => {}
+            },
+            // Back to the picker to try another pair.
+            GattView::Compare(_) if back => browser.open_picker(),
+            GattView::Compare(view) => match key.code {
+                c if c == kb.nav_down || c == KeyCode::Down => view.scroll_by(1),
+                c if c == kb.nav_up || c == KeyCode::Up => view.scroll_by(-1),
+                KeyCode::PageDown => view.scroll_by(10),
+                KeyCode::PageUp => view.scroll_by(-10),
+                c if c == kb.jump_top => view.scroll = 0,
+                c if c == kb.jump_bottom => view.scroll_by(isize::MAX),
+                KeyCode::Char('c') => {
+                    view.changes_only = !view.changes_only;
+                    view.scroll = 0;
+                }
+                _ => {}
+            },
+        }
+    }
+
+    /// Keys while the characteristic write form is open.
+    fn handle_gatt_write_key(browser: &mut GattBrowser, key: KeyEvent) -> AppAction {
+        let Some(form) = browser.write.as_mut() else {
+            return AppAction::Consumed;
+        };
+        match key.code {
+            KeyCode::Esc => browser.write = None,
+            KeyCode::Enter => match form.format.encode(&form.input) {
+                Ok(value) if value.is_empty() => form.error = Some("Nothing to write".into()),
+                Ok(value) => {
+                    let (target, with_response) = (form.target, form.with_response);
+                    browser.write = None;
+                    let text = format!(
+                        "{} {}",
+                        if with_response { "Write" } else { "Write cmd" },
+                        crate::bluetooth::presentation::format_value(None, &value)
+                    );
+                    browser.push_log(target.characteristic, LogKind::Write, text);
+                    return AppAction::BtCommand(BtCommand::GattWrite {
+                        target,
+                        value,
+                        with_response,
+                    });
+                }
+                Err(e) => form.error = Some(e),
+            },
+            KeyCode::Up => form.cycle_format(-1),
+            KeyCode::Down => form.cycle_format(1),
+            KeyCode::Tab => form.with_response = !form.with_response,
+            KeyCode::Backspace => {
+                form.input.pop();
+                form.error = None;
+            }
+            KeyCode::Char(c) => {
+                form.input.push(c);
+                form.error = None;
+            }
+            _ => {}
+        }
+        AppAction::Consumed
+    }
+
     /// Validate the current search query as regex and store any error.
     fn validate_search_regex(&mut self) {
         let mode = crate::config::get().general.search_mode;
@@ -690,3 +2179,75 @@ impl App {
         }
     }
 }
+
+/// Toast / notification title for watchlist rule `rule`.
+fn watch_label(rule: usize) -> String {
+    let label = crate::config::get()
+        .watchlist
+        .rules
+        .get(rule)
+        .map_or("?", |r| r.label.as_str());
+    format!("Watch: {label}")
+}
+
+/// Expand a leading `~` to the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    let home = || directories::BaseDirs::new().map(|d| d.home_dir().to_path_buf());
+    match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => match home() {
+            Some(home) => home.join(rest.trim_start_matches('/')),
+            None => PathBuf::from(path),
+        },
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Tab completion for a typed path: extend it to the longest prefix shared
+/// by the matching directory entries, with a trailing `/` for a directory.
+fn complete_path(typed: &str) -> Option<String> {
+    let (dir, partial) = match typed.rfind('/') {
+        Some(i) => (&typed[..=i], &typed[i + 1..]),
+        None => ("", typed),
+    };
+    let listing = if dir.is_empty() {
+        PathBuf::from(".")
+    } else {
+        expand_home(dir)
+    };
+    let mut matches: Vec<(String, bool)> = std::fs::read_dir(listing)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            // Hidden entries only when asked for.
+            let wanted =
+                name.starts_with(partial) && (partial.starts_with('.') || !name.starts_with('.'));
+            wanted.then(|| (name, entry.path().is_dir()))
+        })
+        .collect();
+    matches.sort();
+    let (first, is_dir) = matches.first()?;
+    let common = matches.iter().fold(first.clone(), |common, (name, _)| {
+        common
+            .chars()
+            .zip(name.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a)
+            .collect()
+    });
+    let slash = if matches.len() == 1 && *is_dir {
+        "/"
+    } else {
+        ""
+    };
+    Some(format!("{dir}{common}{slash}"))
+}
+
+/// Company and service names resolved from a device's advertisement, so
+/// search can match e.g. "Apple" or "Heart Rate".
+fn resolved_names(d: &DeviceInfo) -> impl Iterator<Item = &'static str> + '_ {
+    d.manufacturer_data
+        .keys()
+        .filter_map(|&id| assigned_numbers::company_name(id))
+        .chain(d.uuids.iter().filter_map(assigned_numbers::uuid_name))
+}
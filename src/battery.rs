@@ -0,0 +1,98 @@
+//! Low-battery alerting.
+//!
+//! Each device carries an armed level. An alert fires once when the battery
+//! drops into a worse level than the one already alerted; the level is only
+//! re-armed after the device recharges `rearm_margin` points above the
+//! threshold, so a reading that jitters around 20% doesn't alert repeatedly.
+
+use std::collections::HashMap;
+
+use crate::bluetooth::types::{Address, DeviceInfo};
+use crate::config::{self, BatteryThresholds};
+
+/// Severity of a battery reading against the device's thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum BatteryLevel {
+    #[default]
+    Normal,
+    Low,
+    Critical,
+}
+
+impl BatteryLevel {
+    fn classify(pct: u8, t: BatteryThresholds) -> Self {
+        if pct <= t.critical {
+            Self::Critical
+        } else if pct <= t.low {
+            Self::Low
+        } else {
+            Self::Normal
+        }
+    }
+}
+
+/// A threshold crossing that should be surfaced to the user.
+#[derive(Debug, Clone)]
+pub struct BatteryAlert {
+    pub name: String,
+    pub percent: u8,
+    pub level: BatteryLevel,
+}
+
+/// Tracks the current and alerted battery level of every device.
+#[derive(Debug, Default)]
+pub struct BatteryMonitor {
+    /// Level of the latest reading, for badges.
+    current: HashMap<Address, BatteryLevel>,
+    /// Worst level already alerted and not yet re-armed.
+    alerted: HashMap<Address, BatteryLevel>,
+}
+
+impl BatteryMonitor {
+    /// Feed a device snapshot; returns an alert when a threshold is crossed.
+    pub fn observe(&mut self, info: &DeviceInfo) -> Option<BatteryAlert> {
+        let pct = info.battery?;
+        let cfg = &config::get().battery;
+        let thresholds = cfg.thresholds_for(&info.address);
+
+        let level = BatteryLevel::classify(pct, thresholds);
+        self.current.insert(info.address, level);
+
+        // Re-arm once the reading clears a threshold by `rearm_margin`.
+        let rearmed = BatteryLevel::classify(pct.saturating_sub(cfg.rearm_margin), thresholds);
+        let alerted = self.alerted.entry(info.address).or_default();
+        *alerted = (*alerted).min(rearmed);
+
+        if level > *alerted {
+            *alerted = level;
+            return Some(BatteryAlert {
+                name: info.display_name().to_string(),
+                percent: pct,
+                level,
+            });
+        }
+        None
+    }
+
+    /// Level of the device's latest battery reading.
+    pub fn level(&self, address: &Address) -> BatteryLevel {
+        self.current.get(address).copied().unwrap_or_default()
+    }
+
+    /// Number of devices currently at `Low` or worse, and whether any is
+    /// critical.
+    pub fn summary(&self) -> (usize, bool) {
+        let low = self
+            .current
+            .values()
+            .filter(|&&l| l > BatteryLevel::Normal)
+            .count();
+        let critical = self.current.values().any(|&l| l == BatteryLevel::Critical);
+        (low, critical)
+    }
+
+    pub fn forget(&mut self, address: &Address) {
+        self.current.remove(address);
+        self.alerted.remove(address);
+    }
+}
@@ -0,0 +1,64 @@
+//! BlueZ admin policy: the adapter's service allowlist.
+//!
+//! `AdminPolicySet1.SetServiceAllowList` restricts the services an adapter
+//! accepts (an empty list allows everything); `AdminPolicyStatus1` reports
+//! the list in effect and, per device, whether the policy blocks any of its
+//! services. Both come from bluetoothd's admin plugin, which bluer doesn't
+//! wrap, so they go over [`BluezBus`].
+
+use std::collections::HashSet;
+
+use bluer::{Address, Uuid};
+
+use super::bluez_dbus::{self, BluezBus};
+
+const SET: &str = "org.bluez.AdminPolicySet1";
+const STATUS: &str = "org.bluez.AdminPolicyStatus1";
+
+/// Admin policy state of the adapter.
+#[derive(Debug, Clone, Default)]
+pub struct AdminPolicy {
+    /// Allowed services (empty = all). `None` when bluetoothd has no admin
+    /// policy support.
+    pub allowlist: Option<Vec<Uuid>>,
+    /// Devices with services blocked by the policy.
+    pub affected: HashSet<Address>,
+}
+
+/// Read the allowlist and the affected devices of `adapter`.
+pub async fn query(bus: &BluezBus, adapter: &str) -> Result<AdminPolicy, dbus::Error> {
+    let objects = bus.managed_objects().await?;
+    let adapter_path = bluez_dbus::adapter_path(adapter);
+    let mut policy = AdminPolicy::default();
+
+    for (path, interfaces) in &objects {
+        let Some(status) = interfaces.get(STATUS) else {
+            continue;
+        };
+        if **path == *adapter_path {
+            let mut uuids: Vec<Uuid> = bluez_dbus::prop_strs(status, "ServiceAllowList")
+                .iter()
+                .filter_map(|u| u.parse().ok())
+                .collect();
+            uuids.sort();
+            policy.allowlist = Some(uuids);
+        } else if bluez_dbus::prop_bool(status, "IsAffectedByPolicy") == Some(true) {
+            if let Some(address) = bluez_dbus::device_address(path) {
+                policy.affected.insert(address);
+            }
+        }
+    }
+    Ok(policy)
+}
+
+/// Replace the service allowlist of `adapter` (empty = allow all).
+pub async fn set_allowlist(
+    bus: &BluezBus,
+    adapter: &str,
+    uuids: &[Uuid],
+) -> Result<(), dbus::Error> {
+    let uuids: Vec<String> = uuids.iter().map(Uuid::to_string).collect();
+    bus.proxy(bluez_dbus::adapter_path(adapter))
+        .method_call(SET, "SetServiceAllowList", (uuids,))
+        .await
+}
@@ -0,0 +1,51 @@
+//! Bluetooth SIG assigned numbers used to label raw identifiers: 16-bit
+//! UUIDs (services, characteristics, descriptors, service classes), company
+//! identifiers and GAP appearance values.
+//!
+//! The tables live in `table.rs`, generated by
+//! `scripts/gen_assigned_numbers.py` from a trimmed copy of the SIG's public
+//! `assigned_numbers` YAML kept in `scripts/assigned_numbers/`.
+
+use bluer::UuidExt;
+
+use super::types::{short_uuid, Uuid};
+
+#[rustfmt::skip]
+mod table;
+
+/// Binary search a sorted `(key, name)` table.
+fn lookup(table: &'static [(u16, &'static str)], key: u16) -> Option<&'static str> {
+    table
+        .binary_search_by_key(&key, |&(k, _)| k)
+        .ok()
+        .map(|i| table[i].1)
+}
+
+/// Company name for a manufacturer-data company identifier.
+pub fn company_name(id: u16) -> Option<&'static str> {
+    lookup(table::COMPANIES, id)
+}
+
+/// Name of a SIG-assigned 16-bit UUID (service, characteristic, descriptor,
+/// service class or protocol). Vendor 128-bit UUIDs have no name.
+pub fn uuid_name(uuid: &Uuid) -> Option<&'static str> {
+    lookup(table::UUID16, uuid.as_u16()?)
+}
+
+/// `Battery (0x180F)`, or just the short form for unknown UUIDs.
+pub fn describe_uuid(uuid: &Uuid) -> String {
+    match uuid_name(uuid) {
+        Some(name) => format!("{name} ({})", short_uuid(uuid)),
+        None => short_uuid(uuid),
+    }
+}
+
+/// GAP appearance as `Category: Subcategory` (or just the category).
+/// The upper 10 bits are the category, the lower 6 the subcategory.
+pub fn appearance_name(value: u16) -> Option<String> {
+    let category = lookup(table::APPEARANCE_CATEGORIES, value >> 6)?;
+    Some(match lookup(table::APPEARANCE_SUBCATEGORIES, value) {
+        Some(sub) if value & 0x3F != 0 => format!("{category}: {sub}"),
+        _ => category.to_string(),
+    })
+}
@@ -0,0 +1,109 @@
+//! Device blocklist rollout.
+//!
+//! The `[blocklist]` config section lists devices to block; the worker
+//! enforces it whenever a listed device appears. `voidlink blocklist export`
+//! prints the devices BlueZ currently blocks as such a section, and
+//! `voidlink blocklist import FILE` blocks every device listed in a config
+//! file (or an exported section) right away.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use bluer::{Adapter, Device, Session};
+use color_eyre::eyre::WrapErr;
+use color_eyre::Result;
+use tracing::{info, warn};
+
+/// Block a device listed in the config `[blocklist]` that BlueZ doesn't
+/// block yet.
+pub async fn enforce(device: &Device) {
+    let addr = device.address();
+    if !crate::config::get().blocklist.addresses.contains(&addr)
+        || device.is_blocked().await.unwrap_or(true)
+    {
+        return;
+    }
+    match device.set_blocked(true).await {
+        Ok(()) => info!("Blocked {addr} (config blocklist)"),
+        Err(e) => warn!("Cannot block {addr} from the config blocklist: {e}"),
+    }
+}
+
+async fn default_adapter() -> Result<Adapter> {
+    let session = Session::new()
+        .await
+        .wrap_err("Failed to connect to BlueZ D-Bus")?;
+    session
+        .default_adapter()
+        .await
+        .wrap_err("No Bluetooth adapter found")
+}
+
+/// Print (or write to `path`) a `[blocklist]` section of the blocked
+/// devices, with their aliases as comments.
+pub async fn export_cli(path: Option<&Path>) -> Result<()> {
+    let adapter = default_adapter().await?;
+    let mut addrs = adapter.device_addresses().await?;
+    addrs.sort();
+
+    let mut out = String::from("[blocklist]\naddresses = [\n");
+    let mut count = 0;
+    for addr in addrs {
+        let device = adapter.device(addr)?;
+        if !device.is_blocked().await.unwrap_or(false) {
+            continue;
+        }
+        let alias = device.alias().await.unwrap_or_default();
+        let _ = writeln!(out, "  \"{addr}\", # {alias}");
+        count += 1;
+    }
+    out.push_str("]\n");
+
+    match path {
+        Some(path) => {
+            std::fs::write(path, out)
+                .wrap_err_with(|| format!("Cannot write {}", path.display()))?;
+            eprintln!("Exported {count} blocked device(s) to {}", path.display());
+        }
+        None => print!("{out}"),
+    }
+    Ok(())
+}
+
+/// Block every device in the `[blocklist]` of `path`. Returns `false` if
+/// any device could not be blocked.
+pub async fn import_cli(path: &Path) -> Result<bool> {
+    let blocklist = crate::config::load_blocklist(path)?;
+    let adapter = default_adapter().await?;
+    let known = adapter.device_addresses().await?;
+
+    let mut addrs: Vec<_> = blocklist.addresses.into_iter().collect();
+    addrs.sort();
+    let (mut blocked, mut unknown, mut failed) = (0, 0, 0);
+    for addr in addrs {
+        if !known.contains(&addr) {
+            println!("{addr}  not known to BlueZ yet");
+            unknown += 1;
+            continue;
+        }
+        match adapter.device(addr)?.set_blocked(true).await {
+            Ok(()) => {
+                println!("{addr}  blocked");
+                blocked += 1;
+            }
+            Err(e) => {
+                println!("{addr}  failed: {e}");
+                failed += 1;
+            }
+        }
+    }
+
+    println!("\n{blocked} blocked, {unknown} unknown, {failed} failed");
+    if unknown > 0 {
+        println!(
+            "Unknown devices are blocked when they appear while VoidLink runs with this \
+             [blocklist] in its config."
+        );
+    }
+    Ok(failed == 0)
+}
@@ -0,0 +1,169 @@
+//! Direct D-Bus access to BlueZ interfaces bluer doesn't wrap.
+//!
+//! A second system bus connection, driven by dbus-tokio, used for read-only
+//! object queries (`GetManagedObjects`), the occasional method call and
+//! change signals of interfaces bluer doesn't watch.
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use bluer::Address;
+use dbus::arg::{PropMap, RefArg};
+use dbus::message::{MatchRule, MessageType};
+use dbus::nonblock::stdintf::org_freedesktop_dbus::{ObjectManager, Properties};
+use dbus::nonblock::{Proxy, SyncConnection};
+use dbus::{Message, Path};
+use futures::{Stream, StreamExt};
+use tracing::warn;
+
+pub const SERVICE: &str = "org.bluez";
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Interfaces and their properties, per object path.
+pub type ManagedObjects = HashMap<Path<'static>, HashMap<String, PropMap>>;
+
+/// Ticks whenever a watched interface changes, appears or disappears.
+pub type ChangeStream = Pin<Box<dyn Stream<Item = ()> + Send>>;
+
+static SHARED: OnceLock<Option<BluezBus>> = OnceLock::new();
+
+/// The worker's connection, opened on first use from within the runtime.
+/// `None` when the system bus is unreachable.
+pub fn shared() -> Option<&'static BluezBus> {
+    SHARED
+        .get_or_init(|| {
+            BluezBus::connect()
+                .map_err(|e| warn!("Raw BlueZ D-Bus access unavailable: {e}"))
+                .ok()
+        })
+        .as_ref()
+}
+
+pub struct BluezBus {
+    conn: Arc<SyncConnection>,
+    /// Bus name BlueZ is reached at; [`SERVICE`] outside tests.
+    service: String,
+}
+
+impl BluezBus {
+    /// Open the connection and spawn its I/O driver on the current runtime.
+    pub fn connect() -> Result<Self, dbus::Error> {
+        let (resource, conn) = dbus_tokio::connection::new_system_sync()?;
+        tokio::spawn(async move {
+            let err = resource.await;
+            warn!("BlueZ D-Bus connection lost: {err}");
+        });
+        Ok(Self::new(conn, SERVICE))
+    }
+
+    /// Talk to `service` over an already driven connection.
+    pub fn new(conn: Arc<SyncConnection>, service: &str) -> Self {
+        // Every signal stream sees every message it matches, even when the
+        // match rules of several streams overlap.
+        conn.set_signal_match_mode(true);
+        Self {
+            conn,
+            service: service.to_string(),
+        }
+    }
+
+    pub fn proxy<'a>(&'a self, path: impl Into<Path<'a>>) -> Proxy<'a, Arc<SyncConnection>> {
+        Proxy::new(self.service.as_str(), path, TIMEOUT, self.conn.clone())
+    }
+
+    pub async fn managed_objects(&self) -> Result<ManagedObjects, dbus::Error> {
+        self.proxy("/").get_managed_objects().await
+    }
+
+    /// Changes to objects implementing any of `interfaces`: property
+    /// changes and the interface being added or removed.
+    pub async fn interface_changes(
+        &self,
+        interfaces: &'static [&'static str],
+    ) -> Result<ChangeStream, dbus::Error> {
+        let mut streams = Vec::new();
+        for interface in [
+            "org.freedesktop.DBus.Properties",
+            "org.freedesktop.DBus.ObjectManager",
+        ] {
+            let rule = MatchRule::new()
+                .with_type(MessageType::Signal)
+                .with_sender(self.service.clone())
+                .with_interface(interface);
+            let (_, stream) = self.conn.add_match(rule).await?.msg_stream();
+            streams.push(stream);
+        }
+        let changes = futures::stream::select_all(streams)
+            .filter(move |msg| std::future::ready(concerns(msg, interfaces)))
+            .map(|_| ());
+        Ok(Box::pin(changes))
+    }
+
+    /// `Adapter1.Roles` (`central`, `peripheral`, `central-peripheral`),
+    /// which bluer doesn't expose.
+    pub async fn adapter_roles(&self, adapter: &str) -> Result<Vec<String>, dbus::Error> {
+        self.proxy(adapter_path(adapter))
+            .get("org.bluez.Adapter1", "Roles")
+            .await
+    }
+}
+
+/// Whether a `PropertiesChanged`, `InterfacesAdded` or `InterfacesRemoved`
+/// signal is about one of `interfaces`.
+fn concerns(msg: &Message, interfaces: &[&str]) -> bool {
+    match msg.member().as_deref() {
+        Some("PropertiesChanged") => msg
+            .get1::<&str>()
+            .is_some_and(|iface| interfaces.contains(&iface)),
+        Some("InterfacesAdded") => msg
+            .read2::<Path, HashMap<String, PropMap>>()
+            .is_ok_and(|(_, added)| interfaces.iter().any(|i| added.contains_key(*i))),
+        Some("InterfacesRemoved") => msg
+            .read2::<Path, Vec<String>>()
+            .is_ok_and(|(_, removed)| removed.iter().any(|i| interfaces.contains(&i.as_str()))),
+        _ => false,
+    }
+}
+
+/// Object path of an adapter: `/org/bluez/hci0`.
+pub fn adapter_path(adapter: &str) -> String {
+    format!("/org/bluez/{adapter}")
+}
+
+/// Object path of a device: `/org/bluez/hci0/dev_AA_BB_CC_DD_EE_FF`.
+pub fn device_path(adapter: &str, address: Address) -> String {
+    format!(
+        "{}/dev_{}",
+        adapter_path(adapter),
+        address.to_string().replace(':', "_")
+    )
+}
+
+/// Address of a device object path (`…/dev_AA_BB_CC_DD_EE_FF`).
+pub fn device_address(path: &str) -> Option<Address> {
+    let (_, dev) = path.rsplit_once("/dev_")?;
+    dev.replace('_', ":").parse().ok()
+}
+
+pub fn prop_str<'a>(props: &'a PropMap, key: &str) -> Option<&'a str> {
+    props.get(key)?.0.as_str()
+}
+
+/// A string array property; empty when missing.
+pub fn prop_strs(props: &PropMap, key: &str) -> Vec<String> {
+    props
+        .get(key)
+        .and_then(|v| v.0.as_iter())
+        .map(|items| {
+            items
+                .filter_map(|i| i.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn prop_bool(props: &PropMap, key: &str) -> Option<bool> {
+    props.get(key)?.0.as_i64().map(|v| v != 0)
+}
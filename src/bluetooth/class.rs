@@ -0,0 +1,266 @@
+//! Class of Device (CoD) decoder for BR/EDR devices.
+//!
+//! The 24-bit value packs, from the bottom up: 2 format bits, a 6-bit minor
+//! class (whose meaning depends on the major class), a 5-bit major class and
+//! 11 service class bits (Audio, Telephony, Object Transfer, …).
+
+use std::fmt;
+
+/// Major device class (bits 8–12).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MajorClass {
+    Miscellaneous,
+    Computer,
+    Phone,
+    Network,
+    AudioVideo,
+    Peripheral,
+    Imaging,
+    Wearable,
+    Toy,
+    Health,
+    Uncategorized,
+    Reserved(u8),
+}
+
+impl MajorClass {
+    fn from_bits(bits: u8) -> Self {
+        match bits {
+            0x00 => Self::Miscellaneous,
+            0x01 => Self::Computer,
+            0x02 => Self::Phone,
+            0x03 => Self::Network,
+            0x04 => Self::AudioVideo,
+            0x05 => Self::Peripheral,
+            0x06 => Self::Imaging,
+            0x07 => Self::Wearable,
+            0x08 => Self::Toy,
+            0x09 => Self::Health,
+            0x1F => Self::Uncategorized,
+            other => Self::Reserved(other),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Miscellaneous => "Miscellaneous",
+            Self::Computer => "Computer",
+            Self::Phone => "Phone",
+            Self::Network => "Network access point",
+            Self::AudioVideo => "Audio/Video",
+            Self::Peripheral => "Peripheral",
+            Self::Imaging => "Imaging",
+            Self::Wearable => "Wearable",
+            Self::Toy => "Toy",
+            Self::Health => "Health",
+            Self::Uncategorized => "Uncategorized",
+            Self::Reserved(_) => "Reserved",
+        }
+    }
+}
+
+/// Service class bits (13–23), lowest first.
+const SERVICES: &[(u32, &str)] = &[
+    (1 << 13, "Limited Discoverable"),
+    (1 << 14, "LE Audio"),
+    (1 << 16, "Positioning"),
+    (1 << 17, "Networking"),
+    (1 << 18, "Rendering"),
+    (1 << 19, "Capturing"),
+    (1 << 20, "Object Transfer"),
+    (1 << 21, "Audio"),
+    (1 << 22, "Telephony"),
+    (1 << 23, "Information"),
+];
+
+const COMPUTER: &[&str] = &[
+    "Uncategorized",
+    "Desktop workstation",
+    "Server-class computer",
+    "Laptop",
+    "Handheld PC/PDA",
+    "Palm-size PC/PDA",
+    "Wearable computer",
+    "Tablet",
+];
+
+const PHONE: &[&str] = &[
+    "Uncategorized",
+    "Cellular",
+    "Cordless",
+    "Smartphone",
+    "Wired modem or voice gateway",
+    "Common ISDN access",
+];
+
+const NETWORK_LOAD: &[&str] = &[
+    "Fully available",
+    "1–17% utilized",
+    "17–33% utilized",
+    "33–50% utilized",
+    "50–67% utilized",
+    "67–83% utilized",
+    "83–99% utilized",
+    "No service available",
+];
+
+const AUDIO_VIDEO: &[&str] = &[
+    "Uncategorized",
+    "Wearable headset",
+    "Hands-free",
+    "",
+    "Microphone",
+    "Loudspeaker",
+    "Headphones",
+    "Portable audio",
+    "Car audio",
+    "Set-top box",
+    "HiFi audio",
+    "VCR",
+    "Video camera",
+    "Camcorder",
+    "Video monitor",
+    "Video display and loudspeaker",
+    "Video conferencing",
+    "",
+    "Gaming/Toy",
+];
+
+const PERIPHERAL_KIND: &[&str] = &[
+    "",
+    "Joystick",
+    "Gamepad",
+    "Remote control",
+    "Sensing device",
+    "Digitizer tablet",
+    "Card reader",
+    "Digital pen",
+    "Handheld scanner",
+    "Handheld gestural input",
+];
+
+const IMAGING: &[(u8, &str)] = &[
+    (1 << 2, "Display"),
+    (1 << 3, "Camera"),
+    (1 << 4, "Scanner"),
+    (1 << 5, "Printer"),
+];
+
+const WEARABLE: &[&str] = &[
+    "",
+    "Wristwatch",
+    "Pager",
+    "Jacket",
+    "Helmet",
+    "Glasses",
+    "Pin",
+];
+
+const TOY: &[&str] = &[
+    "",
+    "Robot",
+    "Vehicle",
+    "Doll/Action figure",
+    "Controller",
+    "Game",
+];
+
+const HEALTH: &[&str] = &[
+    "Undefined",
+    "Blood pressure monitor",
+    "Thermometer",
+    "Weighing scale",
+    "Glucose meter",
+    "Pulse oximeter",
+    "Heart/Pulse rate monitor",
+    "Health data display",
+    "Step counter",
+    "Body composition analyzer",
+    "Peak flow monitor",
+    "Medication monitor",
+    "Knee prosthesis",
+    "Ankle prosthesis",
+    "Generic health manager",
+    "Personal mobility device",
+];
+
+/// A decoded Class of Device value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceClass(pub u32);
+
+impl DeviceClass {
+    pub fn major(self) -> MajorClass {
+        MajorClass::from_bits(((self.0 >> 8) & 0x1F) as u8)
+    }
+
+    /// The raw 6-bit minor class.
+    pub fn minor_bits(self) -> u8 {
+        ((self.0 >> 2) & 0x3F) as u8
+    }
+
+    /// Minor class description, interpreted per major class. `None` when
+    /// the minor class is unassigned or unknown.
+    pub fn minor(self) -> Option<String> {
+        let minor = self.minor_bits();
+        let pick = |table: &[&'static str], idx: u8| {
+            table
+                .get(idx as usize)
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+        };
+        match self.major() {
+            MajorClass::Computer => pick(COMPUTER, minor),
+            MajorClass::Phone => pick(PHONE, minor),
+            MajorClass::Network => pick(NETWORK_LOAD, minor >> 3),
+            MajorClass::AudioVideo => pick(AUDIO_VIDEO, minor),
+            MajorClass::Peripheral => {
+                let input = match minor >> 4 {
+                    1 => Some("Keyboard"),
+                    2 => Some("Pointing device"),
+                    3 => Some("Keyboard + pointing device"),
+                    _ => None,
+                };
+                let kind = PERIPHERAL_KIND
+                    .get((minor & 0x0F) as usize)
+                    .filter(|s| !s.is_empty())
+                    .copied();
+                match (input, kind) {
+                    (Some(a), Some(b)) => Some(format!("{a}, {b}")),
+                    (a, b) => a.or(b).map(str::to_string),
+                }
+            }
+            MajorClass::Imaging => {
+                let kinds: Vec<&str> = IMAGING
+                    .iter()
+                    .filter(|&&(bit, _)| minor & bit != 0)
+                    .map(|&(_, name)| name)
+                    .collect();
+                (!kinds.is_empty()).then(|| kinds.join(", "))
+            }
+            MajorClass::Wearable => pick(WEARABLE, minor),
+            MajorClass::Toy => pick(TOY, minor),
+            MajorClass::Health => pick(HEALTH, minor),
+            MajorClass::Miscellaneous | MajorClass::Uncategorized | MajorClass::Reserved(_) => None,
+        }
+    }
+
+    /// Names of the service class bits that are set.
+    pub fn services(self) -> Vec<&'static str> {
+        SERVICES
+            .iter()
+            .filter(|&&(bit, _)| self.0 & bit != 0)
+            .map(|&(_, name)| name)
+            .collect()
+    }
+}
+
+/// `Audio/Video · Headphones`.
+impl fmt::Display for DeviceClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.major().label())?;
+        if let Some(minor) = self.minor() {
+            write!(f, " · {minor}")?;
+        }
+        Ok(())
+    }
+}
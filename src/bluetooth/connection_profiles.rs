@@ -0,0 +1,191 @@
+//! Per-profile connect / disconnect (A2DP, HFP, HID, PAN, …).
+//!
+//! BlueZ keeps no per-profile connection state on `Device1`, so the state is
+//! inferred from the objects profiles create while connected: media
+//! transports (A2DP, HFP/HSP), `MediaControl1` (AVRCP) and `Network1` (PAN).
+//! Profiles BlueZ gives no trace of are reported as connected only when this
+//! session connected them, and as unknown otherwise.
+
+use std::collections::HashSet;
+
+use bluer::{Adapter, Address, Uuid, UuidExt};
+use tracing::warn;
+
+use super::bluez_dbus::{self, BluezBus};
+
+/// Connection state of one profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileState {
+    Connected,
+    Disconnected,
+    /// BlueZ exposes nothing to tell.
+    Unknown,
+}
+
+impl ProfileState {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Connected => "connected",
+            Self::Disconnected => "disconnected",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// A profile the device advertises, with its inferred state.
+#[derive(Debug, Clone)]
+pub struct ProfileStatus {
+    pub uuid: Uuid,
+    pub state: ProfileState,
+}
+
+/// Whether a UUID is a BR/EDR service class (`0x1100`–`0x11FF`), i.e.
+/// something `ConnectProfile` can target.
+pub fn is_connectable(uuid: &Uuid) -> bool {
+    uuid.as_u16()
+        .is_some_and(|v| (0x1100..=0x11FF).contains(&v))
+}
+
+/// The peer role of a profile UUID: a transport for our A2DP Source means
+/// the device's A2DP Sink is connected, and so on.
+fn counterpart(uuid: u16) -> Option<u16> {
+    Some(match uuid {
+        0x110A => 0x110B, // A2DP Source ↔ Sink
+        0x110B => 0x110A,
+        0x111E => 0x111F, // HFP Hands-Free ↔ Audio Gateway
+        0x111F => 0x111E,
+        0x1108 => 0x1112, // HSP Headset ↔ Audio Gateway
+        0x1112 => 0x1108,
+        0x1131 => 0x1112, // HSP Headset (HS) ↔ Audio Gateway
+        _ => return None,
+    })
+}
+
+/// AVRCP UUIDs, all covered by `MediaControl1.Connected`.
+const AVRCP: [u16; 3] = [0x110C, 0x110E, 0x110F];
+
+/// Profiles whose state [`ProfileTracker`] can observe.
+fn observable(uuid: u16) -> bool {
+    counterpart(uuid).is_some() || AVRCP.contains(&uuid) || (0x1115..=0x1117).contains(&uuid)
+}
+
+#[derive(Default)]
+pub struct ProfileTracker {
+    /// Profiles connected by this session, until the device disconnects.
+    requested: HashSet<(Address, Uuid)>,
+}
+
+impl ProfileTracker {
+    pub async fn connect(
+        &mut self,
+        adapter: &Adapter,
+        address: Address,
+        uuid: Uuid,
+    ) -> bluer::Result<()> {
+        adapter.device(address)?.connect_profile(&uuid).await?;
+        self.requested.insert((address, uuid));
+        Ok(())
+    }
+
+    pub async fn disconnect(
+        &mut self,
+        adapter: &Adapter,
+        address: Address,
+        uuid: Uuid,
+    ) -> bluer::Result<()> {
+        adapter.device(address)?.disconnect_profile(&uuid).await?;
+        self.requested.remove(&(address, uuid));
+        Ok(())
+    }
+
+    pub fn forget_device(&mut self, address: Address) {
+        self.requested.retain(|(a, _)| *a != address);
+    }
+
+    /// Connectable profiles of `address` with their state.
+    pub async fn query(
+        &self,
+        adapter: &Adapter,
+        address: Address,
+    ) -> bluer::Result<Vec<ProfileStatus>> {
+        let device = adapter.device(address)?;
+        let connected = device.is_connected().await?;
+        let mut uuids: Vec<Uuid> = device
+            .uuids()
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .filter(is_connectable)
+            .collect();
+        uuids.sort();
+
+        let live = match bluez_dbus::shared() {
+            Some(bus) if connected => live_profiles(bus, adapter.name(), address).await,
+            _ => None,
+        };
+        Ok(uuids
+            .into_iter()
+            .map(|uuid| {
+                let state = if !connected {
+                    ProfileState::Disconnected
+                } else if live.as_ref().is_some_and(|l| l.contains(&uuid))
+                    || self.requested.contains(&(address, uuid))
+                {
+                    ProfileState::Connected
+                } else if live.is_some() && uuid.as_u16().is_some_and(observable) {
+                    ProfileState::Disconnected
+                } else {
+                    ProfileState::Unknown
+                };
+                ProfileStatus { uuid, state }
+            })
+            .collect())
+    }
+}
+
+/// Profiles with a live object under the device path. `None` when the bus
+/// query fails.
+async fn live_profiles(bus: &BluezBus, adapter: &str, address: Address) -> Option<HashSet<Uuid>> {
+    let objects = bus
+        .managed_objects()
+        .await
+        .map_err(|e| warn!("GetManagedObjects failed: {e}"))
+        .ok()?;
+    let device = bluez_dbus::device_path(adapter, address);
+    let mut live = HashSet::new();
+    let mut add = |uuid: u16| {
+        live.insert(Uuid::from_u16(uuid));
+        if let Some(peer) = counterpart(uuid) {
+            live.insert(Uuid::from_u16(peer));
+        }
+    };
+
+    for (path, interfaces) in &objects {
+        if !path.starts_with(&device) {
+            continue;
+        }
+        if let Some(transport) = interfaces.get("org.bluez.MediaTransport1") {
+            let uuid = bluez_dbus::prop_str(transport, "UUID").and_then(|u| u.parse::<Uuid>().ok());
+            if let Some(uuid) = uuid.and_then(|u| u.as_u16()) {
+                add(uuid);
+            }
+        }
+        if **path != *device {
+            continue;
+        }
+        if let Some(control) = interfaces.get("org.bluez.MediaControl1") {
+            if bluez_dbus::prop_bool(control, "Connected") == Some(true) {
+                AVRCP.into_iter().for_each(&mut add);
+            }
+        }
+        if let Some(network) = interfaces.get("org.bluez.Network1") {
+            let uuid = bluez_dbus::prop_str(network, "UUID").and_then(|u| u.parse::<Uuid>().ok());
+            if bluez_dbus::prop_bool(network, "Connected") == Some(true) {
+                if let Some(uuid) = uuid.and_then(|u| u.as_u16()) {
+                    add(uuid);
+                }
+            }
+        }
+    }
+    Some(live)
+}
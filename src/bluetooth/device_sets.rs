@@ -0,0 +1,82 @@
+//! Coordinated sets (CSIP): devices BlueZ groups into one unit, such as a
+//! pair of LE Audio earbuds.
+//!
+//! Each set is a `DeviceSet1` object listing its members; each member's
+//! `Device1.Sets` carries its rank within the set. bluer wraps neither, so
+//! both are read from [`BluezBus`] object snapshots.
+
+use bluer::Address;
+use dbus::arg::RefArg;
+
+use super::bluez_dbus::{self, BluezBus};
+
+const DEVICE_SET: &str = "org.bluez.DeviceSet1";
+
+/// A coordinated set and its members, ordered by rank.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceSet {
+    /// `DeviceSet1` object path.
+    pub id: String,
+    /// Number of members the set declares, known or not.
+    pub size: Option<u8>,
+    pub members: Vec<Address>,
+}
+
+/// All coordinated sets of `adapter`.
+pub async fn query(bus: &BluezBus, adapter: &str) -> Result<Vec<DeviceSet>, dbus::Error> {
+    let objects = bus.managed_objects().await?;
+    let prefix = format!("{}/", bluez_dbus::adapter_path(adapter));
+
+    let mut sets = Vec::new();
+    for (path, interfaces) in &objects {
+        let Some(set) = interfaces.get(DEVICE_SET) else {
+            continue;
+        };
+        if !path.starts_with(&prefix) {
+            continue;
+        }
+        let id = path.to_string();
+        let mut members: Vec<(Option<u8>, Address)> = bluez_dbus::prop_strs(set, "Devices")
+            .iter()
+            .filter_map(|dev| {
+                let address = bluez_dbus::device_address(dev)?;
+                let rank = objects
+                    .get(&dbus::Path::from(dev.as_str()))
+                    .and_then(|ifaces| ifaces.get("org.bluez.Device1"))
+                    .and_then(|props| props.get("Sets"))
+                    .and_then(|sets| rank_in(&*sets.0, &id));
+                Some((rank, address))
+            })
+            .collect();
+        // Unranked members last.
+        members.sort_by_key(|&(rank, address)| (rank.is_none(), rank, address));
+        sets.push(DeviceSet {
+            size: set
+                .get("Size")
+                .and_then(|v| v.0.as_u64())
+                .and_then(|v| u8::try_from(v).ok()),
+            members: members.into_iter().map(|(_, address)| address).collect(),
+            id,
+        });
+    }
+    sets.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(sets)
+}
+
+/// The `Rank` of set `id` in a `Device1.Sets` value (`a{oa{sv}}`). D-Bus
+/// dicts iterate as alternating keys and values.
+fn rank_in(sets: &dyn RefArg, id: &str) -> Option<u8> {
+    let mut entries = sets.as_iter()?;
+    while let (Some(path), Some(props)) = (entries.next(), entries.next()) {
+        if path.as_str() != Some(id) {
+            continue;
+        }
+        let mut props = props.as_iter()?;
+        while let (Some(key), Some(value)) = (props.next(), props.next()) {
+            if key.as_str() == Some("Rank") {
+                return value.as_u64().and_then(|v| u8::try_from(v).ok());
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,84 @@
+//! A private D-Bus daemon for tests that stand in for BlueZ or obexd.
+
+use std::ffi::CStr;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+
+use dbus::channel::{Channel, MatchingReceiver, Sender};
+use dbus::message::MatchRule;
+use dbus::nonblock::SyncConnection;
+use dbus::Message;
+
+/// A `dbus-daemon` of its own, killed on drop.
+pub struct PrivateBus {
+    daemon: Child,
+    address: String,
+}
+
+impl PrivateBus {
+    /// Start the daemon; `None` when `dbus-daemon` isn't installed. Tests
+    /// fail rather than skip then, so a green run means they ran.
+    pub fn start() -> Option<Self> {
+        let mut daemon = Command::new("dbus-daemon")
+            .args(["--session", "--nofork", "--print-address"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| eprintln!("cannot start dbus-daemon: {e}"))
+            .ok()?;
+        let mut address = String::new();
+        BufReader::new(daemon.stdout.take()?)
+            .read_line(&mut address)
+            .ok()?;
+        Some(Self {
+            daemon,
+            address: address.trim().to_string(),
+        })
+    }
+
+    /// A new connection, driven on the current runtime.
+    pub fn connect(&self) -> Arc<SyncConnection> {
+        let mut channel = Channel::open_private(&self.address).expect("open private bus");
+        channel.register().expect("register on private bus");
+        let (resource, conn) =
+            dbus_tokio::connection::from_channel(channel).expect("drive private bus");
+        tokio::spawn(resource);
+        conn
+    }
+
+    /// Own `name` and answer every method call sent to it with `handle`.
+    /// Signals can be sent on the connection passed to `handle`.
+    pub async fn serve<F>(&self, name: &'static str, mut handle: F) -> Arc<SyncConnection>
+    where
+        F: FnMut(&Message, &SyncConnection) -> Message + Send + 'static,
+    {
+        let conn = self.connect();
+        conn.request_name(name, false, true, true)
+            .await
+            .expect("own service name");
+        conn.start_receive(
+            MatchRule::new_method_call(),
+            Box::new(move |msg, conn| {
+                let reply = handle(&msg, conn);
+                let _ = conn.send(reply);
+                true
+            }),
+        );
+        conn
+    }
+}
+
+impl Drop for PrivateBus {
+    fn drop(&mut self) {
+        let _ = self.daemon.kill();
+        let _ = self.daemon.wait();
+    }
+}
+
+/// The error a service returns for a call it doesn't implement.
+pub fn unknown_method(msg: &Message) -> Message {
+    msg.error(
+        &"org.freedesktop.DBus.Error.UnknownMethod".into(),
+        CStr::from_bytes_with_nul(b"unknown method\0").unwrap(),
+    )
+}
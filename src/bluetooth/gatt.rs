@@ -0,0 +1,255 @@
+//! GATT client helpers: snapshot a connected device's GATT database into
+//! plain data for the UI, and run characteristic reads, writes and
+//! notification subscriptions on its behalf.
+
+use std::collections::HashMap;
+
+use bluer::gatt::remote::{Characteristic, CharacteristicWriteRequest, Service};
+use bluer::gatt::{CharacteristicFlags, WriteOp};
+use bluer::{Adapter, Device, UuidExt};
+use futures::StreamExt;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use super::types::{
+    Address, BtEvent, GattCharacteristic, GattDescriptor, GattProps, GattService, GattTarget,
+    PresentationFormat,
+};
+
+/// Characteristic Presentation Format descriptor.
+const PRESENTATION_FORMAT: u16 = 0x2904;
+
+/// Enumerate services, characteristics and descriptors. Waits (bounded by
+/// bluer) for BlueZ to finish resolving services after a connect. With
+/// `read_values`, readable characteristics are read as well; values that
+/// fail to read (e.g. need encryption) are left out.
+pub async fn discover(device: &Device, read_values: bool) -> bluer::Result<Vec<GattService>> {
+    let mut services = Vec::new();
+    for service in device.services().await? {
+        services.push(snapshot_service(&service, read_values).await?);
+    }
+    services.sort_by_key(|s| s.handle);
+    Ok(services)
+}
+
+async fn snapshot_service(service: &Service, read_values: bool) -> bluer::Result<GattService> {
+    let mut characteristics = Vec::new();
+    for characteristic in service.characteristics().await? {
+        characteristics.push(snapshot_characteristic(&characteristic, read_values).await?);
+    }
+    characteristics.sort_by_key(|c| c.handle);
+    Ok(GattService {
+        handle: service.id(),
+        uuid: service.uuid().await?,
+        primary: service.primary().await.unwrap_or(true),
+        characteristics,
+    })
+}
+
+async fn snapshot_characteristic(
+    characteristic: &Characteristic,
+    read_values: bool,
+) -> bluer::Result<GattCharacteristic> {
+    let mut descriptors = Vec::new();
+    let mut presentation = None;
+    for descriptor in characteristic.descriptors().await? {
+        let uuid = descriptor.uuid().await?;
+        // Best effort: the descriptor may need encryption we don't have yet.
+        if presentation.is_none() && uuid.as_u16() == Some(PRESENTATION_FORMAT) {
+            if let Ok(value) = descriptor.read().await {
+                presentation = PresentationFormat::parse(&value);
+            }
+        }
+        descriptors.push(GattDescriptor {
+            handle: descriptor.id(),
+            uuid,
+        });
+    }
+    descriptors.sort_by_key(|d| d.handle);
+    let flags = characteristic.flags().await.unwrap_or_default();
+    let value = if read_values && flags.read {
+        characteristic.read().await.ok()
+    } else {
+        None
+    };
+    Ok(GattCharacteristic {
+        handle: characteristic.id(),
+        uuid: characteristic.uuid().await?,
+        props: to_props(&flags),
+        presentation,
+        value,
+        descriptors,
+    })
+}
+
+fn to_props(flags: &CharacteristicFlags) -> GattProps {
+    GattProps {
+        broadcast: flags.broadcast,
+        read: flags.read,
+        write: flags.write,
+        write_without_response: flags.write_without_response,
+        notify: flags.notify,
+        indicate: flags.indicate,
+        signed_write: flags.authenticated_signed_writes,
+        reliable_write: flags.reliable_write,
+        encrypted: flags.encrypt_read
+            || flags.encrypt_write
+            || flags.encrypt_authenticated_read
+            || flags.encrypt_authenticated_write
+            || flags.secure_read
+            || flags.secure_write,
+    }
+}
+
+/// Resolve a target to its bluer characteristic handle.
+async fn characteristic(adapter: &Adapter, target: GattTarget) -> bluer::Result<Characteristic> {
+    adapter
+        .device(target.address)?
+        .service(target.service)
+        .await?
+        .characteristic(target.characteristic)
+        .await
+}
+
+/// Read a characteristic and report the value (or the failure).
+pub async fn read(adapter: &Adapter, target: GattTarget, evt_tx: &mpsc::Sender<BtEvent>) {
+    let result = match characteristic(adapter, target).await {
+        Ok(c) => c.read().await,
+        Err(e) => Err(e),
+    };
+    let event = match result {
+        Ok(value) => BtEvent::GattValue {
+            target,
+            value,
+            notified: false,
+        },
+        Err(e) => BtEvent::GattOpFailed {
+            target,
+            error: format!("Read failed: {e}"),
+        },
+    };
+    let _ = evt_tx.send(event).await;
+}
+
+/// Write a characteristic as a request (acknowledged) or command.
+pub async fn write(
+    adapter: &Adapter,
+    target: GattTarget,
+    value: &[u8],
+    with_response: bool,
+    evt_tx: &mpsc::Sender<BtEvent>,
+) {
+    let req = CharacteristicWriteRequest {
+        op_type: if with_response {
+            WriteOp::Request
+        } else {
+            WriteOp::Command
+        },
+        ..Default::default()
+    };
+    let result = match characteristic(adapter, target).await {
+        Ok(c) => c.write_ext(value, &req).await,
+        Err(e) => Err(e),
+    };
+    let event = match result {
+        Ok(()) => BtEvent::GattWritten {
+            target,
+            len: value.len(),
+        },
+        Err(e) => BtEvent::GattOpFailed {
+            target,
+            error: format!("Write failed: {e}"),
+        },
+    };
+    let _ = evt_tx.send(event).await;
+}
+
+/// Active notification sessions. Each runs in its own task that forwards
+/// values to the UI; aborting the task drops the bluer stream, which ends
+/// the session in BlueZ.
+#[derive(Default)]
+pub struct Subscriptions {
+    tasks: HashMap<GattTarget, JoinHandle<()>>,
+}
+
+impl Subscriptions {
+    pub async fn subscribe(
+        &mut self,
+        adapter: &Adapter,
+        target: GattTarget,
+        evt_tx: &mpsc::Sender<BtEvent>,
+    ) {
+        self.tasks.retain(|_, task| !task.is_finished());
+        if self.tasks.contains_key(&target) {
+            return;
+        }
+        let stream = match characteristic(adapter, target).await {
+            Ok(c) => c.notify().await,
+            Err(e) => Err(e),
+        };
+        let mut stream = match stream {
+            Ok(stream) => Box::pin(stream),
+            Err(e) => {
+                let _ = evt_tx
+                    .send(BtEvent::GattOpFailed {
+                        target,
+                        error: format!("Subscribe failed: {e}"),
+                    })
+                    .await;
+                return;
+            }
+        };
+        let _ = evt_tx
+            .send(BtEvent::GattNotifying {
+                target,
+                active: true,
+            })
+            .await;
+
+        let evt_tx = evt_tx.clone();
+        let task = tokio::spawn(async move {
+            while let Some(value) = stream.next().await {
+                let event = BtEvent::GattValue {
+                    target,
+                    value,
+                    notified: true,
+                };
+                if evt_tx.send(event).await.is_err() {
+                    return;
+                }
+            }
+            let _ = evt_tx
+                .send(BtEvent::GattNotifying {
+                    target,
+                    active: false,
+                })
+                .await;
+        });
+        self.tasks.insert(target, task);
+    }
+
+    pub async fn unsubscribe(&mut self, target: GattTarget, evt_tx: &mpsc::Sender<BtEvent>) {
+        if let Some(task) = self.tasks.remove(&target) {
+            task.abort();
+            let _ = evt_tx
+                .send(BtEvent::GattNotifying {
+                    target,
+                    active: false,
+                })
+                .await;
+        }
+    }
+
+    /// End every session on `address` (disconnect, browser closed).
+    pub async fn drop_device(&mut self, address: Address, evt_tx: &mpsc::Sender<BtEvent>) {
+        let targets: Vec<_> = self
+            .tasks
+            .keys()
+            .filter(|t| t.address == address)
+            .copied()
+            .collect();
+        for target in targets {
+            self.unsubscribe(target, evt_tx).await;
+        }
+    }
+}
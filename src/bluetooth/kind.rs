@@ -0,0 +1,269 @@
+//! Unified device type model.
+//!
+//! BlueZ exposes several overlapping hints about what a device is: the
+//! freedesktop `icon` name, the BR/EDR Class of Device, the LE GAP appearance
+//! and the advertised service UUIDs. `DeviceKind::detect` folds them into one
+//! value, most specific hint first, which drives icons, the category column,
+//! category filtering and "by type" sorting.
+
+use bluer::UuidExt;
+
+use super::class::{DeviceClass, MajorClass};
+use super::types::Uuid;
+
+/// What a device is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum DeviceKind {
+    Headset,
+    Speaker,
+    Microphone,
+    CarAudio,
+    Keyboard,
+    Mouse,
+    Gamepad,
+    Digitizer,
+    /// Generic HID: remote controls, presenters, unknown HID devices.
+    Input,
+    Phone,
+    Computer,
+    Laptop,
+    Tablet,
+    Watch,
+    Wearable,
+    Health,
+    Camera,
+    Printer,
+    Display,
+    Network,
+    Tag,
+    Toy,
+    #[default]
+    Unknown,
+}
+
+/// Coarse grouping of `DeviceKind`s used for filtering and sorting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DeviceCategory {
+    Audio,
+    Input,
+    Phone,
+    Computer,
+    Wearable,
+    Health,
+    Imaging,
+    Network,
+    Other,
+}
+
+impl DeviceCategory {
+    pub const ALL: [Self; 9] = [
+        Self::Audio,
+        Self::Input,
+        Self::Phone,
+        Self::Computer,
+        Self::Wearable,
+        Self::Health,
+        Self::Imaging,
+        Self::Network,
+        Self::Other,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Audio => "Audio",
+            Self::Input => "Input",
+            Self::Phone => "Phone",
+            Self::Computer => "Computer",
+            Self::Wearable => "Wearable",
+            Self::Health => "Health",
+            Self::Imaging => "Imaging",
+            Self::Network => "Network",
+            Self::Other => "Other",
+        }
+    }
+
+    /// Parse a config value (`"audio"`, `"input"`, …).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|c| c.label().eq_ignore_ascii_case(name))
+    }
+}
+
+impl DeviceKind {
+    /// Derive the kind from everything BlueZ tells us, most specific first:
+    /// CoD minor class, freedesktop icon name, GAP appearance, CoD major
+    /// class, then well-known service UUIDs.
+    pub fn detect(
+        icon: Option<&str>,
+        class: Option<u32>,
+        appearance: Option<u16>,
+        uuids: &[Uuid],
+    ) -> Self {
+        let class = class.map(DeviceClass);
+        class
+            .and_then(from_minor_class)
+            .or_else(|| icon.and_then(from_icon_name))
+            .or_else(|| appearance.and_then(from_appearance))
+            .or_else(|| class.and_then(from_major_class))
+            .or_else(|| from_uuids(uuids))
+            .unwrap_or_default()
+    }
+
+    pub fn category(self) -> DeviceCategory {
+        match self {
+            Self::Headset | Self::Speaker | Self::Microphone | Self::CarAudio => {
+                DeviceCategory::Audio
+            }
+            Self::Keyboard | Self::Mouse | Self::Gamepad | Self::Digitizer | Self::Input => {
+                DeviceCategory::Input
+            }
+            Self::Phone => DeviceCategory::Phone,
+            Self::Computer | Self::Laptop | Self::Tablet => DeviceCategory::Computer,
+            Self::Watch | Self::Wearable => DeviceCategory::Wearable,
+            Self::Health => DeviceCategory::Health,
+            Self::Camera | Self::Printer | Self::Display => DeviceCategory::Imaging,
+            Self::Network => DeviceCategory::Network,
+            Self::Tag | Self::Toy | Self::Unknown => DeviceCategory::Other,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Headset => "Headset",
+            Self::Speaker => "Speaker",
+            Self::Microphone => "Microphone",
+            Self::CarAudio => "Car audio",
+            Self::Keyboard => "Keyboard",
+            Self::Mouse => "Mouse",
+            Self::Gamepad => "Gamepad",
+            Self::Digitizer => "Pen tablet",
+            Self::Input => "Input device",
+            Self::Phone => "Phone",
+            Self::Computer => "Computer",
+            Self::Laptop => "Laptop",
+            Self::Tablet => "Tablet",
+            Self::Watch => "Watch",
+            Self::Wearable => "Wearable",
+            Self::Health => "Health sensor",
+            Self::Camera => "Camera",
+            Self::Printer => "Printer",
+            Self::Display => "Display",
+            Self::Network => "Network",
+            Self::Tag => "Tracker tag",
+            Self::Toy => "Toy",
+            Self::Unknown => "Unknown",
+        }
+    }
+}
+
+fn from_minor_class(class: DeviceClass) -> Option<DeviceKind> {
+    let minor = class.minor_bits();
+    Some(match class.major() {
+        MajorClass::Computer if (3..=5).contains(&minor) => DeviceKind::Laptop,
+        MajorClass::Computer if minor == 7 => DeviceKind::Tablet,
+        MajorClass::Phone if minor == 1 || minor == 3 => DeviceKind::Phone,
+        MajorClass::AudioVideo => match minor {
+            1 | 2 | 6 => DeviceKind::Headset,
+            4 => DeviceKind::Microphone,
+            5 | 7 | 10 => DeviceKind::Speaker,
+            8 => DeviceKind::CarAudio,
+            9 | 14 | 15 => DeviceKind::Display,
+            12 | 13 => DeviceKind::Camera,
+            18 => DeviceKind::Gamepad,
+            _ => return None,
+        },
+        MajorClass::Peripheral => match (minor >> 4, minor & 0x0F) {
+            (_, 1 | 2) => DeviceKind::Gamepad,
+            (_, 5 | 7) => DeviceKind::Digitizer,
+            (1 | 3, _) => DeviceKind::Keyboard,
+            (2, _) => DeviceKind::Mouse,
+            _ => return None,
+        },
+        MajorClass::Imaging if minor & (1 << 5) != 0 => DeviceKind::Printer,
+        MajorClass::Imaging if minor & (1 << 3) != 0 => DeviceKind::Camera,
+        MajorClass::Imaging if minor & (1 << 2) != 0 => DeviceKind::Display,
+        MajorClass::Wearable if minor == 1 => DeviceKind::Watch,
+        MajorClass::Toy if minor == 4 || minor == 5 => DeviceKind::Gamepad,
+        _ => return None,
+    })
+}
+
+fn from_major_class(class: DeviceClass) -> Option<DeviceKind> {
+    Some(match class.major() {
+        MajorClass::Computer => DeviceKind::Computer,
+        MajorClass::Phone => DeviceKind::Phone,
+        MajorClass::Network => DeviceKind::Network,
+        MajorClass::AudioVideo => DeviceKind::Speaker,
+        MajorClass::Peripheral => DeviceKind::Input,
+        MajorClass::Imaging => DeviceKind::Camera,
+        MajorClass::Wearable => DeviceKind::Wearable,
+        MajorClass::Toy => DeviceKind::Toy,
+        MajorClass::Health => DeviceKind::Health,
+        _ => return None,
+    })
+}
+
+fn from_icon_name(icon: &str) -> Option<DeviceKind> {
+    Some(match icon {
+        s if s.contains("audio-headset") || s.contains("audio-headphones") => DeviceKind::Headset,
+        s if s.contains("audio-card") || s.contains("speaker") => DeviceKind::Speaker,
+        s if s.contains("phone") => DeviceKind::Phone,
+        s if s.contains("computer") => DeviceKind::Computer,
+        s if s.contains("input-keyboard") => DeviceKind::Keyboard,
+        s if s.contains("input-mouse") => DeviceKind::Mouse,
+        s if s.contains("input-gaming") => DeviceKind::Gamepad,
+        s if s.contains("input-tablet") => DeviceKind::Digitizer,
+        s if s.contains("camera") => DeviceKind::Camera,
+        s if s.contains("printer") => DeviceKind::Printer,
+        s if s.contains("network") => DeviceKind::Network,
+        s if s.contains("video-display") || s.contains("monitor") => DeviceKind::Display,
+        _ => return None,
+    })
+}
+
+/// GAP appearance: upper 10 bits category, lower 6 subcategory.
+fn from_appearance(value: u16) -> Option<DeviceKind> {
+    let sub = value & 0x3F;
+    Some(match value >> 6 {
+        0x001 => DeviceKind::Phone,
+        0x002 => match sub {
+            3..=6 => DeviceKind::Laptop,
+            7 => DeviceKind::Tablet,
+            _ => DeviceKind::Computer,
+        },
+        0x003 => DeviceKind::Watch,
+        0x005 | 0x028 => DeviceKind::Display,
+        0x006 => DeviceKind::Input,
+        0x007 => DeviceKind::Wearable,
+        0x008 | 0x009 => DeviceKind::Tag,
+        0x00A | 0x021 => DeviceKind::Speaker,
+        0x00C..=0x00E | 0x010 | 0x011 | 0x031 | 0x032 | 0x034..=0x037 => DeviceKind::Health,
+        0x00F => match sub {
+            1 => DeviceKind::Keyboard,
+            2 => DeviceKind::Mouse,
+            3 | 4 => DeviceKind::Gamepad,
+            5 | 7 => DeviceKind::Digitizer,
+            _ => DeviceKind::Input,
+        },
+        0x014 => DeviceKind::Network,
+        0x022 if sub == 1 => DeviceKind::Microphone,
+        0x025 | 0x029 => DeviceKind::Headset,
+        0x02A => DeviceKind::Gamepad,
+        _ => return None,
+    })
+}
+
+/// Last resort: well-known profile / service UUIDs.
+fn from_uuids(uuids: &[Uuid]) -> Option<DeviceKind> {
+    uuids.iter().find_map(|uuid| {
+        Some(match uuid.as_u16()? {
+            0x1108 | 0x1131 | 0x111E | 0x1854 => DeviceKind::Headset,
+            0x110B | 0x184E | 0x1850 => DeviceKind::Speaker,
+            0x1124 | 0x1812 => DeviceKind::Input,
+            0x1808 | 0x1809 | 0x180D | 0x1810 | 0x1822 => DeviceKind::Health,
+            0xFEEC | 0xFEED => DeviceKind::Tag,
+            _ => return None,
+        })
+    })
+}
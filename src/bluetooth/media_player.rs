@@ -0,0 +1,257 @@
+//! AVRCP media players: what a connected phone is playing, and its remote
+//! controls.
+//!
+//! BlueZ exposes a `MediaPlayer1` object under the device for each player
+//! the remote side offers (usually one). bluer doesn't wrap it, so players
+//! are read from [`BluezBus`] object snapshots and controlled with plain
+//! method calls.
+
+use std::time::{Duration, Instant};
+
+use bluer::Address;
+use dbus::arg::{PropMap, RefArg};
+
+use super::bluez_dbus::{self, BluezBus, ManagedObjects};
+
+pub const PLAYER: &str = "org.bluez.MediaPlayer1";
+
+/// Playback state reported by the remote player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackStatus {
+    Playing,
+    Paused,
+    Stopped,
+    Seeking,
+    Error,
+}
+
+impl PlaybackStatus {
+    fn parse(s: &str) -> Self {
+        match s {
+            "playing" => Self::Playing,
+            "paused" => Self::Paused,
+            "forward-seek" | "reverse-seek" => Self::Seeking,
+            "error" => Self::Error,
+            _ => Self::Stopped,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Playing => "Playing",
+            Self::Paused => "Paused",
+            Self::Stopped => "Stopped",
+            Self::Seeking => "Seeking",
+            Self::Error => "Error",
+        }
+    }
+}
+
+/// Track metadata; any field may be missing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Track {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub duration: Option<Duration>,
+}
+
+/// A remote media player on a connected device.
+#[derive(Debug, Clone)]
+pub struct MediaPlayer {
+    pub address: Address,
+    /// `MediaPlayer1` object path.
+    pub path: String,
+    /// Player name, e.g. the phone's music app.
+    pub name: Option<String>,
+    pub status: PlaybackStatus,
+    pub track: Track,
+    /// Position when the player was read.
+    pub position: Option<Duration>,
+    /// When the player was read. BlueZ signals position only on seeks and
+    /// status changes, so the UI advances it from here while playing.
+    pub read_at: Instant,
+}
+
+impl MediaPlayer {
+    /// Current position, extrapolated while playing and capped at the
+    /// track length.
+    pub fn position_now(&self) -> Option<Duration> {
+        let position = self.position?;
+        if self.status != PlaybackStatus::Playing {
+            return Some(position);
+        }
+        let now = position + self.read_at.elapsed();
+        Some(self.track.duration.map_or(now, |d| now.min(d)))
+    }
+}
+
+/// Remote control actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaControl {
+    Play,
+    Pause,
+    Next,
+    Previous,
+}
+
+impl MediaControl {
+    fn method(self) -> &'static str {
+        match self {
+            Self::Play => "Play",
+            Self::Pause => "Pause",
+            Self::Next => "Next",
+            Self::Previous => "Previous",
+        }
+    }
+}
+
+/// All media players on devices of `adapter` in an object snapshot, one
+/// device's players in path order.
+pub fn players(objects: &ManagedObjects, adapter: &str) -> Vec<MediaPlayer> {
+    let prefix = format!("{}/", bluez_dbus::adapter_path(adapter));
+    let read_at = Instant::now();
+
+    let mut players: Vec<MediaPlayer> = objects
+        .iter()
+        .filter(|(path, _)| path.starts_with(&prefix))
+        .filter_map(|(path, interfaces)| {
+            let props = interfaces.get(PLAYER)?;
+            Some(MediaPlayer {
+                address: bluez_dbus::device_address(bluez_dbus::prop_str(props, "Device")?)?,
+                path: path.to_string(),
+                name: bluez_dbus::prop_str(props, "Name").map(str::to_string),
+                status: PlaybackStatus::parse(
+                    bluez_dbus::prop_str(props, "Status").unwrap_or_default(),
+                ),
+                track: props.get("Track").map(|t| track(&*t.0)).unwrap_or_default(),
+                position: millis(props, "Position"),
+                read_at,
+            })
+        })
+        .collect();
+    players.sort_by(|a, b| a.path.cmp(&b.path));
+    players
+}
+
+/// Send a remote control action to the player at `path`.
+pub async fn control(bus: &BluezBus, path: &str, action: MediaControl) -> Result<(), dbus::Error> {
+    bus.proxy(path)
+        .method_call(PLAYER, action.method(), ())
+        .await
+}
+
+/// Decode a `Track` dict (`a{sv}`); D-Bus dicts iterate as alternating keys
+/// and values.
+fn track(value: &dyn RefArg) -> Track {
+    let mut track = Track::default();
+    let Some(mut entries) = value.as_iter() else {
+        return track;
+    };
+    while let (Some(key), Some(value)) = (entries.next(), entries.next()) {
+        let text = || value.as_str().filter(|s| !s.is_empty()).map(str::to_string);
+        match key.as_str() {
+            Some("Title") => track.title = text(),
+            Some("Artist") => track.artist = text(),
+            Some("Album") => track.album = text(),
+            Some("Duration") => {
+                track.duration = value
+                    .as_u64()
+                    .filter(|&ms| ms > 0)
+                    .map(Duration::from_millis);
+            }
+            _ => {}
+        }
+    }
+    track
+}
+
+/// A millisecond `u32` property.
+fn millis(props: &PropMap, key: &str) -> Option<Duration> {
+    props.get(key)?.0.as_u64().map(Duration::from_millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use dbus::arg::Variant;
+    use dbus::Path;
+
+    use super::*;
+    use crate::bluetooth::bluez_dbus::ManagedObjects;
+    use crate::bluetooth::fake_bus::{self, PrivateBus};
+
+    const FAKE_BLUEZ: &str = "test.voidlink.bluez";
+    const PLAYER_PATH: &str = "/org/bluez/hci0/dev_AA_BB_CC_DD_EE_FF/player0";
+
+    fn player_objects() -> ManagedObjects {
+        let mut track: PropMap = HashMap::new();
+        track.insert("Title".into(), Variant(Box::new("Song".to_string())));
+        track.insert("Artist".into(), Variant(Box::new("Band".to_string())));
+        track.insert("Duration".into(), Variant(Box::new(180_000u32)));
+        let mut props: PropMap = HashMap::new();
+        props.insert(
+            "Device".into(),
+            Variant(Box::new(Path::from(
+                "/org/bluez/hci0/dev_AA_BB_CC_DD_EE_FF",
+            ))),
+        );
+        props.insert("Status".into(), Variant(Box::new("paused".to_string())));
+        props.insert("Position".into(), Variant(Box::new(42_000u32)));
+        props.insert("Track".into(), Variant(Box::new(track)));
+        let interfaces = HashMap::from([(PLAYER.to_string(), props)]);
+        HashMap::from([(Path::from(PLAYER_PATH), interfaces)])
+    }
+
+    #[tokio::test]
+    async fn reads_and_controls_a_player() {
+        let daemon = PrivateBus::start().expect("this test needs dbus-daemon");
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let seen = calls.clone();
+        let _service = daemon
+            .serve(FAKE_BLUEZ, move |msg, _| {
+                let member = msg.member().map(|m| m.to_string());
+                match (msg.interface().as_deref(), member.as_deref()) {
+                    (Some("org.freedesktop.DBus.ObjectManager"), Some("GetManagedObjects")) => {
+                        msg.method_return().append1(player_objects())
+                    }
+                    (Some(PLAYER), Some(method)) => {
+                        let path = msg.path().map(|p| p.to_string());
+                        seen.lock().unwrap().push((path, method.to_string()));
+                        msg.method_return()
+                    }
+                    _ => fake_bus::unknown_method(msg),
+                }
+            })
+            .await;
+        let bus = BluezBus::new(daemon.connect(), FAKE_BLUEZ);
+
+        let objects = bus.managed_objects().await.unwrap();
+        let found = players(&objects, "hci0");
+        assert_eq!(found.len(), 1);
+        let player = &found[0];
+        assert_eq!(player.address, "AA:BB:CC:DD:EE:FF".parse().unwrap());
+        assert_eq!(player.status, PlaybackStatus::Paused);
+        assert_eq!(player.position_now(), Some(Duration::from_secs(42)));
+        assert_eq!(
+            player.track,
+            Track {
+                title: Some("Song".into()),
+                artist: Some("Band".into()),
+                album: None,
+                duration: Some(Duration::from_secs(180)),
+            }
+        );
+        assert!(players(&objects, "hci1").is_empty());
+
+        control(&bus, &player.path, MediaControl::Next)
+            .await
+            .unwrap();
+        assert_eq!(
+            *calls.lock().unwrap(),
+            [(Some(PLAYER_PATH.to_string()), "Next".to_string())]
+        );
+    }
+}
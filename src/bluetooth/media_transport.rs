@@ -0,0 +1,318 @@
+//! Audio transports: the codec a device actually negotiated, and absolute
+//! volume.
+//!
+//! BlueZ exposes a `MediaTransport1` object per configured audio stream,
+//! carrying the A2DP codec id and its raw configuration blob. bluer doesn't
+//! wrap it, so transports are read from [`BluezBus`] object snapshots and
+//! the blob is decoded here.
+
+use std::time::Duration;
+
+use bluer::{Address, Uuid};
+use dbus::nonblock::stdintf::org_freedesktop_dbus::Properties;
+
+use super::bluez_dbus::{self, BluezBus, ManagedObjects};
+
+pub const TRANSPORT: &str = "org.bluez.MediaTransport1";
+
+/// Highest AVRCP absolute volume.
+pub const MAX_VOLUME: u16 = 127;
+
+/// A configured audio stream of a device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaTransport {
+    pub address: Address,
+    /// `MediaTransport1` object path.
+    pub path: String,
+    /// Profile the stream belongs to (A2DP sink/source, LE Audio, …).
+    pub uuid: Option<Uuid>,
+    /// `idle`, `pending` or `active`.
+    pub state: String,
+    pub codec: CodecInfo,
+    /// Absolute volume, `0..=MAX_VOLUME`; `None` when unsupported.
+    pub volume: Option<u16>,
+    /// Transport delay reported by the sink.
+    pub delay: Option<Duration>,
+}
+
+impl MediaTransport {
+    /// Short profile label for the transport's UUID.
+    pub fn profile(&self) -> &'static str {
+        match self.uuid.map(|u| u.to_string()) {
+            Some(u) if u.starts_with("0000110a") => "A2DP source",
+            Some(u) if u.starts_with("0000110b") => "A2DP sink",
+            Some(u) if u.starts_with("00001850") => "LE Audio",
+            Some(u) if u.starts_with("00001851") => "LE Audio (broadcast)",
+            _ => "Audio",
+        }
+    }
+}
+
+/// Decoded codec name and configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodecInfo {
+    pub name: String,
+    /// Human-readable configuration fields, e.g. `44.1 kHz`, `bitpool 2–53`.
+    pub details: Vec<String>,
+}
+
+/// All audio transports on devices of `adapter` in an object snapshot, in
+/// path order.
+pub fn transports(objects: &ManagedObjects, adapter: &str) -> Vec<MediaTransport> {
+    let prefix = format!("{}/", bluez_dbus::adapter_path(adapter));
+
+    let mut transports: Vec<MediaTransport> = objects
+        .iter()
+        .filter(|(path, _)| path.starts_with(&prefix))
+        .filter_map(|(path, interfaces)| {
+            let props = interfaces.get(TRANSPORT)?;
+            let codec = props.get("Codec").and_then(|v| v.0.as_u64()).unwrap_or(0) as u8;
+            let config: Vec<u8> = props
+                .get("Configuration")
+                .and_then(|v| v.0.as_iter())
+                .map(|bytes| bytes.filter_map(|b| b.as_u64()).map(|b| b as u8).collect())
+                .unwrap_or_default();
+            Some(MediaTransport {
+                address: bluez_dbus::device_address(bluez_dbus::prop_str(props, "Device")?)?,
+                path: path.to_string(),
+                uuid: bluez_dbus::prop_str(props, "UUID").and_then(|u| u.parse().ok()),
+                state: bluez_dbus::prop_str(props, "State")
+                    .unwrap_or("idle")
+                    .to_string(),
+                codec: decode(codec, &config),
+                volume: props
+                    .get("Volume")
+                    .and_then(|v| v.0.as_u64())
+                    .map(|v| v.min(u64::from(MAX_VOLUME)) as u16),
+                // Reported in 1/10 ms.
+                delay: props
+                    .get("Delay")
+                    .and_then(|v| v.0.as_u64())
+                    .map(|d| Duration::from_micros(d * 100)),
+            })
+        })
+        .collect();
+    transports.sort_by(|a, b| a.path.cmp(&b.path));
+    transports
+}
+
+/// Set the absolute volume of the transport at `path`.
+pub async fn set_volume(bus: &BluezBus, path: &str, volume: u16) -> Result<(), dbus::Error> {
+    bus.proxy(path)
+        .set(TRANSPORT, "Volume", volume.min(MAX_VOLUME))
+        .await
+}
+
+// ── Codec decoding ──────────────────────────────────────────────────────────
+
+/// Decode an A2DP codec id and configuration blob (A2DP spec §4, BlueZ
+/// `a2dp-codecs.h`). Unknown codecs keep the raw bytes.
+pub fn decode(codec: u8, config: &[u8]) -> CodecInfo {
+    let decoded = match codec {
+        0x00 => sbc(config),
+        0x01 => mpeg12(config),
+        0x02 => aac(config),
+        0x06 => Some(named("LC3", Vec::new())),
+        0xff => vendor(config),
+        _ => None,
+    };
+    decoded.unwrap_or_else(|| CodecInfo {
+        name: format!("Codec 0x{codec:02x}"),
+        details: vec![hex(config)],
+    })
+}
+
+fn named(name: &str, details: Vec<String>) -> CodecInfo {
+    CodecInfo {
+        name: name.to_string(),
+        details,
+    }
+}
+
+/// SBC: frequency/channel mode, block length/subbands/allocation, bitpool.
+fn sbc(c: &[u8]) -> Option<CodecInfo> {
+    let [b0, b1, min_bitpool, max_bitpool] = *c.get(..4)? else {
+        return None;
+    };
+    let mut details = Vec::new();
+    details.extend(flag(
+        b0 >> 4,
+        &[
+            (0x8, "16 kHz"),
+            (0x4, "32 kHz"),
+            (0x2, "44.1 kHz"),
+            (0x1, "48 kHz"),
+        ],
+    ));
+    details.extend(channel_mode(b0 & 0x0f));
+    details.extend(flag(
+        b1 >> 4,
+        &[
+            (0x8, "4 blocks"),
+            (0x4, "8 blocks"),
+            (0x2, "12 blocks"),
+            (0x1, "16 blocks"),
+        ],
+    ));
+    details.extend(flag(
+        (b1 >> 2) & 0x3,
+        &[(0x2, "4 subbands"), (0x1, "8 subbands")],
+    ));
+    details.extend(flag(b1 & 0x3, &[(0x2, "SNR"), (0x1, "loudness")]));
+    details.push(format!("bitpool {min_bitpool}–{max_bitpool}"));
+    Some(named("SBC", details))
+}
+
+/// MPEG-1,2 Audio (MP3): layer, channel mode, frequency.
+fn mpeg12(c: &[u8]) -> Option<CodecInfo> {
+    let [b0, b1] = *c.get(..2)? else {
+        return None;
+    };
+    let mut details = Vec::new();
+    details.extend(flag(
+        b0 >> 5,
+        &[(0x4, "layer I"), (0x2, "layer II"), (0x1, "layer III")],
+    ));
+    details.extend(channel_mode(b0 & 0x0f));
+    details.extend(flag(
+        b1 & 0x3f,
+        &[
+            (0x20, "16 kHz"),
+            (0x10, "22.05 kHz"),
+            (0x08, "24 kHz"),
+            (0x04, "32 kHz"),
+            (0x02, "44.1 kHz"),
+            (0x01, "48 kHz"),
+        ],
+    ));
+    Some(named("MPEG-1,2 Audio", details))
+}
+
+/// AAC: object type, frequency, channels, bitrate and VBR.
+fn aac(c: &[u8]) -> Option<CodecInfo> {
+    let [b0, b1, b2, b3, b4, b5] = *c.get(..6)? else {
+        return None;
+    };
+    let mut details = Vec::new();
+    details.extend(flag(
+        b0,
+        &[
+            (0x80, "MPEG-2 AAC LC"),
+            (0x40, "MPEG-4 AAC LC"),
+            (0x20, "MPEG-4 AAC LTP"),
+            (0x10, "MPEG-4 AAC scalable"),
+            (0x08, "MPEG-4 HE-AAC"),
+            (0x04, "MPEG-4 HE-AACv2"),
+            (0x02, "MPEG-4 AAC-ELDv2"),
+        ],
+    ));
+    let frequency = u16::from(b1) << 4 | u16::from(b2 >> 4);
+    details.extend(flag(
+        frequency,
+        &[
+            (0x800, "8 kHz"),
+            (0x400, "11.025 kHz"),
+            (0x200, "12 kHz"),
+            (0x100, "16 kHz"),
+            (0x080, "22.05 kHz"),
+            (0x040, "24 kHz"),
+            (0x020, "32 kHz"),
+            (0x010, "44.1 kHz"),
+            (0x008, "48 kHz"),
+            (0x004, "64 kHz"),
+            (0x002, "88.2 kHz"),
+            (0x001, "96 kHz"),
+        ],
+    ));
+    details.extend(flag((b2 >> 2) & 0x3, &[(0x2, "mono"), (0x1, "stereo")]));
+    let bitrate = u32::from(b3 & 0x7f) << 16 | u32::from(b4) << 8 | u32::from(b5);
+    if bitrate > 0 {
+        details.push(format!("{} kbit/s", bitrate / 1000));
+    }
+    if b3 & 0x80 != 0 {
+        details.push("VBR".into());
+    }
+    Some(named("AAC", details))
+}
+
+/// Vendor codecs: a little-endian vendor id and codec id, then the codec's
+/// own configuration.
+fn vendor(c: &[u8]) -> Option<CodecInfo> {
+    let vendor_id = u32::from_le_bytes(c.get(..4)?.try_into().ok()?);
+    let codec_id = u16::from_le_bytes(c.get(4..6)?.try_into().ok()?);
+    let rest = &c[6..];
+    let aptx = |name| {
+        let b = *rest.first()?;
+        let mut details = Vec::new();
+        details.extend(flag(
+            b >> 4,
+            &[
+                (0x8, "16 kHz"),
+                (0x4, "32 kHz"),
+                (0x2, "44.1 kHz"),
+                (0x1, "48 kHz"),
+            ],
+        ));
+        details.extend(flag(b & 0x0f, &[(0x1, "mono"), (0x2, "stereo")]));
+        Some(named(name, details))
+    };
+    match (vendor_id, codec_id) {
+        (0x0000_004f, 0x0001) => aptx("aptX"),
+        (0x0000_00d7, 0x0024) => aptx("aptX HD"),
+        (0x0000_012d, 0x00aa) => {
+            let [frequency, channels] = *rest.get(..2)? else {
+                return None;
+            };
+            let mut details = Vec::new();
+            details.extend(flag(
+                frequency & 0x3f,
+                &[
+                    (0x20, "44.1 kHz"),
+                    (0x10, "48 kHz"),
+                    (0x08, "88.2 kHz"),
+                    (0x04, "96 kHz"),
+                    (0x02, "176.4 kHz"),
+                    (0x01, "192 kHz"),
+                ],
+            ));
+            details.extend(flag(
+                channels & 0x07,
+                &[(0x4, "mono"), (0x2, "dual channel"), (0x1, "stereo")],
+            ));
+            Some(named("LDAC", details))
+        }
+        _ => Some(named(
+            &format!("Vendor 0x{vendor_id:08x} codec 0x{codec_id:04x}"),
+            vec![hex(rest)],
+        )),
+    }
+}
+
+/// A2DP channel mode bits shared by SBC and MPEG.
+fn channel_mode(bits: u8) -> Option<String> {
+    flag(
+        bits,
+        &[
+            (0x8, "mono"),
+            (0x4, "dual channel"),
+            (0x2, "stereo"),
+            (0x1, "joint stereo"),
+        ],
+    )
+}
+
+/// Label of the first set bit in a configuration bitfield; a configured
+/// (rather than advertised) codec sets exactly one.
+fn flag<T>(bits: T, labels: &[(T, &str)]) -> Option<String>
+where
+    T: Copy + std::ops::BitAnd<Output = T> + PartialEq + Default,
+{
+    labels
+        .iter()
+        .find(|&&(bit, _)| bits & bit != T::default())
+        .map(|(_, label)| (*label).to_string())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
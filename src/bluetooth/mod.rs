@@ -1,3 +1,20 @@
+pub mod admin_policy;
 pub mod agent;
+pub mod assigned_numbers;
+pub mod blocklist;
+pub mod bluez_dbus;
+pub mod class;
+pub mod connection_profiles;
+pub mod device_sets;
+#[cfg(test)]
+mod fake_bus;
+pub mod gatt;
+pub mod kind;
+pub mod media_player;
+pub mod media_transport;
+pub mod obex;
+pub mod presentation;
+pub mod profiles;
+pub mod proximity;
 pub mod types;
 pub mod worker;
@@ -0,0 +1,527 @@
+//! OBEX Object Push: sending files to a paired device.
+//!
+//! File transfer runs in obexd, which serves `org.bluez.obex` on the
+//! session bus: `Client1.CreateSession` opens an OPP session,
+//! `ObjectPush1.SendFile` queues a file and returns a `Transfer1` object
+//! whose property changes track progress. bluer doesn't wrap obexd, so it
+//! gets its own connection.
+
+use std::collections::HashMap;
+use std::path::{Path as FsPath, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use bluer::Address;
+use dbus::arg::{PropMap, RefArg, Variant};
+use dbus::message::MatchRule;
+use dbus::nonblock::stdintf::org_freedesktop_dbus::Properties;
+use dbus::nonblock::{Proxy, SyncConnection};
+use dbus::Path;
+use futures::StreamExt;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use super::bluez_dbus;
+use super::types::BtEvent;
+
+pub const SERVICE: &str = "org.bluez.obex";
+const CLIENT: &str = "org.bluez.obex.Client1";
+const OBJECT_PUSH: &str = "org.bluez.obex.ObjectPush1";
+const TRANSFER: &str = "org.bluez.obex.Transfer1";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Progress state reported by obexd.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferStatus {
+    Queued,
+    Active,
+    Suspended,
+    Complete,
+    Error,
+    /// Over, but obexd dropped the transfer before saying how it ended.
+    Unknown,
+}
+
+impl TransferStatus {
+    fn parse(s: &str) -> Self {
+        match s {
+            "active" => Self::Active,
+            "suspended" => Self::Suspended,
+            "complete" => Self::Complete,
+            "error" => Self::Error,
+            _ => Self::Queued,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Queued => "Queued",
+            Self::Active => "Sending",
+            Self::Suspended => "Suspended",
+            Self::Complete => "Done",
+            Self::Error => "Failed",
+            Self::Unknown => "Unknown",
+        }
+    }
+
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Complete | Self::Error | Self::Unknown)
+    }
+}
+
+/// A file sent, or being sent, to a device.
+#[derive(Debug, Clone)]
+pub struct Transfer {
+    /// `Transfer1` object path.
+    pub id: String,
+    pub address: Address,
+    /// File name as sent to the device.
+    pub name: String,
+    pub size: Option<u64>,
+    pub transferred: u64,
+    pub status: TransferStatus,
+    /// When bytes started flowing.
+    pub active_since: Option<Instant>,
+    /// When the transfer completed or failed.
+    pub finished_at: Option<Instant>,
+}
+
+impl Transfer {
+    /// Share of the file sent, `0.0..=1.0`.
+    pub fn fraction(&self) -> Option<f64> {
+        match self.size {
+            Some(size) if size > 0 => Some((self.transferred as f64 / size as f64).min(1.0)),
+            _ => None,
+        }
+    }
+
+    /// Average speed in bytes per second since bytes started flowing.
+    pub fn speed(&self) -> Option<f64> {
+        let since = self.active_since?;
+        let elapsed = self
+            .finished_at
+            .unwrap_or_else(Instant::now)
+            .duration_since(since)
+            .as_secs_f64();
+        (elapsed > 0.0 && self.transferred > 0).then(|| self.transferred as f64 / elapsed)
+    }
+
+    /// Time left at the average speed.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.status.is_finished() {
+            return None;
+        }
+        let remaining = self.size?.saturating_sub(self.transferred);
+        Some(Duration::from_secs_f64(remaining as f64 / self.speed()?))
+    }
+
+    /// Whether every byte has gone out, going by the last progress report.
+    fn all_sent(&self) -> bool {
+        self.size
+            .is_some_and(|size| size > 0 && self.transferred >= size)
+    }
+
+    /// End the transfer without a final status from obexd.
+    fn finish(&mut self, status: TransferStatus) {
+        self.status = status;
+        self.finished_at.get_or_insert_with(Instant::now);
+        if status == TransferStatus::Complete {
+            self.transferred = self.size.unwrap_or(self.transferred);
+        }
+    }
+
+    /// Apply `Transfer1` properties (a full snapshot or a change set).
+    fn apply(&mut self, props: &PropMap) {
+        if let Some(status) = bluez_dbus::prop_str(props, "Status") {
+            let status = TransferStatus::parse(status);
+            if status == TransferStatus::Active && self.active_since.is_none() {
+                self.active_since = Some(Instant::now());
+            }
+            if status.is_finished() && self.finished_at.is_none() {
+                self.finished_at = Some(Instant::now());
+            }
+            self.status = status;
+        }
+        if let Some(size) = props.get("Size").and_then(|v| v.0.as_u64()) {
+            self.size = Some(size);
+        }
+        if let Some(sent) = props.get("Transferred").and_then(|v| v.0.as_u64()) {
+            self.transferred = sent;
+        }
+        if self.status == TransferStatus::Complete {
+            self.transferred = self.size.unwrap_or(self.transferred);
+        }
+    }
+}
+
+static SHARED: OnceLock<Option<ObexBus>> = OnceLock::new();
+
+/// The session bus connection, opened on first use from within the
+/// runtime. `None` when there is no session bus (e.g. run as root over SSH).
+pub fn shared() -> Option<&'static ObexBus> {
+    SHARED
+        .get_or_init(|| {
+            ObexBus::connect(SERVICE)
+                .map_err(|e| warn!("OBEX unavailable, no session bus: {e}"))
+                .ok()
+        })
+        .as_ref()
+}
+
+pub struct ObexBus {
+    conn: Arc<SyncConnection>,
+    /// Bus name obexd is reached at; [`SERVICE`] outside tests.
+    service: String,
+}
+
+impl ObexBus {
+    /// Open the session bus connection to obexd at `service` and spawn its
+    /// I/O driver on the current runtime.
+    pub fn connect(service: &str) -> Result<Self, dbus::Error> {
+        let (resource, conn) = dbus_tokio::connection::new_session_sync()?;
+        tokio::spawn(async move {
+            let err = resource.await;
+            warn!("OBEX D-Bus connection lost: {err}");
+        });
+        Ok(Self::new(conn, service))
+    }
+
+    /// Talk to obexd at `service` over an already driven connection.
+    pub fn new(conn: Arc<SyncConnection>, service: &str) -> Self {
+        conn.set_signal_match_mode(true);
+        Self {
+            conn,
+            service: service.to_string(),
+        }
+    }
+
+    fn proxy<'a>(&'a self, path: impl Into<Path<'a>>) -> Proxy<'a, Arc<SyncConnection>> {
+        Proxy::new(self.service.as_str(), path, TIMEOUT, self.conn.clone())
+    }
+
+    /// Cancel the transfer at `id`.
+    pub async fn cancel(&self, id: &str) -> Result<(), dbus::Error> {
+        self.proxy(id).method_call(TRANSFER, "Cancel", ()).await
+    }
+
+    /// Open an OPP session from adapter `source` to `address` and queue
+    /// `file`. The session is closed again if queueing fails.
+    async fn send_file(
+        &self,
+        source: Address,
+        address: Address,
+        file: &FsPath,
+    ) -> Result<(Transfer, Path<'static>), dbus::Error> {
+        let mut args: PropMap = HashMap::new();
+        args.insert("Target".into(), Variant(Box::new("opp".to_string())));
+        args.insert("Source".into(), Variant(Box::new(source.to_string())));
+        let (session,): (Path<'static>,) = self
+            .proxy("/org/bluez/obex")
+            .method_call(CLIENT, "CreateSession", (address.to_string(), args))
+            .await?;
+
+        let file_arg = file.to_string_lossy().into_owned();
+        let queued: Result<(Path<'static>, PropMap), _> = self
+            .proxy(session.clone())
+            .method_call(OBJECT_PUSH, "SendFile", (file_arg,))
+            .await;
+        let (path, props) = match queued {
+            Ok(queued) => queued,
+            Err(e) => {
+                self.remove_session(&session).await;
+                return Err(e);
+            }
+        };
+
+        let name = bluez_dbus::prop_str(&props, "Name")
+            .map(str::to_string)
+            .or_else(|| file.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+        let mut transfer = Transfer {
+            id: path.to_string(),
+            address,
+            name,
+            size: None,
+            transferred: 0,
+            status: TransferStatus::Queued,
+            active_since: None,
+            finished_at: None,
+        };
+        transfer.apply(&props);
+        Ok((transfer, session))
+    }
+
+    /// Report `transfer`'s progress until it finishes.
+    async fn follow(&self, transfer: &mut Transfer, tx: &mpsc::Sender<BtEvent>) {
+        // Small files can be gone before `SendFile` even returns.
+        if transfer.all_sent() {
+            transfer.finish(TransferStatus::Complete);
+            let _ = tx.send(BtEvent::TransferUpdated(transfer.clone())).await;
+            return;
+        }
+        let rule = MatchRule::new_signal("org.freedesktop.DBus.Properties", "PropertiesChanged")
+            .with_sender(self.service.clone())
+            .with_path(Path::from(transfer.id.clone()));
+        let (signals, changes) = match self.conn.add_match(rule).await {
+            Ok(signals) => signals.msg_stream(),
+            Err(e) => {
+                warn!("Cannot watch transfer {}: {e}", transfer.id);
+                return;
+            }
+        };
+        // obexd exiting or restarting doesn't end the stream above.
+        let rule = MatchRule::new_signal("org.freedesktop.DBus", "NameOwnerChanged")
+            .with_sender("org.freedesktop.DBus");
+        let (owners, owner_changes) = match self.conn.add_match(rule).await {
+            Ok(owners) => owners.msg_stream(),
+            Err(e) => {
+                warn!("Cannot watch transfer {}: {e}", transfer.id);
+                let _ = self.conn.remove_match(signals.token()).await;
+                return;
+            }
+        };
+        let vanished = owner_changes
+            .filter(|msg| std::future::ready(msg.get1::<&str>() == Some(&self.service)))
+            .map(|_| None);
+        let mut changes = futures::stream::select(changes.map(Some), vanished);
+
+        // Progress made before the match was in place.
+        match self.proxy(transfer.id.as_str()).get_all(TRANSFER).await {
+            Ok(props) => transfer.apply(&props),
+            // obexd drops transfers as soon as they finish, whether they
+            // went through, were rejected or failed.
+            Err(e) => {
+                debug!("Transfer {} already gone: {e}", transfer.id);
+                transfer.finish(TransferStatus::Unknown);
+            }
+        }
+        let _ = tx.send(BtEvent::TransferUpdated(transfer.clone())).await;
+
+        while !transfer.status.is_finished() {
+            let Some(Some(msg)) = changes.next().await else {
+                // obexd restarted or the bus went away: the transfer won't
+                // report again, so don't leave it sending forever.
+                warn!("Lost track of transfer {}", transfer.id);
+                transfer.finish(TransferStatus::Error);
+                let _ = tx.send(BtEvent::TransferUpdated(transfer.clone())).await;
+                break;
+            };
+            let Ok((_, changed)) = msg.read2::<&str, PropMap>() else {
+                continue;
+            };
+            transfer.apply(&changed);
+            let _ = tx.send(BtEvent::TransferUpdated(transfer.clone())).await;
+        }
+        let _ = self.conn.remove_match(signals.token()).await;
+        let _ = self.conn.remove_match(owners.token()).await;
+    }
+
+    async fn remove_session(&self, session: &Path<'static>) {
+        let removed: Result<(), _> = self
+            .proxy("/org/bluez/obex")
+            .method_call(CLIENT, "RemoveSession", (session.clone(),))
+            .await;
+        if let Err(e) = removed {
+            debug!("Cannot close OBEX session {session}: {e}");
+        }
+    }
+}
+
+/// Push `file` to `address` and report the transfer until it finishes.
+/// Spawned per file: session setup waits for the device to connect.
+pub async fn push(
+    bus: &'static ObexBus,
+    source: Address,
+    address: Address,
+    file: PathBuf,
+    tx: mpsc::Sender<BtEvent>,
+) {
+    let (mut transfer, session) = match bus.send_file(source, address, &file).await {
+        Ok(queued) => queued,
+        Err(e) => {
+            let name = file
+                .file_name()
+                .unwrap_or(file.as_os_str())
+                .to_string_lossy();
+            let _ = tx
+                .send(BtEvent::Error(format!("Cannot send {name}: {e}")))
+                .await;
+            return;
+        }
+    };
+    let _ = tx.send(BtEvent::TransferUpdated(transfer.clone())).await;
+    bus.follow(&mut transfer, &tx).await;
+    bus.remove_session(&session).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use dbus::channel::Sender;
+    use dbus::message::SignalArgs;
+    use dbus::nonblock::stdintf::org_freedesktop_dbus::PropertiesPropertiesChanged;
+    use dbus::nonblock::SyncConnection;
+
+    use super::*;
+    use crate::bluetooth::fake_bus::{self, PrivateBus};
+
+    const FAKE_OBEX: &str = "test.voidlink.obex";
+    const SESSION: &str = "/org/bluez/obex/client/session0";
+    const TRANSFER_PATH: &str = "/org/bluez/obex/client/session0/transfer0";
+    const SIZE: u64 = 1000;
+
+    type Calls = Arc<Mutex<Vec<String>>>;
+
+    fn props(entries: Vec<(&str, Box<dyn RefArg>)>) -> PropMap {
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), Variant(value)))
+            .collect()
+    }
+
+    fn progress(conn: &SyncConnection, changed: PropMap) {
+        let signal = PropertiesPropertiesChanged {
+            interface_name: TRANSFER.into(),
+            changed_properties: changed,
+            invalidated_properties: Vec::new(),
+        };
+        let _ = conn.send(signal.to_emit_message(&Path::from(TRANSFER_PATH)));
+    }
+
+    /// obexd with one queued transfer; `Cancel` fails it. Unless `keep` is
+    /// set, the transfer is gone by the time it is looked up.
+    async fn fake_obexd(daemon: &PrivateBus, calls: Calls, keep: bool) -> Arc<SyncConnection> {
+        daemon
+            .serve(FAKE_OBEX, move |msg, conn| {
+                let member = msg.member().map(|m| m.to_string()).unwrap_or_default();
+                calls.lock().unwrap().push(member.clone());
+                let queued = || {
+                    props(vec![
+                        ("Name", Box::new("notes.txt".to_string())),
+                        ("Size", Box::new(SIZE)),
+                        ("Transferred", Box::new(0u64)),
+                        ("Status", Box::new("queued".to_string())),
+                    ])
+                };
+                match member.as_str() {
+                    "CreateSession" => msg.method_return().append1(Path::from(SESSION)),
+                    "SendFile" => msg
+                        .method_return()
+                        .append2(Path::from(TRANSFER_PATH), queued()),
+                    "GetAll" if keep => msg.method_return().append1(queued()),
+                    "Cancel" => {
+                        progress(conn, props(vec![("Status", Box::new("error".to_string()))]));
+                        msg.method_return()
+                    }
+                    "RemoveSession" => msg.method_return(),
+                    _ => fake_bus::unknown_method(msg),
+                }
+            })
+            .await
+    }
+
+    /// Start a push and wait until it is being followed.
+    async fn start_push(
+        daemon: &PrivateBus,
+    ) -> (
+        &'static ObexBus,
+        mpsc::Receiver<BtEvent>,
+        tokio::task::JoinHandle<()>,
+    ) {
+        let bus: &'static ObexBus = Box::leak(Box::new(ObexBus::new(daemon.connect(), FAKE_OBEX)));
+        let (tx, mut rx) = mpsc::channel(16);
+        let task = tokio::spawn(push(
+            bus,
+            "00:11:22:33:44:55".parse().unwrap(),
+            "AA:BB:CC:DD:EE:FF".parse().unwrap(),
+            PathBuf::from("/tmp/notes.txt"),
+            tx,
+        ));
+        // Once from `SendFile`, once more when the transfer is watched.
+        for _ in 0..2 {
+            let transfer = next_update(&mut rx).await;
+            assert_eq!(transfer.name, "notes.txt");
+            assert_eq!(transfer.status, TransferStatus::Queued);
+        }
+        (bus, rx, task)
+    }
+
+    async fn next_update(rx: &mut mpsc::Receiver<BtEvent>) -> Transfer {
+        match tokio::time::timeout(Duration::from_secs(5), rx.recv()).await {
+            Ok(Some(BtEvent::TransferUpdated(transfer))) => transfer,
+            other => panic!("expected a transfer update, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn follows_a_transfer_to_completion() {
+        let daemon = PrivateBus::start().expect("this test needs dbus-daemon");
+        let calls = Calls::default();
+        let obexd = fake_obexd(&daemon, calls.clone(), true).await;
+        let (_, mut rx, task) = start_push(&daemon).await;
+
+        progress(
+            &obexd,
+            props(vec![
+                ("Status", Box::new("active".to_string())),
+                ("Transferred", Box::new(SIZE / 2)),
+            ]),
+        );
+        let transfer = next_update(&mut rx).await;
+        assert_eq!(transfer.status, TransferStatus::Active);
+        assert_eq!(transfer.fraction(), Some(0.5));
+
+        progress(
+            &obexd,
+            props(vec![("Status", Box::new("complete".to_string()))]),
+        );
+        let transfer = next_update(&mut rx).await;
+        assert_eq!(transfer.status, TransferStatus::Complete);
+        assert_eq!(transfer.transferred, SIZE);
+        assert!(transfer.finished_at.is_some());
+
+        task.await.unwrap();
+        assert_eq!(calls.lock().unwrap().last().unwrap(), "RemoveSession");
+    }
+
+    #[tokio::test]
+    async fn cancels_a_transfer() {
+        let daemon = PrivateBus::start().expect("this test needs dbus-daemon");
+        let calls = Calls::default();
+        let _obexd = fake_obexd(&daemon, calls.clone(), true).await;
+        let (bus, mut rx, task) = start_push(&daemon).await;
+
+        bus.cancel(TRANSFER_PATH).await.unwrap();
+        let transfer = next_update(&mut rx).await;
+        assert_eq!(transfer.status, TransferStatus::Error);
+
+        task.await.unwrap();
+        let calls = calls.lock().unwrap();
+        assert!(calls.contains(&"Cancel".to_string()));
+        assert_eq!(calls.last().unwrap(), "RemoveSession");
+    }
+
+    #[tokio::test]
+    async fn a_vanished_transfer_has_an_unknown_outcome() {
+        let daemon = PrivateBus::start().expect("this test needs dbus-daemon");
+        let calls = Calls::default();
+        let _obexd = fake_obexd(&daemon, calls.clone(), false).await;
+        let bus: &'static ObexBus = Box::leak(Box::new(ObexBus::new(daemon.connect(), FAKE_OBEX)));
+        let (tx, mut rx) = mpsc::channel(16);
+        push(
+            bus,
+            "00:11:22:33:44:55".parse().unwrap(),
+            "AA:BB:CC:DD:EE:FF".parse().unwrap(),
+            PathBuf::from("/tmp/notes.txt"),
+            tx,
+        )
+        .await;
+
+        assert_eq!(next_update(&mut rx).await.status, TransferStatus::Queued);
+        let transfer = next_update(&mut rx).await;
+        assert_eq!(transfer.status, TransferStatus::Unknown);
+        assert!(transfer.finished_at.is_some());
+        assert_eq!(calls.lock().unwrap().last().unwrap(), "RemoveSession");
+    }
+}
@@ -0,0 +1,186 @@
+//! Characteristic Presentation Format (descriptor 0x2904) decoding and
+//! value formatting for the GATT value log.
+
+use serde::{Deserialize, Serialize};
+
+/// Parsed Characteristic Presentation Format descriptor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresentationFormat {
+    /// Format type (`0x04` = uint8, `0x0E` = sint16, `0x19` = utf8s, …).
+    pub format: u8,
+    /// Base-10 exponent applied to integer values.
+    pub exponent: i8,
+    /// Unit UUID (`0x272F` = degrees Celsius, …).
+    pub unit: u16,
+}
+
+impl PresentationFormat {
+    /// Parse the 7-byte descriptor value.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        match *data {
+            [format, exponent, unit_lo, unit_hi, ..] => Some(Self {
+                format,
+                exponent: exponent as i8,
+                unit: u16::from_le_bytes([unit_lo, unit_hi]),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Format `value` per this descriptor, e.g. `21.5 °C`. `None` when the
+    /// value doesn't fit the declared format.
+    pub fn format_value(&self, value: &[u8]) -> Option<String> {
+        let text = match self.format {
+            0x01 => (value.first()? & 1 == 1).to_string(),
+            0x02..=0x04 => self.scaled(i128::from(*value.first()?)),
+            0x05 | 0x06 => self.scaled(le_uint(value, 2)? as i128),
+            0x07 => self.scaled(le_uint(value, 3)? as i128),
+            0x08 => self.scaled(le_uint(value, 4)? as i128),
+            0x09 => self.scaled(le_uint(value, 6)? as i128),
+            0x0A => self.scaled(le_uint(value, 8)? as i128),
+            0x0C => self.scaled(i128::from(*value.first()? as i8)),
+            0x0D => self.scaled(le_sint(value, 2, 12)?),
+            0x0E => self.scaled(le_sint(value, 2, 16)?),
+            0x0F => self.scaled(le_sint(value, 3, 24)?),
+            0x10 => self.scaled(le_sint(value, 4, 32)?),
+            0x11 => self.scaled(le_sint(value, 6, 48)?),
+            0x12 => self.scaled(le_sint(value, 8, 64)?),
+            0x14 => f32::from_le_bytes(value.get(..4)?.try_into().ok()?).to_string(),
+            0x15 => f64::from_le_bytes(value.get(..8)?.try_into().ok()?).to_string(),
+            0x16 => sfloat(le_uint(value, 2)? as u16)?,
+            0x19 => String::from_utf8(value.to_vec()).ok()?,
+            _ => return None,
+        };
+        Some(match unit_symbol(self.unit) {
+            Some(unit) if !unit.is_empty() => format!("{text} {unit}"),
+            _ => text,
+        })
+    }
+
+    /// Apply the base-10 exponent to an integer value.
+    fn scaled(&self, raw: i128) -> String {
+        match self.exponent {
+            0 => raw.to_string(),
+            // The exponent comes from the device; past i128 range, show it
+            // in scientific notation rather than overflow.
+            e if e > 0 => 10i128
+                .checked_pow(e as u32)
+                .and_then(|scale| raw.checked_mul(scale))
+                .map_or_else(|| format!("{raw}e{e}"), |v| v.to_string()),
+            e => {
+                let digits = e.unsigned_abs() as usize;
+                format!("{:.*}", digits, raw as f64 / 10f64.powi(digits as i32))
+            }
+        }
+    }
+}
+
+/// Little-endian unsigned integer from the first `len` bytes.
+fn le_uint(value: &[u8], len: usize) -> Option<u64> {
+    let bytes = value.get(..len)?;
+    Some(
+        bytes
+            .iter()
+            .rev()
+            .fold(0u64, |acc, &b| (acc << 8) | u64::from(b)),
+    )
+}
+
+/// Little-endian signed integer of `bits` width stored in `len` bytes.
+fn le_sint(value: &[u8], len: usize, bits: u32) -> Option<i128> {
+    let raw = le_uint(value, len)? as i128 & ((1i128 << bits) - 1);
+    Some(if (raw >> (bits - 1)) & 1 == 1 {
+        raw - (1i128 << bits)
+    } else {
+        raw
+    })
+}
+
+/// IEEE 11073 16-bit SFLOAT: 4-bit exponent, 12-bit mantissa.
+fn sfloat(raw: u16) -> Option<String> {
+    let mantissa = raw & 0x0FFF;
+    match mantissa {
+        0x07FF => return Some("NaN".into()),
+        0x0800 => return Some("NRes".into()),
+        0x07FE => return Some("+INF".into()),
+        0x0802 => return Some("-INF".into()),
+        _ => {}
+    }
+    let mantissa = if mantissa & 0x0800 != 0 {
+        i32::from(mantissa) - 0x1000
+    } else {
+        i32::from(mantissa)
+    };
+    let exponent = (raw >> 12) as i32;
+    let exponent = if exponent & 0x8 != 0 {
+        exponent - 16
+    } else {
+        exponent
+    };
+    Some((f64::from(mantissa) * 10f64.powi(exponent)).to_string())
+}
+
+/// Symbol for common GATT unit UUIDs.
+fn unit_symbol(unit: u16) -> Option<&'static str> {
+    Some(match unit {
+        0x2700 => "",
+        0x2701 => "m",
+        0x2702 => "kg",
+        0x2703 => "s",
+        0x2704 => "A",
+        0x2705 => "K",
+        0x2722 => "Hz",
+        0x2724 => "Pa",
+        0x2725 => "J",
+        0x2726 => "W",
+        0x2728 => "V",
+        0x272F => "°C",
+        0x2731 => "lx",
+        0x27A7 => "bpm",
+        0x27AD => "%",
+        0x27C3 => "dB",
+        _ => return None,
+    })
+}
+
+/// Format a raw value for display: via the presentation format when known,
+/// otherwise as hex plus printable text.
+pub fn format_value(format: Option<&PresentationFormat>, value: &[u8]) -> String {
+    if let Some(text) = format.and_then(|f| f.format_value(value)) {
+        return text;
+    }
+    let hex = value
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    match std::str::from_utf8(value) {
+        Ok(text) if !text.is_empty() && text.chars().all(|c| !c.is_control()) => {
+            format!("{hex}  \"{text}\"")
+        }
+        _ if value.is_empty() => "(empty)".into(),
+        _ => hex,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(exponent: i8) -> PresentationFormat {
+        PresentationFormat {
+            format: 0x0E,
+            exponent,
+            unit: 0x2700,
+        }
+    }
+
+    #[test]
+    fn exponents_scale_without_overflow() {
+        assert_eq!(format(2).scaled(-15), "-1500");
+        assert_eq!(format(-2).scaled(2150), "21.50");
+        assert_eq!(format(38).scaled(1), format!("1{}", "0".repeat(38)));
+        assert_eq!(format(39).scaled(7), "7e39");
+        assert_eq!(format(i8::MAX).scaled(-3), "-3e127");
+    }
+}
@@ -0,0 +1,253 @@
+//! Built-in clients for standard GATT profiles: Battery Service, Device
+//! Information, Heart Rate and Environmental Sensing.
+//!
+//! When a device connects, a monitor task reads the characteristics it knows,
+//! subscribes to the ones that notify, and forwards decoded readings to the
+//! UI until the device disconnects.
+
+use std::collections::HashMap;
+use std::pin::Pin;
+
+use bluer::{Adapter, Address, Device, UuidExt};
+use futures::{future, Stream, StreamExt};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use super::types::BtEvent;
+
+const BATTERY_SERVICE: u16 = 0x180F;
+const DEVICE_INFORMATION: u16 = 0x180A;
+const HEART_RATE: u16 = 0x180D;
+const ENVIRONMENTAL_SENSING: u16 = 0x181A;
+
+/// Device Information Service string fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DisField {
+    Manufacturer,
+    Model,
+    Serial,
+    Hardware,
+    Firmware,
+    Software,
+}
+
+impl DisField {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Manufacturer => "Maker",
+            Self::Model => "Model",
+            Self::Serial => "Serial",
+            Self::Hardware => "HW",
+            Self::Firmware => "FW",
+            Self::Software => "SW",
+        }
+    }
+}
+
+/// Decoded Heart Rate Measurement (0x2A37).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeartRate {
+    pub bpm: u16,
+    /// `None` when the sensor doesn't support contact detection.
+    pub contact: Option<bool>,
+    /// Energy expended in kJ, when reported.
+    pub energy_kj: Option<u16>,
+    /// RR intervals in 1/1024 s.
+    pub rr_intervals: Vec<u16>,
+}
+
+/// Environmental Sensing measurement.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EnvReading {
+    /// °C.
+    Temperature(f64),
+    /// Relative humidity, %.
+    Humidity(f64),
+    /// Pa.
+    Pressure(f64),
+    UvIndex(u8),
+}
+
+/// A decoded value from one of the standard profiles.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProfileReading {
+    Battery(u8),
+    DeviceInfo(DisField, String),
+    HeartRate(HeartRate),
+    Environment(EnvReading),
+}
+
+/// Decode a characteristic value by its 16-bit UUID. `None` for unknown
+/// characteristics and malformed values.
+pub fn decode(characteristic: u16, value: &[u8]) -> Option<ProfileReading> {
+    let dis = |field| {
+        let text = String::from_utf8_lossy(value);
+        let text = text.trim_matches(char::from(0)).trim();
+        (!text.is_empty()).then(|| ProfileReading::DeviceInfo(field, text.to_string()))
+    };
+    let env = ProfileReading::Environment;
+    match characteristic {
+        0x2A19 => Some(ProfileReading::Battery((*value.first()?).min(100))),
+        0x2A29 => dis(DisField::Manufacturer),
+        0x2A24 => dis(DisField::Model),
+        0x2A25 => dis(DisField::Serial),
+        0x2A27 => dis(DisField::Hardware),
+        0x2A26 => dis(DisField::Firmware),
+        0x2A28 => dis(DisField::Software),
+        0x2A37 => decode_heart_rate(value).map(ProfileReading::HeartRate),
+        0x2A6E => {
+            let raw = i16::from_le_bytes(value.get(..2)?.try_into().ok()?);
+            // 0x8000 = "value is not known".
+            (raw != i16::MIN).then(|| env(EnvReading::Temperature(f64::from(raw) / 100.0)))
+        }
+        0x2A6F => {
+            let raw = u16::from_le_bytes(value.get(..2)?.try_into().ok()?);
+            (raw != 0xFFFF).then(|| env(EnvReading::Humidity(f64::from(raw) / 100.0)))
+        }
+        0x2A6D => {
+            let raw = u32::from_le_bytes(value.get(..4)?.try_into().ok()?);
+            Some(env(EnvReading::Pressure(f64::from(raw) / 10.0)))
+        }
+        0x2A76 => Some(env(EnvReading::UvIndex(*value.first()?))),
+        _ => None,
+    }
+}
+
+/// Characteristics [`decode`] understands.
+fn is_known(characteristic: u16) -> bool {
+    matches!(
+        characteristic,
+        0x2A19 | 0x2A24..=0x2A29 | 0x2A37 | 0x2A6D..=0x2A6F | 0x2A76
+    )
+}
+
+/// Heart Rate Measurement: flags, then u8/u16 BPM, optional energy expended
+/// and any number of RR intervals.
+fn decode_heart_rate(value: &[u8]) -> Option<HeartRate> {
+    let (&flags, rest) = value.split_first()?;
+    let u16_at = |bytes: &[u8], at: usize| -> Option<u16> {
+        Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+    };
+    let (bpm, mut at) = if flags & 0x01 != 0 {
+        (u16_at(rest, 0)?, 2)
+    } else {
+        (u16::from(*rest.first()?), 1)
+    };
+    let contact = (flags & 0x04 != 0).then_some(flags & 0x02 != 0);
+    let energy_kj = if flags & 0x08 != 0 {
+        let energy = u16_at(rest, at)?;
+        at += 2;
+        Some(energy)
+    } else {
+        None
+    };
+    let rr_intervals = if flags & 0x10 != 0 {
+        rest.get(at..)?
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    Some(HeartRate {
+        bpm,
+        contact,
+        energy_kj,
+        rr_intervals,
+    })
+}
+
+type ReadingStream = Pin<Box<dyn Stream<Item = ProfileReading> + Send>>;
+
+/// Read and subscribe to every known characteristic of `device`, forwarding
+/// readings until all notification streams end.
+async fn monitor(device: Device, evt_tx: mpsc::Sender<BtEvent>) {
+    let address = device.address();
+    let send = |reading| {
+        let evt_tx = evt_tx.clone();
+        async move {
+            evt_tx
+                .send(BtEvent::ProfileReading { address, reading })
+                .await
+                .is_ok()
+        }
+    };
+
+    // Waits for BlueZ to resolve services.
+    let Ok(services) = device.services().await else {
+        return;
+    };
+    let mut streams: Vec<ReadingStream> = Vec::new();
+    for service in services {
+        let service_uuid = service.uuid().await.ok().and_then(|u| u.as_u16());
+        if !matches!(
+            service_uuid,
+            Some(BATTERY_SERVICE | DEVICE_INFORMATION | HEART_RATE | ENVIRONMENTAL_SENSING)
+        ) {
+            continue;
+        }
+        for characteristic in service.characteristics().await.unwrap_or_default() {
+            let Some(uuid) = characteristic.uuid().await.ok().and_then(|u| u.as_u16()) else {
+                continue;
+            };
+            if !is_known(uuid) {
+                continue;
+            }
+            let flags = characteristic.flags().await.unwrap_or_default();
+            if flags.read {
+                if let Some(reading) = characteristic
+                    .read()
+                    .await
+                    .ok()
+                    .and_then(|v| decode(uuid, &v))
+                {
+                    if !send(reading).await {
+                        return;
+                    }
+                }
+            }
+            if flags.notify || flags.indicate {
+                if let Ok(stream) = characteristic.notify().await {
+                    streams.push(Box::pin(
+                        stream.filter_map(move |v| future::ready(decode(uuid, &v))),
+                    ));
+                }
+            }
+        }
+    }
+
+    let mut readings = futures::stream::select_all(streams);
+    while let Some(reading) = readings.next().await {
+        if !send(reading).await {
+            return;
+        }
+    }
+}
+
+/// Profile monitor tasks of connected devices.
+#[derive(Default)]
+pub struct ProfileMonitors {
+    tasks: HashMap<Address, JoinHandle<()>>,
+}
+
+impl ProfileMonitors {
+    /// Start monitoring `address` unless it already is.
+    pub fn start(&mut self, adapter: &Adapter, address: Address, evt_tx: &mpsc::Sender<BtEvent>) {
+        self.tasks.retain(|_, task| !task.is_finished());
+        if self.tasks.contains_key(&address) {
+            return;
+        }
+        let Ok(device) = adapter.device(address) else {
+            return;
+        };
+        let task = tokio::spawn(monitor(device, evt_tx.clone()));
+        self.tasks.insert(address, task);
+    }
+
+    /// Stop monitoring (disconnect); dropping the streams ends the sessions.
+    pub fn stop(&mut self, address: Address) {
+        if let Some(task) = self.tasks.remove(&address) {
+            task.abort();
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Proximity watchlist, evaluated in the worker on every sighting.
+//!
+//! A watched device *enters* range when its RSSI reaches `enter_rssi` (or it
+//! connects) and *leaves* when its RSSI drops below `leave_rssi`, when it
+//! hasn't been heard from for `leave_after_secs`, or when BlueZ removes it.
+//! Keeping `leave_rssi` below `enter_rssi` gives hysteresis so a device
+//! hovering at the edge of range doesn't flap.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use regex::Regex;
+use tokio::time::Instant;
+
+use super::types::{Address, BtEvent, DeviceInfo};
+
+/// What a watchlist rule matches against.
+#[derive(Debug, Clone)]
+pub enum WatchTarget {
+    Address(Address),
+    /// Case-insensitive pattern over the device's display name.
+    Name(Regex),
+}
+
+/// One watchlist entry from the config.
+#[derive(Debug, Clone)]
+pub struct WatchRule {
+    pub label: String,
+    pub target: WatchTarget,
+    pub enter_rssi: i16,
+    pub leave_rssi: i16,
+}
+
+impl WatchRule {
+    fn matches(&self, info: &DeviceInfo) -> bool {
+        match &self.target {
+            WatchTarget::Address(addr) => *addr == info.address,
+            WatchTarget::Name(re) => re.is_match(info.display_name()),
+        }
+    }
+}
+
+/// A matched device currently considered in range.
+struct Present {
+    name: String,
+    last_heard: Instant,
+    connected: bool,
+}
+
+/// Watchlist state: which (rule, device) pairs are in range.
+pub struct Proximity {
+    rules: &'static [WatchRule],
+    leave_after: Duration,
+    present: HashMap<(usize, Address), Present>,
+}
+
+impl Proximity {
+    pub fn new() -> Self {
+        let cfg = &crate::config::get().watchlist;
+        Self {
+            rules: &cfg.rules,
+            leave_after: Duration::from_secs(cfg.leave_after_secs),
+            present: HashMap::new(),
+        }
+    }
+
+    /// Evaluate a device snapshot against every rule.
+    pub fn observe(&mut self, info: &DeviceInfo) -> Vec<BtEvent> {
+        let mut events = Vec::new();
+        // Only snapshots taken on an advertisement / property change count
+        // as hearing from the device.
+        let heard = info.last_seen.is_some() || info.connected;
+
+        for (rule_idx, rule) in self.rules.iter().enumerate() {
+            if !rule.matches(info) {
+                continue;
+            }
+            let key = (rule_idx, info.address);
+            match self.present.get_mut(&key) {
+                Some(p) => {
+                    if info.rssi.is_some_and(|r| r < rule.leave_rssi) && !info.connected {
+                        self.present.remove(&key);
+                        events.push(left(key, info.display_name().to_string()));
+                        continue;
+                    }
+                    // A disconnect starts the leave timer afresh.
+                    if heard || p.connected {
+                        p.last_heard = Instant::now();
+                    }
+                    p.connected = info.connected;
+                }
+                None => {
+                    let strong = info.rssi.is_some_and(|r| r >= rule.enter_rssi);
+                    if heard && (strong || info.connected) {
+                        self.present.insert(
+                            key,
+                            Present {
+                                name: info.display_name().to_string(),
+                                last_heard: Instant::now(),
+                                connected: info.connected,
+                            },
+                        );
+                        events.push(BtEvent::WatchEntered {
+                            rule: rule_idx,
+                            address: info.address,
+                            name: info.display_name().to_string(),
+                            rssi: info.rssi,
+                        });
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    /// The device is gone from BlueZ: it has left every rule's range.
+    pub fn remove(&mut self, address: &Address) -> Vec<BtEvent> {
+        let keys: Vec<_> = self
+            .present
+            .keys()
+            .filter(|(_, addr)| addr == address)
+            .copied()
+            .collect();
+        keys.into_iter()
+            .filter_map(|key| self.present.remove(&key).map(|p| left(key, p.name)))
+            .collect()
+    }
+
+    /// Earliest time a present, disconnected device times out.
+    pub fn next_expiry(&self) -> Option<Instant> {
+        self.present
+            .values()
+            .filter(|p| !p.connected)
+            .map(|p| p.last_heard + self.leave_after)
+            .min()
+    }
+
+    /// Drop devices not heard from for `leave_after_secs`.
+    pub fn expire(&mut self) -> Vec<BtEvent> {
+        let now = Instant::now();
+        let leave_after = self.leave_after;
+        let expired: Vec<_> = self
+            .present
+            .iter()
+            .filter(|(_, p)| !p.connected && p.last_heard + leave_after <= now)
+            .map(|(&key, _)| key)
+            .collect();
+        expired
+            .into_iter()
+            .filter_map(|key| self.present.remove(&key).map(|p| left(key, p.name)))
+            .collect()
+    }
+}
+
+fn left((rule, address): (usize, Address), name: String) -> BtEvent {
+    BtEvent::WatchLeft {
+        rule,
+        address,
+        name,
+    }
+}
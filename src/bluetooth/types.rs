@@ -2,10 +2,30 @@
 //! Bluetooth worker task. All types are plain data — no `bluer` handles cross
 //! the channel boundary, keeping the TUI thread free of D-Bus concerns.
 
+use std::collections::BTreeMap;
 use std::fmt;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 /// Re-export bluer's Address so callers don't need a direct bluer dependency.
 pub use bluer::Address;
+/// Re-export bluer's AddressType (BR/EDR, LE public, LE random).
+pub use bluer::AddressType;
+/// Re-export bluer's Uuid for the same reason.
+pub use bluer::Uuid;
+
+use bluer::UuidExt;
+
+pub use super::admin_policy::AdminPolicy;
+use super::class::DeviceClass;
+pub use super::connection_profiles::{ProfileState, ProfileStatus};
+pub use super::device_sets::DeviceSet;
+pub use super::kind::{DeviceCategory, DeviceKind};
+pub use super::media_player::{MediaControl, MediaPlayer, PlaybackStatus};
+pub use super::media_transport::{MediaTransport, MAX_VOLUME};
+pub use super::obex::{Transfer, TransferStatus};
+pub use super::presentation::PresentationFormat;
+pub use super::profiles::ProfileReading;
 
 // ─── UI → Worker commands ────────────────────────────────────────────────────
 
@@ -20,20 +40,74 @@ pub enum BtCommand {
     StartScan,
     /// Stop active device discovery.
     StopScan,
+    /// Enable/disable the background "scan N seconds every M minutes" mode.
+    SetPeriodicScan(bool),
     /// Full lifecycle: pair → trust → connect.
     Connect(Address),
+    /// Connect a single profile (e.g. A2DP Sink only) by its remote UUID.
+    ConnectProfile(Address, Uuid),
+    /// Disconnect a single profile.
+    DisconnectProfile(Address, Uuid),
+    /// Report the device's connectable profiles and their state.
+    QueryProfiles(Address),
     /// Graceful disconnect.
     Disconnect(Address),
+    /// Connect every member of a coordinated set that isn't connected.
+    ConnectSet(Vec<Address>),
+    /// Disconnect every member of a coordinated set.
+    DisconnectSet(Vec<Address>),
+    /// Remove every member of a coordinated set.
+    RemoveSet(Vec<Address>),
     /// Initiate pairing only.
     Pair(Address),
+    /// Change an adapter setting (alias, discoverable, pairable, timeouts).
+    SetAdapter(AdapterSetting),
+    /// Report the admin policy service allowlist and affected devices.
+    QueryAdminPolicy,
+    /// Replace the admin policy service allowlist (empty = allow all).
+    SetServiceAllowList(Vec<Uuid>),
+    /// Allow (`true`) or forbid a device to wake the host from suspend.
+    SetWakeAllowed(Address, bool),
+    /// Block (`true`) or unblock a device.
+    SetBlocked(Address, bool),
     /// Toggle the trusted flag on a device.
     Trust(Address),
     /// Remove a cached/paired device.
     RemoveDevice(Address),
     /// Re-snapshot a single device's properties.
     RefreshDevice(Address),
+    /// Remote control action for the media player at an object path.
+    MediaControl(String, MediaControl),
+    /// Set the absolute volume of the audio transport at an object path.
+    SetTransportVolume(String, u16),
+    /// Send a file to a device over OBEX Object Push.
+    SendFile(Address, PathBuf),
+    /// Cancel the OBEX transfer at an object path.
+    CancelTransfer(String),
     /// Set a custom alias (friendly name) on a device.
     SetAlias(Address, String),
+    /// Replace the discovery filter. Restarts discovery if it is running.
+    SetDiscoveryFilter(ScanFilter),
+    /// Enumerate the GATT database of a connected LE device, first dropping
+    /// its notification subscriptions.
+    DiscoverGatt(Address),
+    /// Enumerate the GATT database and read every readable characteristic,
+    /// for export.
+    SnapshotGatt(Address),
+    /// Read a characteristic value.
+    GattRead(GattTarget),
+    /// Write a characteristic value, with or without response.
+    GattWrite {
+        target: GattTarget,
+        value: Vec<u8>,
+        with_response: bool,
+    },
+    /// Start notifications / indications on a characteristic.
+    GattSubscribe(GattTarget),
+    /// Stop notifications / indications on a characteristic.
+    GattUnsubscribe(GattTarget),
+    /// Stop every subscription on a device (GATT browser closed).
+    GattUnsubscribeAll(Address),
 }
 
 // ─── Worker → UI events ─────────────────────────────────────────────────────
@@ -43,6 +117,16 @@ pub enum BtCommand {
 pub enum BtEvent {
     /// Full adapter state snapshot.
     AdapterState(AdapterInfo),
+    /// Coordinated sets (e.g. earbud pairs) and their members.
+    DeviceSets(Vec<DeviceSet>),
+    /// AVRCP media players of connected devices.
+    MediaPlayers(Vec<MediaPlayer>),
+    /// Audio transports (negotiated codec, volume) of connected devices.
+    MediaTransports(Vec<MediaTransport>),
+    /// Progress of an OBEX file transfer.
+    TransferUpdated(Transfer),
+    /// Admin policy allowlist and the devices it affects.
+    AdminPolicy(AdminPolicy),
     /// A new or updated device was discovered / properties changed.
     DeviceFound(DeviceInfo),
     /// A device's properties were updated (same struct, fresh snapshot).
@@ -54,6 +138,8 @@ pub enum BtEvent {
         address: Address,
         success: bool,
         error: Option<String>,
+        /// Wall time spent in the pair → trust → connect lifecycle.
+        elapsed: Duration,
     },
     /// Result of a pairing attempt.
     PairResult {
@@ -64,12 +150,179 @@ pub enum BtEvent {
     },
     /// BlueZ is requesting the user confirm/view a PIN.
     PinRequest { address: Address, pin: String },
-    /// Scanning state changed.
-    ScanningChanged(bool),
+    /// Scanning state changed. Only reported as `false` once BlueZ has
+    /// actually stopped discovery.
+    ScanningChanged {
+        scanning: bool,
+        /// When the scan stops on its own (`None` = until stopped).
+        deadline: Option<Instant>,
+    },
+    /// Periodic scan schedule changed.
+    PeriodicScanChanged {
+        /// When the next periodic scan starts (`None` = periodic mode off).
+        next: Option<Instant>,
+    },
+    /// A device matching watchlist rule `rule` came into range.
+    WatchEntered {
+        rule: usize,
+        address: Address,
+        name: String,
+        rssi: Option<i16>,
+    },
+    /// A watched device left range (weak signal, timeout or removed).
+    WatchLeft {
+        rule: usize,
+        address: Address,
+        name: String,
+    },
+    /// GATT services, characteristics and descriptors of a device.
+    GattDiscovered {
+        address: Address,
+        services: Vec<GattService>,
+    },
+    /// GATT database with characteristic values filled in (`SnapshotGatt`).
+    GattSnapshot {
+        address: Address,
+        services: Vec<GattService>,
+    },
+    /// GATT enumeration failed (not connected, services unresolved, …).
+    GattFailed { address: Address, error: String },
+    /// A characteristic value was read or notified.
+    GattValue {
+        target: GattTarget,
+        value: Vec<u8>,
+        notified: bool,
+    },
+    /// A characteristic write completed.
+    GattWritten { target: GattTarget, len: usize },
+    /// A notification subscription started or ended.
+    GattNotifying { target: GattTarget, active: bool },
+    /// A read / write / subscribe request failed.
+    GattOpFailed { target: GattTarget, error: String },
+    /// Connectable profiles of a device with their inferred state.
+    ProfileStates {
+        address: Address,
+        profiles: Vec<ProfileStatus>,
+    },
+    /// `ConnectProfile` / `DisconnectProfile` failed.
+    ProfileOpFailed {
+        address: Address,
+        uuid: Uuid,
+        error: String,
+    },
+    /// Decoded value from a standard GATT profile (battery, heart rate, …).
+    ProfileReading {
+        address: Address,
+        reading: ProfileReading,
+    },
     /// Catch-all error surfaced from BlueZ / D-Bus.
     Error(String),
 }
 
+// ─── Discovery filter ───────────────────────────────────────────────────────
+
+/// Which radio transport to scan on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScanTransport {
+    /// Interleaved LE + BR/EDR scan (whatever the controller has enabled).
+    #[default]
+    Auto,
+    /// LE scan only.
+    Le,
+    /// BR/EDR inquiry only.
+    BrEdr,
+}
+
+impl ScanTransport {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Auto => Self::Le,
+            Self::Le => Self::BrEdr,
+            Self::BrEdr => Self::Auto,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Auto => "Auto",
+            Self::Le => "LE",
+            Self::BrEdr => "BR/EDR",
+        }
+    }
+}
+
+/// Plain-data BlueZ discovery filter, converted to `bluer::DiscoveryFilter`
+/// inside the worker.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanFilter {
+    pub transport: ScanTransport,
+    /// Minimum RSSI (dBm). Takes precedence over `pathloss` — BlueZ rejects
+    /// filters that set both.
+    pub rssi: Option<i16>,
+    /// Maximum pathloss (dB). Only honoured when `rssi` is unset.
+    pub pathloss: Option<u16>,
+    /// Only report devices advertising at least one of these services.
+    pub uuids: Vec<Uuid>,
+    /// Report every advertisement instead of de-duplicating them.
+    pub duplicate_data: bool,
+}
+
+impl ScanFilter {
+    /// True when the filter restricts nothing (BlueZ default behaviour).
+    pub fn is_unrestricted(&self) -> bool {
+        self.transport == ScanTransport::Auto
+            && self.rssi.is_none()
+            && self.pathloss.is_none()
+            && self.uuids.is_empty()
+            && !self.duplicate_data
+    }
+
+    /// Compact one-line summary for the status bar, e.g. `LE ≥-70dBm 2 UUIDs`.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![self.transport.label().to_string()];
+        if let Some(rssi) = self.rssi {
+            parts.push(format!("≥{rssi}dBm"));
+        } else if let Some(pathloss) = self.pathloss {
+            parts.push(format!("≤{pathloss}dB loss"));
+        }
+        match self.uuids.len() {
+            0 => {}
+            1 => parts.push("1 UUID".into()),
+            n => parts.push(format!("{n} UUIDs")),
+        }
+        if self.duplicate_data {
+            parts.push("dup".into());
+        }
+        parts.join(" ")
+    }
+}
+
+/// Parse a Bluetooth UUID in 16-bit (`180F`), 32-bit (`0000180F`) or full
+/// 128-bit form. A leading `0x` is accepted on the short forms.
+pub fn parse_uuid(s: &str) -> Option<Uuid> {
+    let s = s.trim();
+    let short = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+    match short.len() {
+        4 => u16::from_str_radix(short, 16).ok().map(Uuid::from_u16),
+        8 => u32::from_str_radix(short, 16).ok().map(Uuid::from_u32),
+        _ => s.parse().ok(),
+    }
+}
+
+/// Format a UUID in its shortest Bluetooth form (`0x180F` for SIG UUIDs).
+pub fn short_uuid(uuid: &Uuid) -> String {
+    match uuid.as_u16() {
+        Some(v) => format!("0x{v:04X}"),
+        None => match uuid.as_u32() {
+            Some(v) => format!("0x{v:08X}"),
+            None => uuid.to_string(),
+        },
+    }
+}
+
 // ─── Snapshot structs ───────────────────────────────────────────────────────
 
 /// Plain-data snapshot of the host Bluetooth adapter.
@@ -77,11 +330,35 @@ pub enum BtEvent {
 pub struct AdapterInfo {
     pub name: String,
     pub address: Option<Address>,
+    pub address_type: Option<AddressType>,
+    /// Friendly name shown to remote devices.
+    pub alias: String,
     pub powered: bool,
     #[allow(dead_code)]
     pub discovering: bool,
-    #[allow(dead_code)]
     pub discoverable: bool,
+    /// Seconds until discoverable switches off again (0 = never).
+    pub discoverable_timeout: u32,
+    pub pairable: bool,
+    /// Seconds until pairable switches off again (0 = never).
+    pub pairable_timeout: u32,
+    pub class: Option<u32>,
+    /// `source vendor:product:device`, from the Modalias property.
+    pub modalias: Option<String>,
+    pub uuids: Vec<Uuid>,
+    /// Supported LE roles (`central`, `peripheral`, …).
+    pub roles: Vec<String>,
+}
+
+/// An adapter setting changed from the settings view.
+#[derive(Debug, Clone)]
+pub enum AdapterSetting {
+    /// Empty resets to the system name.
+    Alias(String),
+    Discoverable(bool),
+    DiscoverableTimeout(u32),
+    Pairable(bool),
+    PairableTimeout(u32),
 }
 
 /// Plain-data snapshot of a remote Bluetooth device.
@@ -94,13 +371,33 @@ pub struct DeviceInfo {
     pub alias: String,
     pub icon: Option<String>,
     pub rssi: Option<i16>,
-    #[allow(dead_code)]
+    /// Advertised TX power level (dBm).
     pub tx_power: Option<i16>,
     pub battery: Option<u8>,
     pub paired: bool,
     pub trusted: bool,
+    /// BlueZ rejects incoming connections from blocked devices.
+    pub blocked: bool,
+    /// May wake the host from suspend. `None` for devices that can't
+    /// (BlueZ only exposes it on HID / HID-over-GATT devices).
+    pub wake_allowed: Option<bool>,
     pub connected: bool,
     pub class: Option<u32>,
+    /// Device type derived from icon, class, appearance and UUIDs.
+    pub kind: DeviceKind,
+    /// Advertised / resolved service UUIDs, sorted.
+    pub uuids: Vec<Uuid>,
+    /// Manufacturer-specific advertisement data, keyed by company id.
+    pub manufacturer_data: BTreeMap<u16, Vec<u8>>,
+    /// Service advertisement data, keyed by service UUID.
+    pub service_data: BTreeMap<Uuid, Vec<u8>>,
+    /// GAP appearance value.
+    pub appearance: Option<u16>,
+    pub address_type: Option<AddressType>,
+    /// When the device was last heard over the air (discovery, advertisement
+    /// or RSSI update). `None` for cached devices not seen this session; the
+    /// UI keeps the previous value when an update carries `None`.
+    pub last_seen: Option<Instant>,
 }
 
 impl DeviceInfo {
@@ -109,6 +406,26 @@ impl DeviceInfo {
         self.name.as_deref().unwrap_or(&self.alias)
     }
 
+    /// Decoded Class of Device (BR/EDR only).
+    pub fn device_class(&self) -> Option<DeviceClass> {
+        self.class.map(DeviceClass)
+    }
+
+    /// Time since the device was last seen. Connected devices are always
+    /// considered present.
+    pub fn age(&self) -> Option<Duration> {
+        if self.connected {
+            return Some(Duration::ZERO);
+        }
+        self.last_seen.map(|t| t.elapsed())
+    }
+
+    /// True when the device hasn't been seen for at least `threshold`
+    /// (or never this session).
+    pub fn is_stale(&self, threshold: Duration) -> bool {
+        self.age().map_or(true, |age| age >= threshold)
+    }
+
     /// Effective sort key: connected first, then paired/trusted, then the rest.
     /// Within each tier, sort by RSSI descending (strongest signal first).
     /// Devices with no RSSI sink to the bottom of their tier.
@@ -132,3 +449,96 @@ impl fmt::Display for DeviceInfo {
         write!(f, "{} [{}]", self.display_name(), self.address)
     }
 }
+
+// ─── GATT database ──────────────────────────────────────────────────────────
+
+/// Identifies a characteristic for read / write / notify requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GattTarget {
+    pub address: Address,
+    /// Handle of the containing service.
+    pub service: u16,
+    /// Handle of the characteristic.
+    pub characteristic: u16,
+}
+
+/// A remote GATT service. Handles are the ATT handles BlueZ encodes in its
+/// object paths (`service000c`, `char000d`, …).
+#[derive(Debug, Clone)]
+pub struct GattService {
+    pub handle: u16,
+    pub uuid: Uuid,
+    pub primary: bool,
+    /// Sorted by handle.
+    pub characteristics: Vec<GattCharacteristic>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GattCharacteristic {
+    pub handle: u16,
+    pub uuid: Uuid,
+    pub props: GattProps,
+    /// Characteristic Presentation Format (0x2904), if the device has one.
+    pub presentation: Option<PresentationFormat>,
+    /// Value captured by a snapshot; `None` when not read.
+    pub value: Option<Vec<u8>>,
+    /// Sorted by handle.
+    pub descriptors: Vec<GattDescriptor>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GattDescriptor {
+    pub handle: u16,
+    pub uuid: Uuid,
+}
+
+/// Characteristic properties, reduced to what a client cares about.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GattProps {
+    pub broadcast: bool,
+    pub read: bool,
+    pub write: bool,
+    pub write_without_response: bool,
+    pub notify: bool,
+    pub indicate: bool,
+    pub signed_write: bool,
+    pub reliable_write: bool,
+    /// Any encrypt / authenticate / secure access requirement.
+    pub encrypted: bool,
+}
+
+impl GattProps {
+    /// Short labels of the set properties, e.g. `["read", "notify"]`.
+    pub fn labels(&self) -> Vec<&'static str> {
+        [
+            (self.broadcast, "broadcast"),
+            (self.read, "read"),
+            (self.write, "write"),
+            (self.write_without_response, "write-cmd"),
+            (self.notify, "notify"),
+            (self.indicate, "indicate"),
+            (self.signed_write, "signed"),
+            (self.reliable_write, "reliable"),
+            (self.encrypted, "encrypted"),
+        ]
+        .into_iter()
+        .filter_map(|(set, label)| set.then_some(label))
+        .collect()
+    }
+
+    /// Inverse of [`labels`](Self::labels); unknown labels are ignored.
+    pub fn from_labels(labels: &[String]) -> Self {
+        let has = |label: &str| labels.iter().any(|l| l == label);
+        Self {
+            broadcast: has("broadcast"),
+            read: has("read"),
+            write: has("write"),
+            write_without_response: has("write-cmd"),
+            notify: has("notify"),
+            indicate: has("indicate"),
+            signed_write: has("signed"),
+            reliable_write: has("reliable"),
+            encrypted: has("encrypted"),
+        }
+    }
+}
@@ -4,16 +4,229 @@
 //! UI and emits `BtEvent`s back. Runs entirely on the tokio runtime — the TUI
 //! thread never touches D-Bus.
 
-use std::collections::HashSet;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::time::Duration;
 
-use bluer::{Adapter, AdapterEvent, Address, Device, Session};
-use futures::StreamExt;
+use bluer::{
+    Adapter, AdapterEvent, Address, Device, DeviceEvent, DeviceProperty, DiscoveryFilter,
+    DiscoveryTransport, Session,
+};
+use futures::stream::{AbortHandle, SelectAll};
+use futures::{FutureExt, StreamExt};
 use tokio::sync::mpsc;
+use tokio::time::Instant;
 use tracing::{debug, info, warn};
 
+use super::admin_policy;
 use super::agent;
+use super::blocklist;
+use super::bluez_dbus;
+use super::connection_profiles::ProfileTracker;
+use super::device_sets;
+use super::gatt;
+use super::media_player::{self, PLAYER};
+use super::media_transport::{self, TRANSPORT};
+use super::obex;
+use super::profiles::ProfileMonitors;
+use super::proximity::Proximity;
 use super::types::*;
 
+/// Boxed adapter event stream, from `Adapter::discover_devices` or
+/// `Adapter::events`.
+type DiscoverStream = Pin<Box<dyn futures::Stream<Item = AdapterEvent> + Send>>;
+
+/// Boxed per-device property stream, tagged with the device address.
+type DeviceEventStream = Pin<Box<dyn futures::Stream<Item = (Address, DeviceEvent)> + Send>>;
+
+/// Devices the worker has announced to the UI, plus a merged stream of their
+/// D-Bus property changes so connection, battery and RSSI updates arrive
+/// even when they originate outside VoidLink.
+struct DeviceWatch {
+    /// Addresses we've already sent `DeviceFound` for, so subsequent
+    /// sightings become `DeviceUpdated`, with the handle that ends their
+    /// property stream.
+    known: HashMap<Address, Option<AbortHandle>>,
+    /// Property change streams; each ends when BlueZ removes the device or
+    /// the device is forgotten.
+    events: SelectAll<DeviceEventStream>,
+}
+
+impl DeviceWatch {
+    fn new() -> Self {
+        Self {
+            known: HashMap::new(),
+            events: SelectAll::new(),
+        }
+    }
+
+    /// Start tracking `device`. Returns `true` if it was not known before.
+    async fn track(&mut self, device: &Device) -> bool {
+        let addr = device.address();
+        if self.known.contains_key(&addr) {
+            return false;
+        }
+        let handle = match device.events().await {
+            Ok(stream) => {
+                let (stream, handle) = futures::stream::abortable(stream);
+                self.events
+                    .push(Box::pin(stream.map(move |evt| (addr, evt))));
+                Some(handle)
+            }
+            Err(e) => {
+                debug!("Cannot watch {addr} for property changes: {e}");
+                None
+            }
+        };
+        self.known.insert(addr, handle);
+        true
+    }
+
+    /// Stop tracking `addr`, ending its property stream so a later
+    /// `track` doesn't leave two streams for one device.
+    fn forget(&mut self, addr: &Address) {
+        if let Some(Some(handle)) = self.known.remove(addr) {
+            handle.abort();
+        }
+    }
+}
+
+/// Discovery state owned by the worker: the live stream, the filter applied
+/// to it, and the timers driving timed and periodic scans.
+struct Scanner {
+    /// Open discovery session, if scanning.
+    stream: Option<DiscoverStream>,
+    /// Active discovery filter — seeded from config, replaced at runtime.
+    filter: ScanFilter,
+    /// When the current scan stops automatically (`None` = until stopped).
+    deadline: Option<Instant>,
+    /// When the next periodic scan starts (`None` = periodic mode off).
+    next_periodic: Option<Instant>,
+}
+
+impl Scanner {
+    fn new() -> Self {
+        let bt_cfg = &crate::config::get().bluetooth;
+        Self {
+            stream: None,
+            filter: bt_cfg.discovery.clone(),
+            deadline: None,
+            next_periodic: bt_cfg
+                .periodic_scan
+                .then(|| Instant::now() + periodic_interval()),
+        }
+    }
+
+    /// Apply the discovery filter and open a new discovery stream that stops
+    /// itself after `duration` (if any).
+    async fn start(
+        &mut self,
+        adapter: &Adapter,
+        evt_tx: &mpsc::Sender<BtEvent>,
+        duration: Option<Duration>,
+    ) {
+        // Dropping a previous stream ends its session; setting the filter
+        // below waits for BlueZ to acknowledge that before continuing.
+        self.stream = None;
+        if let Err(e) = adapter
+            .set_discovery_filter(to_discovery_filter(&self.filter))
+            .await
+        {
+            let _ = evt_tx
+                .send(BtEvent::Error(format!(
+                    "Failed to set discovery filter: {e}"
+                )))
+                .await;
+        }
+
+        match adapter.discover_devices().await {
+            Ok(stream) => {
+                self.stream = Some(Box::pin(stream));
+                self.deadline = duration.map(|d| Instant::now() + d);
+                self.emit_scanning(evt_tx).await;
+                info!("Discovery started");
+            }
+            Err(e) => {
+                self.deadline = None;
+                self.emit_scanning(evt_tx).await;
+                let _ = evt_tx
+                    .send(BtEvent::Error(format!("Failed to start scanning: {e}")))
+                    .await;
+            }
+        }
+    }
+
+    /// End the discovery session and wait until BlueZ has processed
+    /// `StopDiscovery` before reporting the adapter as idle.
+    async fn stop(&mut self, adapter: &Adapter, evt_tx: &mpsc::Sender<BtEvent>) {
+        let was_scanning = self.stream.take().is_some();
+        self.deadline = None;
+        if was_scanning {
+            // bluer stops discovery in a background task when the stream is
+            // dropped; `set_discovery_filter` blocks until that task is done.
+            let _ = adapter
+                .set_discovery_filter(to_discovery_filter(&self.filter))
+                .await;
+            info!("Discovery stopped");
+        }
+        self.emit_scanning(evt_tx).await;
+    }
+
+    /// Enable or disable the background periodic scan schedule.
+    async fn set_periodic(&mut self, enabled: bool, evt_tx: &mpsc::Sender<BtEvent>) {
+        self.next_periodic = enabled.then(|| Instant::now() + periodic_interval());
+        self.emit_periodic(evt_tx).await;
+    }
+
+    // `&mut self` rather than `&self`: the boxed stream is `Send` but not
+    // `Sync`, so a shared borrow held across `.await` would make the worker
+    // future non-`Send`.
+    async fn emit_scanning(&mut self, evt_tx: &mpsc::Sender<BtEvent>) {
+        let _ = evt_tx
+            .send(BtEvent::ScanningChanged {
+                scanning: self.stream.is_some(),
+                deadline: self.deadline.map(Instant::into_std),
+            })
+            .await;
+    }
+
+    async fn emit_periodic(&mut self, evt_tx: &mpsc::Sender<BtEvent>) {
+        let _ = evt_tx
+            .send(BtEvent::PeriodicScanChanged {
+                next: self.next_periodic.map(Instant::into_std),
+            })
+            .await;
+    }
+}
+
+/// Duration of a manual scan from config (`None` = until stopped).
+fn manual_scan_duration() -> Option<Duration> {
+    match crate::config::get().bluetooth.scan_duration_secs {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    }
+}
+
+/// Start-to-start interval between periodic scans.
+fn periodic_interval() -> Duration {
+    Duration::from_secs(crate::config::get().bluetooth.periodic_scan_interval_mins * 60)
+}
+
+/// Resolve when an optional deadline fires; pends forever when unset.
+async fn sleep_until_opt(deadline: Option<Instant>) {
+    match deadline {
+        Some(d) => tokio::time::sleep_until(d).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Forward a batch of events (e.g. watchlist crossings) to the UI.
+async fn send_all(evt_tx: &mpsc::Sender<BtEvent>, events: Vec<BtEvent>) {
+    for event in events {
+        let _ = evt_tx.send(event).await;
+    }
+}
+
 /// Snapshot all interesting properties from a `bluer::Device` into a plain
 /// `DeviceInfo` struct that can be sent across the channel.
 async fn snapshot_device(device: &Device) -> DeviceInfo {
@@ -27,9 +240,36 @@ async fn snapshot_device(device: &Device) -> DeviceInfo {
     let tx_power = device.tx_power().await.unwrap_or(None);
     let paired = device.is_paired().await.unwrap_or(false);
     let trusted = device.is_trusted().await.unwrap_or(false);
+    let blocked = device.is_blocked().await.unwrap_or(false);
+    let wake_allowed = device.is_wake_allowed().await.unwrap_or(None);
     let connected = device.is_connected().await.unwrap_or(false);
     let class = device.class().await.unwrap_or(None);
     let battery = device.battery_percentage().await.unwrap_or(None);
+    let mut uuids: Vec<Uuid> = device
+        .uuids()
+        .await
+        .unwrap_or(None)
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    uuids.sort();
+    let manufacturer_data = device
+        .manufacturer_data()
+        .await
+        .unwrap_or(None)
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    let service_data = device
+        .service_data()
+        .await
+        .unwrap_or(None)
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    let appearance = device.appearance().await.unwrap_or(None);
+    let address_type = device.address_type().await.ok();
+    let kind = DeviceKind::detect(icon.as_deref(), class, appearance, &uuids);
 
     DeviceInfo {
         address,
@@ -41,19 +281,141 @@ async fn snapshot_device(device: &Device) -> DeviceInfo {
         battery,
         paired,
         trusted,
+        blocked,
+        wake_allowed,
         connected,
         class,
+        kind,
+        uuids,
+        manufacturer_data,
+        service_data,
+        appearance,
+        address_type,
+        last_seen: None,
     }
 }
 
+/// Snapshot a device that was just heard from over the air.
+async fn snapshot_sighting(device: &Device) -> DeviceInfo {
+    DeviceInfo {
+        last_seen: Some(std::time::Instant::now()),
+        ..snapshot_device(device).await
+    }
+}
+
+/// Whether a property change proves the device is currently in range
+/// (an advertisement or an active link), as opposed to a local state change.
+fn is_sighting(prop: &DeviceProperty) -> bool {
+    matches!(
+        prop,
+        DeviceProperty::Rssi(_)
+            | DeviceProperty::TxPower(_)
+            | DeviceProperty::ManufacturerData(_)
+            | DeviceProperty::ServiceData(_)
+            | DeviceProperty::Connected(true)
+    )
+}
+
+/// Send a device's profiles and their state to the UI.
+async fn emit_profile_states(
+    adapter: &Adapter,
+    tracker: &ProfileTracker,
+    address: Address,
+    tx: &mpsc::Sender<BtEvent>,
+) {
+    let event = match tracker.query(adapter, address).await {
+        Ok(profiles) => BtEvent::ProfileStates { address, profiles },
+        Err(e) => BtEvent::Error(format!("Cannot list profiles: {e}")),
+    };
+    let _ = tx.send(event).await;
+}
+
+/// Send the adapter's coordinated sets to the UI. Silent on failure: sets
+/// only exist with bluetoothd's experimental LE Audio support.
+async fn emit_device_sets(adapter: &Adapter, tx: &mpsc::Sender<BtEvent>) {
+    let Some(bus) = bluez_dbus::shared() else {
+        return;
+    };
+    match device_sets::query(bus, adapter.name()).await {
+        Ok(sets) => {
+            let _ = tx.send(BtEvent::DeviceSets(sets)).await;
+        }
+        Err(e) => debug!("Cannot read device sets: {e}"),
+    }
+}
+
+/// Send the adapter's media players and audio transports to the UI, both
+/// read from one object snapshot.
+async fn emit_media(adapter: &Adapter, tx: &mpsc::Sender<BtEvent>) {
+    let Some(bus) = bluez_dbus::shared() else {
+        return;
+    };
+    let objects = match bus.managed_objects().await {
+        Ok(objects) => objects,
+        Err(e) => {
+            debug!("Cannot read media players and audio transports: {e}");
+            return;
+        }
+    };
+    let players = media_player::players(&objects, adapter.name());
+    let _ = tx.send(BtEvent::MediaPlayers(players)).await;
+    let transports = media_transport::transports(&objects, adapter.name());
+    let _ = tx.send(BtEvent::MediaTransports(transports)).await;
+}
+
+/// Read the adapter's admin policy.
+async fn read_admin_policy(adapter: &Adapter) -> Result<AdminPolicy, String> {
+    let bus = bluez_dbus::shared().ok_or("no D-Bus connection")?;
+    admin_policy::query(bus, adapter.name())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Send the admin policy to the UI, or an error when it can't be read.
+async fn emit_admin_policy(adapter: &Adapter, tx: &mpsc::Sender<BtEvent>) {
+    let event = match read_admin_policy(adapter).await {
+        Ok(policy) => BtEvent::AdminPolicy(policy),
+        Err(e) => BtEvent::Error(format!("Cannot read admin policy: {e}")),
+    };
+    let _ = tx.send(event).await;
+}
+
 /// Send the current adapter state to the UI.
 async fn emit_adapter_state(adapter: &Adapter, tx: &mpsc::Sender<BtEvent>) {
+    let modalias = adapter.modalias().await.ok().flatten().map(|m| {
+        format!(
+            "{} {:04x}:{:04x}:{:04x}",
+            m.source, m.vendor, m.product, m.device
+        )
+    });
+    let mut uuids: Vec<Uuid> = adapter
+        .uuids()
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    uuids.sort();
+    let roles = match bluez_dbus::shared() {
+        Some(bus) => bus.adapter_roles(adapter.name()).await.unwrap_or_default(),
+        None => Vec::new(),
+    };
     let info = AdapterInfo {
         name: adapter.name().to_string(),
         address: adapter.address().await.ok(),
+        address_type: adapter.address_type().await.ok(),
+        alias: adapter.alias().await.unwrap_or_default(),
         powered: adapter.is_powered().await.unwrap_or(false),
         discovering: adapter.is_discovering().await.unwrap_or(false),
         discoverable: adapter.is_discoverable().await.unwrap_or(false),
+        discoverable_timeout: adapter.discoverable_timeout().await.unwrap_or(0),
+        pairable: adapter.is_pairable().await.unwrap_or(false),
+        pairable_timeout: adapter.pairable_timeout().await.unwrap_or(0),
+        class: adapter.class().await.ok(),
+        modalias,
+        uuids,
+        roles,
     };
     let _ = tx.send(BtEvent::AdapterState(info)).await;
 }
@@ -97,26 +459,66 @@ pub async fn run(mut cmd_rx: mpsc::Receiver<BtCommand>, evt_tx: mpsc::Sender<BtE
 
     info!("Using adapter: {}", adapter.name());
     emit_adapter_state(&adapter, &evt_tx).await;
+    emit_device_sets(&adapter, &evt_tx).await;
+    emit_media(&adapter, &evt_tx).await;
+    // Quietly: most bluetoothd builds lack the admin policy plugin.
+    if let Ok(policy) = read_admin_policy(&adapter).await {
+        let _ = evt_tx.send(BtEvent::AdminPolicy(policy)).await;
+    }
 
-    // Send initial list of already-known devices.
+    // Send initial list of already-known devices and watch them for
+    // property changes. Devices that are already connected get their
+    // standard profiles monitored straight away.
+    let mut watch = DeviceWatch::new();
+    let mut proximity = Proximity::new();
+    let mut profiles = ProfileMonitors::default();
+    let dashboards = crate::config::get().bluetooth.profile_dashboards;
     if let Ok(addrs) = adapter.device_addresses().await {
         for addr in addrs {
             if let Ok(device) = adapter.device(addr) {
+                watch.track(&device).await;
+                blocklist::enforce(&device).await;
                 let info = snapshot_device(&device).await;
+                if dashboards && info.connected {
+                    profiles.start(&adapter, addr, &evt_tx);
+                }
+                let crossings = proximity.observe(&info);
                 let _ = evt_tx.send(BtEvent::DeviceFound(info)).await;
+                send_all(&evt_tx, crossings).await;
             }
         }
     }
 
-    // ── Discovery stream (optional — started/stopped by commands) ───────
-    // We hold the discovery stream in an Option so we can start/stop it.
-    let mut discover_stream: Option<
-        std::pin::Pin<Box<dyn futures::Stream<Item = AdapterEvent> + Send>>,
-    > = None;
+    // ── Discovery (optional — started/stopped by commands and timers) ────
+    let mut scanner = Scanner::new();
+    scanner.emit_periodic(&evt_tx).await;
 
-    // Track which addresses we've already sent DeviceFound for so we can
-    // send DeviceUpdated on subsequent sightings.
-    let mut known_addresses: HashSet<Address> = HashSet::new();
+    // ── GATT notification sessions ──────────────────────────────────────
+    let mut subscriptions = gatt::Subscriptions::default();
+
+    // ── Adapter property changes (discoverable timeout, other clients) ──
+    let mut adapter_events: DiscoverStream = match adapter.events().await {
+        Ok(stream) => Box::pin(stream),
+        Err(e) => {
+            warn!("Cannot watch adapter properties: {e}");
+            Box::pin(futures::stream::pending())
+        }
+    };
+
+    // ── Per-profile connection state ────────────────────────────────────
+    let mut profile_tracker = ProfileTracker::default();
+
+    // ── AVRCP players and audio transports (track, codec, volume) ───────
+    let mut media_changes = match bluez_dbus::shared() {
+        Some(bus) => bus
+            .interface_changes(&[PLAYER, TRANSPORT])
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Cannot watch media players: {e}");
+                Box::pin(futures::stream::pending())
+            }),
+        None => Box::pin(futures::stream::pending()),
+    };
 
     // ── Main select loop ────────────────────────────────────────────────
     loop {
@@ -131,30 +533,114 @@ pub async fn run(mut cmd_rx: mpsc::Receiver<BtCommand>, evt_tx: mpsc::Sender<BtE
                     &cmd,
                     &adapter,
                     &evt_tx,
-                    &mut discover_stream,
-                    &mut known_addresses,
+                    &mut scanner,
+                    &mut watch,
+                    &mut subscriptions,
+                    &mut profile_tracker,
                 )
                 .await;
             }
 
             // ── Discovery events ────────────────────────────────────────
-            Some(adapter_event) = async {
-                match discover_stream.as_mut() {
+            adapter_event = async {
+                match scanner.stream.as_mut() {
                     Some(stream) => stream.next().await,
                     None => std::future::pending::<Option<AdapterEvent>>().await,
                 }
             } => {
-                handle_adapter_event(
-                    adapter_event,
-                    &adapter,
-                    &evt_tx,
-                    &mut known_addresses,
-                )
-                .await;
+                match adapter_event {
+                    Some(adapter_event) => {
+                        handle_adapter_event(
+                            adapter_event,
+                            &adapter,
+                            &evt_tx,
+                            &mut watch,
+                            &mut proximity,
+                        )
+                        .await;
+                    }
+                    // BlueZ ended discovery on its own (adapter powered off,
+                    // another client, …).
+                    None => scanner.stop(&adapter, &evt_tx).await,
+                }
+            }
+
+            // ── Adapter property changes ───────────────────────────────
+            Some(AdapterEvent::PropertyChanged(_)) = adapter_events.next() => {
+                emit_adapter_state(&adapter, &evt_tx).await;
+            }
+
+            // ── Media player and transport changes ──────────────────────
+            Some(()) = media_changes.next() => {
+                // A track change arrives as a burst of signals; read once.
+                while let Some(Some(())) = media_changes.next().now_or_never() {}
+                emit_media(&adapter, &evt_tx).await;
+            }
+
+            // ── Device property changes ────────────────────────────────
+            Some((addr, DeviceEvent::PropertyChanged(prop))) = watch.events.next(),
+                if !watch.events.is_empty() =>
+            {
+                match prop {
+                    DeviceProperty::Connected(false) => {
+                        subscriptions.drop_device(addr, &evt_tx).await;
+                        profiles.stop(addr);
+                        profile_tracker.forget_device(addr);
+                    }
+                    DeviceProperty::Connected(true) if dashboards => {
+                        profiles.start(&adapter, addr, &evt_tx);
+                    }
+                    _ => {}
+                }
+                let membership_may_change = matches!(
+                    prop,
+                    DeviceProperty::Connected(_)
+                        | DeviceProperty::Paired(_)
+                        | DeviceProperty::ServicesResolved(true)
+                );
+                if let Ok(device) = adapter.device(addr) {
+                    let info = if is_sighting(&prop) {
+                        snapshot_sighting(&device).await
+                    } else {
+                        snapshot_device(&device).await
+                    };
+                    let crossings = proximity.observe(&info);
+                    let _ = evt_tx.send(BtEvent::DeviceUpdated(info)).await;
+                    send_all(&evt_tx, crossings).await;
+                }
+                if membership_may_change {
+                    emit_device_sets(&adapter, &evt_tx).await;
+                }
+            }
+
+            // ── Watchlist leave timeout ─────────────────────────────────
+            _ = sleep_until_opt(proximity.next_expiry()) => {
+                send_all(&evt_tx, proximity.expire()).await;
+            }
+
+            // ── Timed scan expiry ───────────────────────────────────────
+            _ = sleep_until_opt(scanner.deadline) => {
+                info!("Scan duration elapsed");
+                scanner.stop(&adapter, &evt_tx).await;
+                emit_adapter_state(&adapter, &evt_tx).await;
+            }
+
+            // ── Periodic background scan ────────────────────────────────
+            _ = sleep_until_opt(scanner.next_periodic) => {
+                let secs = crate::config::get().bluetooth.periodic_scan_secs;
+                scanner.next_periodic = Some(Instant::now() + periodic_interval());
+                if scanner.stream.is_none() {
+                    info!("Periodic scan for {secs}s");
+                    scanner.start(&adapter, &evt_tx, Some(Duration::from_secs(secs))).await;
+                    emit_adapter_state(&adapter, &evt_tx).await;
+                }
+                scanner.emit_periodic(&evt_tx).await;
             }
         }
     }
 
+    // Never leave the adapter in discovery mode behind us.
+    scanner.stop(&adapter, &evt_tx).await;
     info!("Bluetooth worker shut down");
 }
 
@@ -163,10 +649,10 @@ async fn handle_command(
     cmd: &BtCommand,
     adapter: &Adapter,
     evt_tx: &mpsc::Sender<BtEvent>,
-    discover_stream: &mut Option<
-        std::pin::Pin<Box<dyn futures::Stream<Item = AdapterEvent> + Send>>,
-    >,
-    known_addresses: &mut HashSet<Address>,
+    scanner: &mut Scanner,
+    watch: &mut DeviceWatch,
+    subscriptions: &mut gatt::Subscriptions,
+    profile_tracker: &mut ProfileTracker,
 ) {
     match cmd {
         BtCommand::EnableAdapter => {
@@ -188,73 +674,150 @@ async fn handle_command(
         }
 
         BtCommand::StartScan => {
-            match adapter.discover_devices().await {
-                Ok(stream) => {
-                    *discover_stream = Some(Box::pin(stream));
-                    let _ = evt_tx.send(BtEvent::ScanningChanged(true)).await;
-                    info!("Discovery started");
-                }
-                Err(e) => {
-                    let _ = evt_tx
-                        .send(BtEvent::Error(format!("Failed to start scanning: {e}")))
-                        .await;
-                }
-            }
+            scanner.start(adapter, evt_tx, manual_scan_duration()).await;
             emit_adapter_state(adapter, evt_tx).await;
         }
 
         BtCommand::StopScan => {
-            *discover_stream = None;
-            let _ = evt_tx.send(BtEvent::ScanningChanged(false)).await;
+            scanner.stop(adapter, evt_tx).await;
+            emit_adapter_state(adapter, evt_tx).await;
+        }
+
+        BtCommand::SetAdapter(setting) => {
+            let result = match setting {
+                AdapterSetting::Alias(alias) => adapter.set_alias(alias.clone()).await,
+                AdapterSetting::Discoverable(on) => adapter.set_discoverable(*on).await,
+                AdapterSetting::DiscoverableTimeout(secs) => {
+                    adapter.set_discoverable_timeout(*secs).await
+                }
+                AdapterSetting::Pairable(on) => adapter.set_pairable(*on).await,
+                AdapterSetting::PairableTimeout(secs) => adapter.set_pairable_timeout(*secs).await,
+            };
+            if let Err(e) = result {
+                let _ = evt_tx
+                    .send(BtEvent::Error(format!(
+                        "Failed to change adapter setting: {e}"
+                    )))
+                    .await;
+            }
             emit_adapter_state(adapter, evt_tx).await;
-            info!("Discovery stopped");
+        }
+
+        BtCommand::SetPeriodicScan(enabled) => {
+            scanner.set_periodic(*enabled, evt_tx).await;
+        }
+
+        BtCommand::SetDiscoveryFilter(filter) => {
+            scanner.filter = filter.clone();
+            info!("Discovery filter set: {}", scanner.filter.summary());
+            // BlueZ only accepts a new filter while no discovery session from
+            // this client is active, so restart a running scan around it,
+            // keeping whatever time it had left.
+            if scanner.stream.is_some() {
+                let remaining = scanner
+                    .deadline
+                    .map(|d| d.saturating_duration_since(Instant::now()));
+                scanner.start(adapter, evt_tx, remaining).await;
+                emit_adapter_state(adapter, evt_tx).await;
+            }
         }
 
         BtCommand::Connect(addr) => {
-            let addr = *addr;
-            let adapter_name = adapter.name().to_string();
-            let evt_tx = evt_tx.clone();
+            let started = std::time::Instant::now();
+            let result = connect_device(adapter, *addr, evt_tx).await;
+            let _ = evt_tx
+                .send(BtEvent::ConnectionResult {
+                    address: *addr,
+                    success: result.is_ok(),
+                    error: result.err(),
+                    elapsed: started.elapsed(),
+                })
+                .await;
+        }
 
-            // Get the device handle.
-            match adapter.device(addr) {
-                Ok(device) => {
-                    // Pair → Trust → Connect lifecycle.
-                    let result = connect_lifecycle(&device).await;
-                    match result {
-                        Ok(()) => {
-                            let info = snapshot_device(&device).await;
-                            let _ = evt_tx.send(BtEvent::DeviceUpdated(info)).await;
-                            let _ = evt_tx
-                                .send(BtEvent::ConnectionResult {
-                                    address: addr,
-                                    success: true,
-                                    error: None,
-                                })
-                                .await;
-                        }
-                        Err(e) => {
-                            let _ = evt_tx
-                                .send(BtEvent::ConnectionResult {
-                                    address: addr,
-                                    success: false,
-                                    error: Some(e.to_string()),
-                                })
-                                .await;
-                        }
-                    }
+        BtCommand::ConnectSet(members) => {
+            // Members connect one after another; the result covers the set.
+            let started = std::time::Instant::now();
+            let mut failures = Vec::new();
+            for &addr in members {
+                let connected = match adapter.device(addr) {
+                    Ok(device) => device.is_connected().await.unwrap_or(false),
+                    Err(_) => false,
+                };
+                if connected {
+                    continue;
                 }
-                Err(e) => {
+                if let Err(e) = connect_device(adapter, addr, evt_tx).await {
+                    failures.push(format!("{addr}: {e}"));
+                }
+            }
+            let Some(&first) = members.first() else {
+                return;
+            };
+            let _ = evt_tx
+                .send(BtEvent::ConnectionResult {
+                    address: first,
+                    success: failures.is_empty(),
+                    error: (!failures.is_empty()).then(|| failures.join("; ")),
+                    elapsed: started.elapsed(),
+                })
+                .await;
+        }
+
+        BtCommand::DisconnectSet(members) => {
+            for &addr in members {
+                let Ok(device) = adapter.device(addr) else {
+                    continue;
+                };
+                if let Err(e) = device.disconnect().await {
                     let _ = evt_tx
-                        .send(BtEvent::ConnectionResult {
-                            address: addr,
-                            success: false,
-                            error: Some(format!("Device not found on {adapter_name}: {e}")),
-                        })
+                        .send(BtEvent::Error(format!("Disconnect of {addr} failed: {e}")))
                         .await;
                 }
+                let info = snapshot_device(&device).await;
+                let _ = evt_tx.send(BtEvent::DeviceUpdated(info)).await;
             }
         }
 
+        BtCommand::RemoveSet(members) => {
+            for &addr in members {
+                if let Err(e) = adapter.remove_device(addr).await {
+                    let _ = evt_tx
+                        .send(BtEvent::Error(format!("Failed to remove {addr}: {e}")))
+                        .await;
+                } else {
+                    let _ = evt_tx.send(BtEvent::DeviceRemoved(addr)).await;
+                    watch.forget(&addr);
+                }
+            }
+            emit_device_sets(adapter, evt_tx).await;
+        }
+
+        BtCommand::ConnectProfile(addr, uuid) | BtCommand::DisconnectProfile(addr, uuid) => {
+            let (address, uuid) = (*addr, *uuid);
+            let connect = matches!(cmd, BtCommand::ConnectProfile(..));
+            let result = if connect {
+                profile_tracker.connect(adapter, address, uuid).await
+            } else {
+                profile_tracker.disconnect(adapter, address, uuid).await
+            };
+            if let Err(e) = result {
+                let op = if connect { "Connect" } else { "Disconnect" };
+                let _ = evt_tx
+                    .send(BtEvent::ProfileOpFailed {
+                        address,
+                        uuid,
+                        error: format!("{op} failed: {e}"),
+                    })
+                    .await;
+            }
+            emit_profile_states(adapter, profile_tracker, address, evt_tx).await;
+        }
+
+        BtCommand::QueryProfiles(addr) => {
+            emit_profile_states(adapter, profile_tracker, *addr, evt_tx).await;
+        }
+
         BtCommand::Disconnect(addr) => {
             let addr = *addr;
             match adapter.device(addr) {
@@ -333,6 +896,132 @@ async fn handle_command(
             }
         }
 
+        BtCommand::MediaControl(path, action) => {
+            let result = match bluez_dbus::shared() {
+                Some(bus) => media_player::control(bus, path, *action)
+                    .await
+                    .map_err(|e| e.to_string()),
+                None => Err("no D-Bus connection".into()),
+            };
+            if let Err(e) = result {
+                let _ = evt_tx
+                    .send(BtEvent::Error(format!("Media control failed: {e}")))
+                    .await;
+            }
+        }
+
+        BtCommand::SetTransportVolume(path, volume) => {
+            let result = match bluez_dbus::shared() {
+                Some(bus) => media_transport::set_volume(bus, path, *volume)
+                    .await
+                    .map_err(|e| e.to_string()),
+                None => Err("no D-Bus connection".into()),
+            };
+            if let Err(e) = result {
+                let _ = evt_tx
+                    .send(BtEvent::Error(format!("Failed to set volume: {e}")))
+                    .await;
+            }
+        }
+
+        BtCommand::SendFile(addr, file) => {
+            let Some(bus) = obex::shared() else {
+                let _ = evt_tx
+                    .send(BtEvent::Error(
+                        "Cannot send files: no session bus for obexd".into(),
+                    ))
+                    .await;
+                return;
+            };
+            match adapter.address().await {
+                Ok(source) => {
+                    tokio::spawn(obex::push(bus, source, *addr, file.clone(), evt_tx.clone()));
+                }
+                Err(e) => {
+                    let _ = evt_tx
+                        .send(BtEvent::Error(format!("Cannot read adapter address: {e}")))
+                        .await;
+                }
+            }
+        }
+
+        BtCommand::CancelTransfer(id) => {
+            let result = match obex::shared() {
+                Some(bus) => bus.cancel(id).await.map_err(|e| e.to_string()),
+                None => Err("no session bus".into()),
+            };
+            if let Err(e) = result {
+                let _ = evt_tx
+                    .send(BtEvent::Error(format!("Failed to cancel transfer: {e}")))
+                    .await;
+            }
+        }
+
+        BtCommand::QueryAdminPolicy => {
+            emit_admin_policy(adapter, evt_tx).await;
+        }
+
+        BtCommand::SetServiceAllowList(uuids) => {
+            let result = match bluez_dbus::shared() {
+                Some(bus) => admin_policy::set_allowlist(bus, adapter.name(), uuids)
+                    .await
+                    .map_err(|e| e.to_string()),
+                None => Err("no D-Bus connection".into()),
+            };
+            match result {
+                Ok(()) => info!("Service allowlist set: {} UUID(s)", uuids.len()),
+                Err(e) => {
+                    let _ = evt_tx
+                        .send(BtEvent::Error(format!("Failed to set admin policy: {e}")))
+                        .await;
+                }
+            }
+            emit_admin_policy(adapter, evt_tx).await;
+        }
+
+        BtCommand::SetWakeAllowed(addr, allowed) => {
+            let addr = *addr;
+            match adapter.device(addr) {
+                Ok(device) => {
+                    if let Err(e) = device.set_wake_allowed(*allowed).await {
+                        let _ = evt_tx
+                            .send(BtEvent::Error(format!(
+                                "Failed to set wake permission: {e}"
+                            )))
+                            .await;
+                    }
+                    let info = snapshot_device(&device).await;
+                    let _ = evt_tx.send(BtEvent::DeviceUpdated(info)).await;
+                }
+                Err(e) => {
+                    let _ = evt_tx
+                        .send(BtEvent::Error(format!("Device not found: {e}")))
+                        .await;
+                }
+            }
+        }
+
+        BtCommand::SetBlocked(addr, blocked) => {
+            let addr = *addr;
+            match adapter.device(addr) {
+                Ok(device) => {
+                    if let Err(e) = device.set_blocked(*blocked).await {
+                        let verb = if *blocked { "block" } else { "unblock" };
+                        let _ = evt_tx
+                            .send(BtEvent::Error(format!("Failed to {verb} device: {e}")))
+                            .await;
+                    }
+                    let info = snapshot_device(&device).await;
+                    let _ = evt_tx.send(BtEvent::DeviceUpdated(info)).await;
+                }
+                Err(e) => {
+                    let _ = evt_tx
+                        .send(BtEvent::Error(format!("Device not found: {e}")))
+                        .await;
+                }
+            }
+        }
+
         BtCommand::RemoveDevice(addr) => {
             let addr = *addr;
             if let Err(e) = adapter.remove_device(addr).await {
@@ -341,7 +1030,7 @@ async fn handle_command(
                     .await;
             } else {
                 let _ = evt_tx.send(BtEvent::DeviceRemoved(addr)).await;
-                known_addresses.remove(&addr);
+                watch.forget(&addr);
             }
         }
 
@@ -360,6 +1049,57 @@ async fn handle_command(
             }
         }
 
+        BtCommand::DiscoverGatt(addr) | BtCommand::SnapshotGatt(addr) => {
+            let address = *addr;
+            let snapshot = matches!(cmd, BtCommand::SnapshotGatt(_));
+            if !snapshot {
+                // A fresh enumeration replaces the tree the subscriptions
+                // were made against.
+                subscriptions.drop_device(address, evt_tx).await;
+            }
+            let device = adapter.device(address);
+            let evt_tx = evt_tx.clone();
+            // Waits for service resolution and, for a snapshot, reads every
+            // characteristic: far too long to hold up the select loop.
+            tokio::spawn(async move {
+                let result = match device {
+                    Ok(device) if device.is_connected().await.unwrap_or(false) => {
+                        gatt::discover(&device, snapshot)
+                            .await
+                            .map_err(|e| e.to_string())
+                    }
+                    Ok(_) => Err("Device is not connected".to_string()),
+                    Err(e) => Err(format!("Device not found: {e}")),
+                };
+                let event = match result {
+                    Ok(services) if snapshot => BtEvent::GattSnapshot { address, services },
+                    Ok(services) => BtEvent::GattDiscovered { address, services },
+                    Err(error) => BtEvent::GattFailed { address, error },
+                };
+                let _ = evt_tx.send(event).await;
+            });
+        }
+
+        BtCommand::GattRead(target) => gatt::read(adapter, *target, evt_tx).await,
+
+        BtCommand::GattWrite {
+            target,
+            value,
+            with_response,
+        } => gatt::write(adapter, *target, value, *with_response, evt_tx).await,
+
+        BtCommand::GattSubscribe(target) => {
+            subscriptions.subscribe(adapter, *target, evt_tx).await;
+        }
+
+        BtCommand::GattUnsubscribe(target) => {
+            subscriptions.unsubscribe(*target, evt_tx).await;
+        }
+
+        BtCommand::GattUnsubscribeAll(addr) => {
+            subscriptions.drop_device(*addr, evt_tx).await;
+        }
+
         BtCommand::SetAlias(addr, new_alias) => {
             let addr = *addr;
             match adapter.device(addr) {
@@ -383,27 +1123,76 @@ async fn handle_command(
     }
 }
 
+/// Run the pair → trust → connect lifecycle on one device, reporting its
+/// new state on success.
+async fn connect_device(
+    adapter: &Adapter,
+    addr: Address,
+    evt_tx: &mpsc::Sender<BtEvent>,
+) -> Result<(), String> {
+    let device = adapter
+        .device(addr)
+        .map_err(|e| format!("Device not found on {}: {e}", adapter.name()))?;
+    connect_lifecycle(&device)
+        .await
+        .map_err(|e| e.to_string())?;
+    let info = snapshot_device(&device).await;
+    let _ = evt_tx.send(BtEvent::DeviceUpdated(info)).await;
+    Ok(())
+}
+
+/// Convert our plain-data filter into bluer's representation.
+fn to_discovery_filter(filter: &ScanFilter) -> DiscoveryFilter {
+    DiscoveryFilter {
+        uuids: filter.uuids.iter().copied().collect(),
+        rssi: filter.rssi,
+        // BlueZ rejects filters that set both RSSI and pathloss.
+        pathloss: if filter.rssi.is_some() {
+            None
+        } else {
+            filter.pathloss
+        },
+        transport: match filter.transport {
+            ScanTransport::Auto => DiscoveryTransport::Auto,
+            ScanTransport::Le => DiscoveryTransport::Le,
+            ScanTransport::BrEdr => DiscoveryTransport::BrEdr,
+        },
+        duplicate_data: filter.duplicate_data,
+        ..Default::default()
+    }
+}
+
 /// Handle a single adapter discovery event.
 async fn handle_adapter_event(
     event: AdapterEvent,
     adapter: &Adapter,
     evt_tx: &mpsc::Sender<BtEvent>,
-    known_addresses: &mut HashSet<Address>,
+    watch: &mut DeviceWatch,
+    proximity: &mut Proximity,
 ) {
     match event {
         AdapterEvent::DeviceAdded(addr) => {
             if let Ok(device) = adapter.device(addr) {
-                let info = snapshot_device(&device).await;
-                if known_addresses.insert(addr) {
+                blocklist::enforce(&device).await;
+                // Starting discovery replays every cached device as added;
+                // only one with a fresh RSSI was actually heard.
+                let mut info = snapshot_device(&device).await;
+                if info.rssi.is_some() {
+                    info.last_seen = Some(std::time::Instant::now());
+                }
+                let crossings = proximity.observe(&info);
+                if watch.track(&device).await {
                     let _ = evt_tx.send(BtEvent::DeviceFound(info)).await;
                 } else {
                     let _ = evt_tx.send(BtEvent::DeviceUpdated(info)).await;
                 }
+                send_all(evt_tx, crossings).await;
             }
         }
         AdapterEvent::DeviceRemoved(addr) => {
-            known_addresses.remove(&addr);
+            watch.forget(&addr);
             let _ = evt_tx.send(BtEvent::DeviceRemoved(addr)).await;
+            send_all(evt_tx, proximity.remove(&addr)).await;
         }
         AdapterEvent::PropertyChanged(_prop) => {
             emit_adapter_state(adapter, evt_tx).await;
@@ -0,0 +1,107 @@
+//! Minimal command-line parsing.
+//!
+//! VoidLink is primarily a TUI, so the CLI surface is intentionally tiny and
+//! parsed by hand rather than pulling in an argument-parsing crate.
+
+use std::path::PathBuf;
+
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+
+/// What the binary should do, derived from `argv`.
+#[derive(Debug)]
+pub enum Command {
+    /// Launch the interactive TUI (default). With `--record FILE`, the scan
+    /// session is exported to `FILE` on demand and on exit.
+    Tui { record: Option<PathBuf> },
+    /// `voidlink history [ADDR]` — print recorded device history and exit.
+    History { address: Option<String> },
+    /// `voidlink gatt-diff OLD NEW` — compare two GATT exports and exit.
+    GattDiff { old: PathBuf, new: PathBuf },
+    /// `voidlink blocklist export [FILE]` — print or save the blocked devices
+    /// as a `[blocklist]` config section.
+    BlocklistExport { path: Option<PathBuf> },
+    /// `voidlink blocklist import FILE` — block the devices in a config
+    /// file's `[blocklist]`.
+    BlocklistImport { path: PathBuf },
+    /// `voidlink --help`.
+    Help,
+}
+
+pub const USAGE: &str = "\
+Usage:
+  voidlink                   Launch the TUI
+  voidlink --record FILE     Launch the TUI and save the scan session to
+                             FILE (.csv or .json) on export and on exit
+  voidlink history [ADDR]    Show recorded history for one device (or all)
+  voidlink gatt-diff OLD NEW Compare two GATT database exports (.json)
+  voidlink blocklist export [FILE]
+                             Print (or save) blocked devices as a
+                             [blocklist] config section
+  voidlink blocklist import FILE
+                             Block the devices in FILE's [blocklist]
+  voidlink --help            Show this help";
+
+/// Parse the process arguments.
+pub fn parse() -> Result<Command> {
+    parse_from(std::env::args().skip(1))
+}
+
+fn parse_from(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let Some(first) = args.next() else {
+        return Ok(Command::Tui { record: None });
+    };
+    match first.as_str() {
+        "--record" => {
+            let path = args
+                .next()
+                .ok_or_else(|| eyre!("--record needs a file path\n\n{USAGE}"))?;
+            if let Some(extra) = args.next() {
+                return Err(eyre!("Unexpected argument: {extra}\n\n{USAGE}"));
+            }
+            Ok(Command::Tui {
+                record: Some(PathBuf::from(path)),
+            })
+        }
+        "history" => {
+            let address = args.next();
+            if let Some(extra) = args.next() {
+                return Err(eyre!("Unexpected argument: {extra}\n\n{USAGE}"));
+            }
+            Ok(Command::History { address })
+        }
+        "gatt-diff" => {
+            let (Some(old), Some(new)) = (args.next(), args.next()) else {
+                return Err(eyre!("gatt-diff needs two export files\n\n{USAGE}"));
+            };
+            if let Some(extra) = args.next() {
+                return Err(eyre!("Unexpected argument: {extra}\n\n{USAGE}"));
+            }
+            Ok(Command::GattDiff {
+                old: PathBuf::from(old),
+                new: PathBuf::from(new),
+            })
+        }
+        "blocklist" => {
+            let command = match (args.next().as_deref(), args.next()) {
+                (Some("export"), path) => Command::BlocklistExport {
+                    path: path.map(PathBuf::from),
+                },
+                (Some("import"), Some(path)) => Command::BlocklistImport {
+                    path: PathBuf::from(path),
+                },
+                _ => {
+                    return Err(eyre!(
+                        "blocklist needs `export [FILE]` or `import FILE`\n\n{USAGE}"
+                    ))
+                }
+            };
+            if let Some(extra) = args.next() {
+                return Err(eyre!("Unexpected argument: {extra}\n\n{USAGE}"));
+            }
+            Ok(command)
+        }
+        "-h" | "--help" | "help" => Ok(Command::Help),
+        other => Err(eyre!("Unknown argument: {other}\n\n{USAGE}")),
+    }
+}
@@ -9,8 +9,9 @@
 //!
 //! Every other module calls `config::get()` to obtain a `&'static Config`.
 
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 use color_eyre::eyre::{eyre, WrapErr};
@@ -20,6 +21,12 @@ use ratatui::style::Color;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use tracing::{info, warn};
 
+use crate::bluetooth::proximity::{WatchRule, WatchTarget};
+use crate::bluetooth::types::{
+    parse_uuid, Address, DeviceCategory, ScanFilter, ScanTransport, Uuid,
+};
+use crate::recorder::ExportFormat;
+
 /// Embedded default configuration — baked into the binary at compile time.
 const DEFAULT_CONFIG_STR: &str = include_str!("../default_config.toml");
 
@@ -158,6 +165,9 @@ pub enum SortMode {
     Name,
     Rssi,
     Address,
+    LastSeen,
+    /// Grouped by device category, then by name.
+    Type,
 }
 
 impl SortMode {
@@ -166,7 +176,9 @@ impl SortMode {
             Self::Default => Self::Name,
             Self::Name => Self::Rssi,
             Self::Rssi => Self::Address,
-            Self::Address => Self::Default,
+            Self::Address => Self::LastSeen,
+            Self::LastSeen => Self::Type,
+            Self::Type => Self::Default,
         }
     }
 
@@ -176,6 +188,8 @@ impl SortMode {
             Self::Name => "Name",
             Self::Rssi => "RSSI",
             Self::Address => "Address",
+            Self::LastSeen => "Last Seen",
+            Self::Type => "Type",
         }
     }
 }
@@ -197,6 +211,12 @@ struct RawConfig {
     general: RawGeneral,
     bluetooth: RawBluetooth,
     notifications: RawNotifications,
+    battery: RawBattery,
+    watchlist: RawWatchlist,
+    blocklist: RawBlocklist,
+    admin_policy: RawAdminPolicy,
+    recorder: RawRecorder,
+    history: RawHistory,
     theme: RawTheme,
     keybindings: RawKeybindings,
 }
@@ -210,6 +230,11 @@ struct RawGeneral {
     device_list_percent: u16,
     sort_mode: String,
     search_mode: String,
+    category_filter: String,
+    stale_after_secs: u64,
+    hide_stale_devices: bool,
+    purge_stale_after_secs: u64,
+    signal_history_secs: u64,
 }
 
 impl Default for RawGeneral {
@@ -221,6 +246,11 @@ impl Default for RawGeneral {
             device_list_percent: 55,
             sort_mode: "default".into(),
             search_mode: "smart".into(),
+            category_filter: "all".into(),
+            stale_after_secs: 300,
+            hide_stale_devices: false,
+            purge_stale_after_secs: 0,
+            signal_history_secs: 300,
         }
     }
 }
@@ -230,6 +260,12 @@ impl Default for RawGeneral {
 struct RawBluetooth {
     auto_trust_on_pair: bool,
     connection_timeout_secs: u64,
+    scan_duration_secs: u64,
+    periodic_scan: bool,
+    periodic_scan_secs: u64,
+    periodic_scan_interval_mins: u64,
+    profile_dashboards: bool,
+    discovery: RawDiscovery,
 }
 
 impl Default for RawBluetooth {
@@ -237,6 +273,34 @@ impl Default for RawBluetooth {
         Self {
             auto_trust_on_pair: true,
             connection_timeout_secs: 30,
+            scan_duration_secs: 120,
+            periodic_scan: false,
+            periodic_scan_secs: 10,
+            periodic_scan_interval_mins: 5,
+            profile_dashboards: true,
+            discovery: RawDiscovery::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct RawDiscovery {
+    transport: String,
+    rssi_threshold: Option<i16>,
+    pathloss: Option<u16>,
+    uuids: Vec<String>,
+    duplicate_data: bool,
+}
+
+impl Default for RawDiscovery {
+    fn default() -> Self {
+        Self {
+            transport: "auto".into(),
+            rssi_threshold: None,
+            pathloss: None,
+            uuids: Vec::new(),
+            duplicate_data: false,
         }
     }
 }
@@ -246,7 +310,9 @@ impl Default for RawBluetooth {
 struct RawNotifications {
     success_duration_ms: u64,
     error_duration_ms: u64,
+    alert_duration_ms: u64,
     slide_speed: f32,
+    command: Vec<String>,
 }
 
 impl Default for RawNotifications {
@@ -254,7 +320,123 @@ impl Default for RawNotifications {
         Self {
             success_duration_ms: 3000,
             error_duration_ms: 7000,
+            alert_duration_ms: 8000,
             slide_speed: 0.08,
+            command: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct RawBattery {
+    low_percent: u8,
+    critical_percent: u8,
+    rearm_margin: u8,
+    devices: HashMap<String, RawBatteryOverride>,
+}
+
+impl Default for RawBattery {
+    fn default() -> Self {
+        Self {
+            low_percent: 20,
+            critical_percent: 10,
+            rearm_margin: 5,
+            devices: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct RawBatteryOverride {
+    low_percent: Option<u8>,
+    critical_percent: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct RawWatchlist {
+    leave_after_secs: u64,
+    entries: Vec<RawWatchEntry>,
+}
+
+impl Default for RawWatchlist {
+    fn default() -> Self {
+        Self {
+            leave_after_secs: 60,
+            entries: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct RawWatchEntry {
+    label: String,
+    address: Option<String>,
+    name: Option<String>,
+    enter_rssi: i16,
+    leave_rssi: i16,
+}
+
+impl Default for RawWatchEntry {
+    fn default() -> Self {
+        Self {
+            label: String::new(),
+            address: None,
+            name: None,
+            enter_rssi: -70,
+            leave_rssi: -80,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct RawBlocklist {
+    addresses: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct RawAdminPolicy {
+    presets: Vec<RawPolicyPreset>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct RawPolicyPreset {
+    name: String,
+    uuids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct RawRecorder {
+    export_format: String,
+}
+
+impl Default for RawRecorder {
+    fn default() -> Self {
+        Self {
+            export_format: "csv".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct RawHistory {
+    enabled: bool,
+    flush_interval_secs: u64,
+}
+
+impl Default for RawHistory {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            flush_interval_secs: 30,
         }
     }
 }
@@ -312,10 +494,30 @@ struct RawKeybindings {
     disconnect: String,
     pair: String,
     trust: String,
+    block: String,
+    wake_allowed: String,
+    media_play_pause: String,
+    media_next: String,
+    media_previous: String,
+    volume_up: String,
+    volume_down: String,
+    send_file: String,
+    transfers: String,
     remove: String,
     refresh: String,
     cycle_sort: String,
+    cycle_category: String,
+    blocked_filter: String,
+    gatt_browser: String,
+    profiles: String,
     rename: String,
+    discovery_filter: String,
+    adapter_settings: String,
+    admin_policy: String,
+    toggle_periodic_scan: String,
+    export_session: String,
+    scroll_detail_down: String,
+    scroll_detail_up: String,
 }
 
 impl Default for RawKeybindings {
@@ -334,10 +536,30 @@ impl Default for RawKeybindings {
             disconnect: "d".into(),
             pair: "p".into(),
             trust: "t".into(),
+            block: "B".into(),
+            wake_allowed: "W".into(),
+            media_play_pause: "m".into(),
+            media_next: ".".into(),
+            media_previous: ",".into(),
+            volume_up: "+".into(),
+            volume_down: "-".into(),
+            send_file: "F".into(),
+            transfers: "T".into(),
             remove: "r".into(),
             refresh: "R".into(),
             cycle_sort: "S".into(),
+            cycle_category: "c".into(),
+            blocked_filter: "X".into(),
+            gatt_browser: "b".into(),
+            profiles: "o".into(),
             rename: "A".into(),
+            discovery_filter: "f".into(),
+            adapter_settings: "C".into(),
+            admin_policy: "P".into(),
+            toggle_periodic_scan: "I".into(),
+            export_session: "e".into(),
+            scroll_detail_down: "J".into(),
+            scroll_detail_up: "K".into(),
         }
     }
 }
@@ -349,6 +571,12 @@ pub struct Config {
     pub general: GeneralConfig,
     pub bluetooth: BluetoothConfig,
     pub notifications: NotificationsConfig,
+    pub battery: BatteryConfig,
+    pub watchlist: WatchlistConfig,
+    pub blocklist: BlocklistConfig,
+    pub admin_policy: AdminPolicyConfig,
+    pub recorder: RecorderConfig,
+    pub history: HistoryConfig,
     pub theme: ThemeConfig,
     pub keys: KeybindingsConfig,
 }
@@ -361,19 +589,114 @@ pub struct GeneralConfig {
     pub device_list_percent: u16,
     pub sort_mode: SortMode,
     pub search_mode: SearchMode,
+    /// Only list devices of this category (`None` = all).
+    pub category_filter: Option<DeviceCategory>,
+    /// Devices unseen for this long are dimmed.
+    pub stale_after_secs: u64,
+    /// Hide unpaired stale devices from the list.
+    pub hide_stale_devices: bool,
+    /// Drop unpaired devices unseen for this long (`0` = never).
+    pub purge_stale_after_secs: u64,
+    /// Time span covered by the detail panel's signal chart.
+    pub signal_history_secs: u64,
 }
 
 #[derive(Debug, Clone)]
 pub struct BluetoothConfig {
     pub auto_trust_on_pair: bool,
     pub connection_timeout_secs: u64,
+    /// Manual scan length in seconds (`0` = until stopped).
+    pub scan_duration_secs: u64,
+    /// Start with the periodic background scan enabled.
+    pub periodic_scan: bool,
+    /// Length of each periodic scan in seconds.
+    pub periodic_scan_secs: u64,
+    /// Minutes between the starts of two periodic scans.
+    pub periodic_scan_interval_mins: u64,
+    /// Read / subscribe to standard GATT profiles (battery, heart rate, …)
+    /// of connected devices.
+    pub profile_dashboards: bool,
+    /// Discovery filter applied when scanning starts.
+    pub discovery: ScanFilter,
 }
 
 #[derive(Debug, Clone)]
 pub struct NotificationsConfig {
     pub success_duration_ms: u64,
     pub error_duration_ms: u64,
+    /// How long a non-modal alert toast stays on screen.
+    pub alert_duration_ms: u64,
     pub slide_speed: f32,
+    /// External notifier argv (`{title}`, `{body}`, `{urgency}` are
+    /// substituted). Empty = in-app toasts only.
+    pub command: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatteryConfig {
+    /// Thresholds applied to devices without an override.
+    pub thresholds: BatteryThresholds,
+    /// Points above a threshold a device must recharge to before that
+    /// alert can fire again.
+    pub rearm_margin: u8,
+    /// Per-device threshold overrides.
+    pub devices: HashMap<Address, BatteryThresholds>,
+}
+
+impl BatteryConfig {
+    /// Thresholds in effect for `address`.
+    pub fn thresholds_for(&self, address: &Address) -> BatteryThresholds {
+        self.devices
+            .get(address)
+            .copied()
+            .unwrap_or(self.thresholds)
+    }
+}
+
+/// Low / critical battery percentages (`critical <= low`).
+#[derive(Debug, Clone, Copy)]
+pub struct BatteryThresholds {
+    pub low: u8,
+    pub critical: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct WatchlistConfig {
+    /// A watched device not heard from for this long has left range.
+    pub leave_after_secs: u64,
+    pub rules: Vec<WatchRule>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlocklistConfig {
+    /// Devices blocked whenever they appear.
+    pub addresses: HashSet<Address>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AdminPolicyConfig {
+    /// Named service allowlists offered by the admin policy view.
+    pub presets: Vec<PolicyPreset>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PolicyPreset {
+    pub name: String,
+    pub uuids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecorderConfig {
+    /// Format used by the export key when no `--record` path is given.
+    pub export_format: ExportFormat,
+}
+
+#[derive(Debug, Clone)]
+pub struct HistoryConfig {
+    /// Record device history to the XDG data dir.
+    pub enabled: bool,
+    /// How often pending history changes are written to disk.
+    pub flush_interval_secs: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -409,10 +732,30 @@ pub struct KeybindingsConfig {
     pub disconnect: KeyCode,
     pub pair: KeyCode,
     pub trust: KeyCode,
+    pub block: KeyCode,
+    pub wake_allowed: KeyCode,
+    pub media_play_pause: KeyCode,
+    pub media_next: KeyCode,
+    pub media_previous: KeyCode,
+    pub volume_up: KeyCode,
+    pub volume_down: KeyCode,
+    pub send_file: KeyCode,
+    pub transfers: KeyCode,
     pub remove: KeyCode,
     pub refresh: KeyCode,
     pub cycle_sort: KeyCode,
+    pub cycle_category: KeyCode,
+    pub blocked_filter: KeyCode,
+    pub gatt_browser: KeyCode,
+    pub profiles: KeyCode,
     pub rename: KeyCode,
+    pub discovery_filter: KeyCode,
+    pub adapter_settings: KeyCode,
+    pub admin_policy: KeyCode,
+    pub toggle_periodic_scan: KeyCode,
+    pub export_session: KeyCode,
+    pub scroll_detail_down: KeyCode,
+    pub scroll_detail_up: KeyCode,
 }
 
 // ─── Raw → Resolved conversion ─────────────────────────────────────────────
@@ -429,6 +772,8 @@ impl From<RawConfig> for Config {
                     "name" => SortMode::Name,
                     "rssi" => SortMode::Rssi,
                     "address" => SortMode::Address,
+                    "last_seen" => SortMode::LastSeen,
+                    "type" => SortMode::Type,
                     _ => SortMode::Default,
                 },
                 search_mode: match raw.general.search_mode.as_str() {
@@ -436,15 +781,66 @@ impl From<RawConfig> for Config {
                     "regex" => SearchMode::Regex,
                     _ => SearchMode::Smart,
                 },
+                category_filter: DeviceCategory::from_name(&raw.general.category_filter),
+                stale_after_secs: raw.general.stale_after_secs.clamp(10, 86_400),
+                hide_stale_devices: raw.general.hide_stale_devices,
+                purge_stale_after_secs: match raw.general.purge_stale_after_secs {
+                    0 => 0,
+                    secs => secs.clamp(60, 604_800),
+                },
+                signal_history_secs: raw.general.signal_history_secs.clamp(30, 3600),
             },
             bluetooth: BluetoothConfig {
                 auto_trust_on_pair: raw.bluetooth.auto_trust_on_pair,
                 connection_timeout_secs: raw.bluetooth.connection_timeout_secs.clamp(5, 120),
+                scan_duration_secs: match raw.bluetooth.scan_duration_secs {
+                    0 => 0,
+                    secs => secs.clamp(5, 3600),
+                },
+                periodic_scan: raw.bluetooth.periodic_scan,
+                periodic_scan_secs: raw.bluetooth.periodic_scan_secs.clamp(5, 300),
+                periodic_scan_interval_mins: raw
+                    .bluetooth
+                    .periodic_scan_interval_mins
+                    .clamp(1, 1440),
+                profile_dashboards: raw.bluetooth.profile_dashboards,
+                discovery: resolve_discovery(&raw.bluetooth.discovery),
             },
             notifications: NotificationsConfig {
                 success_duration_ms: raw.notifications.success_duration_ms.clamp(500, 30_000),
                 error_duration_ms: raw.notifications.error_duration_ms.clamp(500, 60_000),
+                alert_duration_ms: raw.notifications.alert_duration_ms.clamp(1000, 120_000),
                 slide_speed: raw.notifications.slide_speed.clamp(0.01, 1.0),
+                command: raw.notifications.command,
+            },
+            battery: resolve_battery(&raw.battery),
+            watchlist: WatchlistConfig {
+                leave_after_secs: raw.watchlist.leave_after_secs.clamp(10, 3600),
+                rules: raw
+                    .watchlist
+                    .entries
+                    .iter()
+                    .filter_map(resolve_watch_entry)
+                    .collect(),
+            },
+            blocklist: resolve_blocklist(&raw.blocklist),
+            admin_policy: AdminPolicyConfig {
+                presets: raw
+                    .admin_policy
+                    .presets
+                    .iter()
+                    .filter_map(resolve_policy_preset)
+                    .collect(),
+            },
+            recorder: RecorderConfig {
+                export_format: match raw.recorder.export_format.as_str() {
+                    "json" => ExportFormat::Json,
+                    _ => ExportFormat::Csv,
+                },
+            },
+            history: HistoryConfig {
+                enabled: raw.history.enabled,
+                flush_interval_secs: raw.history.flush_interval_secs.clamp(5, 3600),
             },
             theme: ThemeConfig {
                 palette: Palette {
@@ -473,15 +869,222 @@ impl From<RawConfig> for Config {
                 disconnect: parse_key(&raw.keybindings.disconnect),
                 pair: parse_key(&raw.keybindings.pair),
                 trust: parse_key(&raw.keybindings.trust),
+                block: parse_key(&raw.keybindings.block),
+                wake_allowed: parse_key(&raw.keybindings.wake_allowed),
+                media_play_pause: parse_key(&raw.keybindings.media_play_pause),
+                media_next: parse_key(&raw.keybindings.media_next),
+                media_previous: parse_key(&raw.keybindings.media_previous),
+                volume_up: parse_key(&raw.keybindings.volume_up),
+                volume_down: parse_key(&raw.keybindings.volume_down),
+                send_file: parse_key(&raw.keybindings.send_file),
+                transfers: parse_key(&raw.keybindings.transfers),
                 remove: parse_key(&raw.keybindings.remove),
                 refresh: parse_key(&raw.keybindings.refresh),
                 cycle_sort: parse_key(&raw.keybindings.cycle_sort),
+                cycle_category: parse_key(&raw.keybindings.cycle_category),
+                blocked_filter: parse_key(&raw.keybindings.blocked_filter),
+                gatt_browser: parse_key(&raw.keybindings.gatt_browser),
+                profiles: parse_key(&raw.keybindings.profiles),
                 rename: parse_key(&raw.keybindings.rename),
+                discovery_filter: parse_key(&raw.keybindings.discovery_filter),
+                adapter_settings: parse_key(&raw.keybindings.adapter_settings),
+                admin_policy: parse_key(&raw.keybindings.admin_policy),
+                toggle_periodic_scan: parse_key(&raw.keybindings.toggle_periodic_scan),
+                export_session: parse_key(&raw.keybindings.export_session),
+                scroll_detail_down: parse_key(&raw.keybindings.scroll_detail_down),
+                scroll_detail_up: parse_key(&raw.keybindings.scroll_detail_up),
             },
         }
     }
 }
 
+fn resolve_discovery(raw: &RawDiscovery) -> ScanFilter {
+    let transport = match raw.transport.as_str() {
+        "le" => ScanTransport::Le,
+        "bredr" => ScanTransport::BrEdr,
+        _ => ScanTransport::Auto,
+    };
+    let rssi = raw.rssi_threshold.map(|r| r.clamp(-127, 20));
+    let pathloss = match (rssi, raw.pathloss) {
+        (Some(_), Some(_)) => {
+            warn!(
+                "discovery: rssi_threshold and pathloss are mutually exclusive — ignoring pathloss"
+            );
+            None
+        }
+        (_, p) => p.map(|p| p.min(137)),
+    };
+    let uuids = raw
+        .uuids
+        .iter()
+        .filter_map(|s| {
+            let parsed = parse_uuid(s);
+            if parsed.is_none() {
+                warn!("discovery: invalid UUID \"{s}\" in config — ignoring");
+            }
+            parsed
+        })
+        .collect();
+    ScanFilter {
+        transport,
+        rssi,
+        pathloss,
+        uuids,
+        duplicate_data: raw.duplicate_data,
+    }
+}
+
+/// Resolve battery thresholds, dropping overrides with unparseable addresses.
+fn resolve_battery(raw: &RawBattery) -> BatteryConfig {
+    let thresholds = |low: u8, critical: u8| {
+        let low = low.min(100);
+        BatteryThresholds {
+            low,
+            critical: critical.min(low),
+        }
+    };
+    let global = thresholds(raw.low_percent, raw.critical_percent);
+
+    let devices = raw
+        .devices
+        .iter()
+        .filter_map(|(addr, o)| {
+            let Ok(address) = addr.parse::<Address>() else {
+                warn!("battery: invalid device address \"{addr}\" in config — ignoring");
+                return None;
+            };
+            let low = o.low_percent.unwrap_or(global.low);
+            let critical = o.critical_percent.unwrap_or(global.critical);
+            Some((address, thresholds(low, critical)))
+        })
+        .collect();
+
+    BatteryConfig {
+        thresholds: global,
+        rearm_margin: raw.rearm_margin.min(50),
+        devices,
+    }
+}
+
+/// Resolve the blocklist; invalid addresses are dropped.
+fn resolve_blocklist(raw: &RawBlocklist) -> BlocklistConfig {
+    let addresses = raw
+        .addresses
+        .iter()
+        .filter_map(|addr| match addr.parse::<Address>() {
+            Ok(a) => Some(a),
+            Err(_) => {
+                warn!("blocklist: invalid address \"{addr}\" in config — ignoring");
+                None
+            }
+        })
+        .collect();
+    BlocklistConfig { addresses }
+}
+
+/// Resolve one admin policy preset; presets with an invalid UUID are
+/// dropped rather than applied with a service missing.
+fn resolve_policy_preset(raw: &RawPolicyPreset) -> Option<PolicyPreset> {
+    if raw.name.is_empty() || raw.uuids.is_empty() {
+        warn!("admin_policy: preset without a name or UUIDs — ignoring");
+        return None;
+    }
+    let mut uuids = Vec::with_capacity(raw.uuids.len());
+    for token in &raw.uuids {
+        match parse_uuid(token) {
+            Some(uuid) => uuids.push(uuid),
+            None => {
+                warn!(
+                    "admin_policy: invalid UUID \"{token}\" in preset \"{}\" — ignoring preset",
+                    raw.name
+                );
+                return None;
+            }
+        }
+    }
+    Some(PolicyPreset {
+        name: raw.name.clone(),
+        uuids,
+    })
+}
+
+/// Read the `[blocklist]` of a config file (or of an exported section),
+/// without touching the active configuration.
+pub fn load_blocklist(path: &Path) -> Result<BlocklistConfig> {
+    let contents =
+        fs::read_to_string(path).wrap_err_with(|| format!("Cannot read {}", path.display()))?;
+    let raw: RawConfig =
+        toml::from_str(&contents).wrap_err_with(|| format!("Cannot parse {}", path.display()))?;
+    Ok(resolve_blocklist(&raw.blocklist))
+}
+
+/// Resolve one watchlist entry; entries without a valid target are dropped.
+fn resolve_watch_entry(raw: &RawWatchEntry) -> Option<WatchRule> {
+    let target = match (&raw.address, &raw.name) {
+        (Some(addr), _) => match addr.parse::<Address>() {
+            Ok(a) => WatchTarget::Address(a),
+            Err(_) => {
+                warn!("watchlist: invalid address \"{addr}\" in config — ignoring entry");
+                return None;
+            }
+        },
+        (None, Some(pattern)) => match regex::RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+        {
+            Ok(re) => WatchTarget::Name(re),
+            Err(e) => {
+                warn!("watchlist: invalid name pattern \"{pattern}\": {e} — ignoring entry");
+                return None;
+            }
+        },
+        (None, None) => {
+            warn!("watchlist: entry without address or name — ignoring");
+            return None;
+        }
+    };
+    let label = if raw.label.is_empty() {
+        raw.address
+            .clone()
+            .or_else(|| raw.name.clone())
+            .unwrap_or_default()
+    } else {
+        raw.label.clone()
+    };
+    let enter_rssi = raw.enter_rssi.clamp(-127, 20);
+    Some(WatchRule {
+        label,
+        target,
+        enter_rssi,
+        // Leaving must not be easier than entering, or the state would flap.
+        leave_rssi: raw.leave_rssi.clamp(-127, enter_rssi),
+    })
+}
+
+/// How a bound key is written in the config, for hints in the UI. `None`
+/// for an unbound key.
+pub fn key_label(key: KeyCode) -> Option<String> {
+    Some(match key {
+        KeyCode::Enter => "Enter".into(),
+        KeyCode::Esc => "Esc".into(),
+        KeyCode::Tab => "Tab".into(),
+        KeyCode::Backspace => "Backspace".into(),
+        KeyCode::Char(' ') => "Space".into(),
+        KeyCode::Up => "Up".into(),
+        KeyCode::Down => "Down".into(),
+        KeyCode::Left => "Left".into(),
+        KeyCode::Right => "Right".into(),
+        KeyCode::Home => "Home".into(),
+        KeyCode::End => "End".into(),
+        KeyCode::PageUp => "PageUp".into(),
+        KeyCode::PageDown => "PageDown".into(),
+        KeyCode::Delete => "Delete".into(),
+        KeyCode::Insert => "Insert".into(),
+        KeyCode::Char(c) => c.to_string(),
+        _ => return None,
+    })
+}
+
 fn parse_key(s: &str) -> KeyCode {
     match s {
         "Enter" => KeyCode::Enter,
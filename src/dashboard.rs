@@ -0,0 +1,90 @@
+//! Standard-profile dashboards: the latest Battery, Device Information,
+//! Heart Rate and Environmental Sensing readings per device, as decoded by
+//! the worker's profile monitors.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::bluetooth::profiles::{DisField, EnvReading, HeartRate};
+use crate::bluetooth::types::{Address, ProfileReading};
+
+/// Latest readings of one device.
+#[derive(Debug, Default)]
+pub struct Dashboard {
+    /// Battery Service level, %.
+    pub battery: Option<u8>,
+    pub info: BTreeMap<DisField, String>,
+    pub heart_rate: Option<HeartRate>,
+    /// °C.
+    pub temperature: Option<f64>,
+    /// Relative humidity, %.
+    pub humidity: Option<f64>,
+    /// Pa.
+    pub pressure: Option<f64>,
+    pub uv_index: Option<u8>,
+}
+
+impl Dashboard {
+    pub fn has_environment(&self) -> bool {
+        self.temperature.is_some()
+            || self.humidity.is_some()
+            || self.pressure.is_some()
+            || self.uv_index.is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.info.is_empty() && self.heart_rate.is_none() && !self.has_environment()
+    }
+
+    /// Drop live values; device information stays valid across connections.
+    fn clear_live(&mut self) {
+        self.battery = None;
+        self.heart_rate = None;
+        self.temperature = None;
+        self.humidity = None;
+        self.pressure = None;
+        self.uv_index = None;
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Dashboards {
+    devices: HashMap<Address, Dashboard>,
+}
+
+impl Dashboards {
+    pub fn observe(&mut self, address: Address, reading: ProfileReading) {
+        let dash = self.devices.entry(address).or_default();
+        match reading {
+            ProfileReading::Battery(level) => dash.battery = Some(level),
+            ProfileReading::DeviceInfo(field, value) => {
+                dash.info.insert(field, value);
+            }
+            ProfileReading::HeartRate(hr) => dash.heart_rate = Some(hr),
+            ProfileReading::Environment(env) => match env {
+                EnvReading::Temperature(v) => dash.temperature = Some(v),
+                EnvReading::Humidity(v) => dash.humidity = Some(v),
+                EnvReading::Pressure(v) => dash.pressure = Some(v),
+                EnvReading::UvIndex(v) => dash.uv_index = Some(v),
+            },
+        }
+    }
+
+    pub fn get(&self, address: &Address) -> Option<&Dashboard> {
+        self.devices.get(address).filter(|d| !d.is_empty())
+    }
+
+    /// Battery Service level, for devices without BlueZ's `Battery1`.
+    pub fn battery(&self, address: &Address) -> Option<u8> {
+        self.devices.get(address).and_then(|d| d.battery)
+    }
+
+    pub fn disconnected(&mut self, address: &Address) {
+        if let Some(dash) = self.devices.get_mut(address) {
+            dash.clear_live();
+        }
+    }
+
+    pub fn forget(&mut self, address: &Address) {
+        self.devices.remove(address);
+    }
+}
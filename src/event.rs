@@ -83,4 +83,22 @@ impl EventHandler {
             }
         }
     }
+
+    /// Drain Bluetooth events until the worker reports scanning stopped.
+    /// Returns `false` if that doesn't happen within `timeout` or the worker
+    /// is gone. Used during shutdown, after the UI has stopped rendering.
+    pub async fn wait_for_scan_stop(&mut self, timeout: Duration) -> bool {
+        let wait = async {
+            while let Some(event) = self.bt_rx.recv().await {
+                if let BtEvent::ScanningChanged {
+                    scanning: false, ..
+                } = event
+                {
+                    return true;
+                }
+            }
+            false
+        };
+        tokio::time::timeout(timeout, wait).await.unwrap_or(false)
+    }
 }
@@ -0,0 +1,656 @@
+//! GATT browser state: the collapsible service → characteristic →
+//! descriptor tree shown in place of the detail panel, plus a value log and
+//! write form per characteristic.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::bluetooth::presentation;
+use crate::bluetooth::types::{Address, GattCharacteristic, GattService, GattTarget};
+use crate::gatt_export::{self, DiffRow, DiffSummary, GattExport};
+
+/// Log entries kept per characteristic; older ones scroll off.
+const MAX_LOG: usize = 200;
+
+/// One visible line of the tree, pointing into `GattBrowser::services`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GattNode {
+    Service(usize),
+    Characteristic(usize, usize),
+    Descriptor(usize, usize, usize),
+}
+
+impl GattNode {
+    /// Nesting level, for indentation.
+    pub fn depth(self) -> usize {
+        match self {
+            Self::Service(..) => 0,
+            Self::Characteristic(..) => 1,
+            Self::Descriptor(..) => 2,
+        }
+    }
+}
+
+/// What produced a value log entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogKind {
+    Read,
+    Notify,
+    Write,
+    Info,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// Time since the browser was opened.
+    pub at: Duration,
+    pub kind: LogKind,
+    pub text: String,
+}
+
+/// Value log and subscription state of one characteristic.
+#[derive(Debug, Clone, Default)]
+pub struct CharLog {
+    pub entries: VecDeque<LogEntry>,
+    pub notifying: bool,
+}
+
+/// How the write form interprets its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteFormat {
+    Hex,
+    Utf8,
+    U8,
+    U16,
+    U32,
+    I8,
+    I16,
+    I32,
+}
+
+impl WriteFormat {
+    const ALL: [Self; 8] = [
+        Self::Hex,
+        Self::Utf8,
+        Self::U8,
+        Self::U16,
+        Self::U32,
+        Self::I8,
+        Self::I16,
+        Self::I32,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Hex => "hex",
+            Self::Utf8 => "utf-8",
+            Self::U8 => "u8",
+            Self::U16 => "u16 le",
+            Self::U32 => "u32 le",
+            Self::I8 => "i8",
+            Self::I16 => "i16 le",
+            Self::I32 => "i32 le",
+        }
+    }
+
+    fn cycle(self, dir: isize) -> Self {
+        let idx = Self::ALL.iter().position(|f| *f == self).unwrap_or(0);
+        let len = Self::ALL.len() as isize;
+        Self::ALL[(idx as isize + dir).rem_euclid(len) as usize]
+    }
+
+    /// Encode the typed input. Integers accept decimal or `0x` hex and are
+    /// written little-endian.
+    pub fn encode(self, input: &str) -> Result<Vec<u8>, String> {
+        let input = input.trim();
+        let int = |min: i64, max: i64| -> Result<i64, String> {
+            let value = match input
+                .strip_prefix("0x")
+                .or_else(|| input.strip_prefix("0X"))
+            {
+                Some(hex) => i64::from_str_radix(hex, 16),
+                None => input.parse(),
+            }
+            .map_err(|_| format!("not an integer: {input:?}"))?;
+            if (min..=max).contains(&value) {
+                Ok(value)
+            } else {
+                Err(format!("{value} is out of range for {}", self.label()))
+            }
+        };
+        Ok(match self {
+            Self::Hex => {
+                let digits: String = input
+                    .trim_start_matches("0x")
+                    .chars()
+                    .filter(|c| !c.is_whitespace() && *c != ':' && *c != '-')
+                    .collect();
+                if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(format!("invalid hex: {input:?}"));
+                }
+                if digits.len() % 2 != 0 {
+                    return Err("odd number of hex digits".into());
+                }
+                // All ASCII now, so byte pairs are whole characters.
+                digits
+                    .as_bytes()
+                    .chunks(2)
+                    .map(|pair| {
+                        let pair = std::str::from_utf8(pair).expect("ASCII");
+                        u8::from_str_radix(pair, 16).expect("hex digits")
+                    })
+                    .collect()
+            }
+            Self::Utf8 => input.as_bytes().to_vec(),
+            Self::U8 => vec![int(0, u8::MAX.into())? as u8],
+            Self::U16 => (int(0, u16::MAX.into())? as u16).to_le_bytes().to_vec(),
+            Self::U32 => (int(0, u32::MAX.into())? as u32).to_le_bytes().to_vec(),
+            Self::I8 => vec![int(i8::MIN.into(), i8::MAX.into())? as i8 as u8],
+            Self::I16 => (int(i16::MIN.into(), i16::MAX.into())? as i16)
+                .to_le_bytes()
+                .to_vec(),
+            Self::I32 => (int(i32::MIN.into(), i32::MAX.into())? as i32)
+                .to_le_bytes()
+                .to_vec(),
+        })
+    }
+}
+
+/// Inline form for writing a characteristic value.
+#[derive(Debug, Clone)]
+pub struct WriteForm {
+    pub target: GattTarget,
+    pub input: String,
+    pub format: WriteFormat,
+    /// Write request (acknowledged) rather than write command.
+    pub with_response: bool,
+    pub error: Option<String>,
+}
+
+impl WriteForm {
+    pub fn cycle_format(&mut self, dir: isize) {
+        self.format = self.format.cycle(dir);
+        self.error = None;
+    }
+}
+
+/// One side of a comparison: the live tree or an export file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompareSource {
+    Live,
+    File(PathBuf),
+}
+
+impl CompareSource {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Live => "Live database".into(),
+            Self::File(path) => path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string()),
+        }
+    }
+}
+
+/// Picks the two sides of a comparison.
+#[derive(Debug, Clone)]
+pub struct ComparePicker {
+    pub sources: Vec<CompareSource>,
+    pub cursor: usize,
+    /// Source marked as the old side; defaults to the selection vs live.
+    pub base: Option<usize>,
+    pub error: Option<String>,
+}
+
+impl ComparePicker {
+    pub fn move_cursor(&mut self, delta: isize) {
+        let max = self.sources.len().saturating_sub(1);
+        self.cursor = self.cursor.saturating_add_signed(delta).min(max);
+        self.error = None;
+    }
+
+    /// Mark (or unmark) the selection as the old side.
+    pub fn toggle_base(&mut self) {
+        self.base = match self.base {
+            Some(i) if i == self.cursor => None,
+            _ => Some(self.cursor),
+        };
+        self.error = None;
+    }
+}
+
+/// Result of comparing two snapshots.
+#[derive(Debug, Clone)]
+pub struct CompareView {
+    pub old: String,
+    pub new: String,
+    pub rows: Vec<DiffRow>,
+    pub summary: DiffSummary,
+    /// Hide rows without changes.
+    pub changes_only: bool,
+    pub scroll: usize,
+}
+
+impl CompareView {
+    pub fn visible_rows(&self) -> Vec<&DiffRow> {
+        self.rows
+            .iter()
+            .filter(|r| !self.changes_only || r.change != gatt_export::Change::Same)
+            .collect()
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        let max = self.visible_rows().len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+}
+
+/// What the browser pane shows.
+#[derive(Debug, Clone)]
+pub enum GattView {
+    Tree,
+    Picker(ComparePicker),
+    Compare(CompareView),
+}
+
+/// Browser for one device's GATT database.
+#[derive(Debug, Clone)]
+pub struct GattBrowser {
+    pub address: Address,
+    pub name: String,
+    /// `None` while the worker is still enumerating.
+    pub services: Option<Vec<GattService>>,
+    /// Last enumeration error, shown instead of the tree.
+    pub error: Option<String>,
+    /// Handles of expanded services and characteristics.
+    expanded: HashSet<u16>,
+    /// Cursor position among the visible rows.
+    pub cursor: usize,
+    /// Value logs keyed by characteristic handle.
+    logs: HashMap<u16, CharLog>,
+    /// Open write form, if any.
+    pub write: Option<WriteForm>,
+    /// The tree came from the cache and live enumeration is still running.
+    pub cached: bool,
+    /// A snapshot for export is being taken.
+    pub exporting: bool,
+    /// The tree is a snapshot, with every readable value read.
+    values_read: bool,
+    pub view: GattView,
+    opened: Instant,
+}
+
+impl GattBrowser {
+    pub fn new(address: Address, name: String) -> Self {
+        Self {
+            address,
+            name,
+            services: None,
+            error: None,
+            expanded: HashSet::new(),
+            cursor: 0,
+            logs: HashMap::new(),
+            write: None,
+            cached: false,
+            exporting: false,
+            values_read: false,
+            view: GattView::Tree,
+            opened: Instant::now(),
+        }
+    }
+
+    /// Show a cached tree until live enumeration finishes.
+    pub fn set_cached(&mut self, services: Vec<GattService>) {
+        self.set_services(services);
+        self.cached = true;
+    }
+
+    /// Install a snapshot: an enumeration with every readable value read.
+    pub fn set_snapshot(&mut self, services: Vec<GattService>) {
+        self.set_services(services);
+        self.values_read = true;
+    }
+
+    /// Forget the current tree and wait for a fresh enumeration.
+    pub fn reload(&mut self) {
+        self.services = None;
+        self.error = None;
+        self.cached = false;
+        self.write = None;
+        self.logs.clear();
+    }
+
+    /// Install an enumerated database. Services start expanded; previously
+    /// expanded nodes stay expanded across reloads.
+    pub fn set_services(&mut self, services: Vec<GattService>) {
+        if self.expanded.is_empty() {
+            self.expanded = services.iter().map(|s| s.handle).collect();
+        }
+        self.services = Some(services);
+        self.error = None;
+        self.cached = false;
+        self.values_read = false;
+        self.clamp_cursor();
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.services = None;
+        self.error = Some(error);
+        self.cached = false;
+        self.exporting = false;
+        self.cursor = 0;
+        self.write = None;
+        for log in self.logs.values_mut() {
+            log.notifying = false;
+        }
+    }
+
+    /// Characteristic under the cursor (a descriptor selects its parent).
+    pub fn selected_characteristic(&self) -> Option<(GattTarget, &GattCharacteristic)> {
+        let (si, ci) = match self.selected()? {
+            GattNode::Service(_) => return None,
+            GattNode::Characteristic(si, ci) | GattNode::Descriptor(si, ci, _) => (si, ci),
+        };
+        let service = self.services.as_ref()?.get(si)?;
+        let characteristic = service.characteristics.get(ci)?;
+        let target = GattTarget {
+            address: self.address,
+            service: service.handle,
+            characteristic: characteristic.handle,
+        };
+        Some((target, characteristic))
+    }
+
+    fn find_characteristic(&self, handle: u16) -> Option<&GattCharacteristic> {
+        self.services
+            .iter()
+            .flatten()
+            .flat_map(|s| &s.characteristics)
+            .find(|c| c.handle == handle)
+    }
+
+    pub fn log(&self, handle: u16) -> Option<&CharLog> {
+        self.logs.get(&handle)
+    }
+
+    pub fn is_notifying(&self, handle: u16) -> bool {
+        self.logs.get(&handle).is_some_and(|l| l.notifying)
+    }
+
+    /// Whether any subscription is active (to clean up on close).
+    pub fn any_notifying(&self) -> bool {
+        self.logs.values().any(|l| l.notifying)
+    }
+
+    pub fn push_log(&mut self, handle: u16, kind: LogKind, text: String) {
+        let log = self.logs.entry(handle).or_default();
+        if log.entries.len() >= MAX_LOG {
+            log.entries.pop_front();
+        }
+        log.entries.push_back(LogEntry {
+            at: self.opened.elapsed(),
+            kind,
+            text,
+        });
+    }
+
+    /// Log a read / notified value, formatted via the characteristic's
+    /// presentation format when it has one.
+    pub fn push_value(&mut self, handle: u16, value: &[u8], notified: bool) {
+        let format = self
+            .find_characteristic(handle)
+            .and_then(|c| c.presentation.as_ref());
+        let text = presentation::format_value(format, value);
+        let kind = if notified {
+            LogKind::Notify
+        } else {
+            LogKind::Read
+        };
+        self.push_log(handle, kind, text);
+    }
+
+    pub fn set_notifying(&mut self, handle: u16, active: bool) {
+        let log = self.logs.entry(handle).or_default();
+        if log.notifying == active {
+            return;
+        }
+        log.notifying = active;
+        let text = if active {
+            "Notifications on"
+        } else {
+            "Notifications off"
+        };
+        self.push_log(handle, LogKind::Info, text.into());
+    }
+
+    /// Export form of the tree currently shown.
+    pub fn to_export(&self) -> Option<GattExport> {
+        let services = self.services.as_ref()?;
+        Some(GattExport::from_services(
+            self.address,
+            &self.name,
+            services,
+            self.values_read,
+        ))
+    }
+
+    /// Open the comparison picker: the live tree (when loaded) and this
+    /// device's exports, newest first.
+    pub fn open_picker(&mut self) {
+        let mut sources = Vec::new();
+        if self.services.is_some() && !self.cached {
+            sources.push(CompareSource::Live);
+        }
+        sources.extend(
+            gatt_export::list_exports(self.address)
+                .into_iter()
+                .map(CompareSource::File),
+        );
+        self.view = GattView::Picker(ComparePicker {
+            sources,
+            cursor: 0,
+            base: None,
+            error: None,
+        });
+    }
+
+    /// Compare the picker's base (or the selection) against the selection
+    /// (or the live tree).
+    pub fn compare_selected(&mut self) {
+        let GattView::Picker(picker) = &mut self.view else {
+            return;
+        };
+        let Some(selected) = picker.sources.get(picker.cursor).cloned() else {
+            return;
+        };
+        let (old, new) = match picker.base.and_then(|i| picker.sources.get(i)).cloned() {
+            Some(base) if base != selected => (base, selected),
+            Some(_) => {
+                picker.error = Some("Select a different snapshot to compare with".into());
+                return;
+            }
+            None if selected == CompareSource::Live => {
+                picker.error = Some("Mark a base with Space, or pick an export".into());
+                return;
+            }
+            None if self.services.is_none() || self.cached => {
+                picker.error = Some("Live database not loaded; mark a base with Space".into());
+                return;
+            }
+            None => (selected, CompareSource::Live),
+        };
+
+        let load = |source: &CompareSource| match source {
+            CompareSource::Live => self
+                .to_export()
+                .ok_or_else(|| "Live database not loaded".to_string()),
+            CompareSource::File(path) => GattExport::load(path).map_err(|e| format!("{e:#}")),
+        };
+        match (load(&old), load(&new)) {
+            (Ok(old_export), Ok(new_export)) => {
+                let (rows, summary) = gatt_export::diff(&old_export, &new_export);
+                self.view = GattView::Compare(CompareView {
+                    old: old.label(),
+                    new: new.label(),
+                    rows,
+                    summary,
+                    changes_only: false,
+                    scroll: 0,
+                });
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                if let GattView::Picker(picker) = &mut self.view {
+                    picker.error = Some(e);
+                }
+            }
+        }
+    }
+
+    pub fn clear_log(&mut self, handle: u16) {
+        if let Some(log) = self.logs.get_mut(&handle) {
+            log.entries.clear();
+        }
+    }
+
+    pub fn is_expanded(&self, handle: u16) -> bool {
+        self.expanded.contains(&handle)
+    }
+
+    /// Visible rows, top to bottom.
+    pub fn rows(&self) -> Vec<GattNode> {
+        let mut rows = Vec::new();
+        for (si, service) in self.services.iter().flatten().enumerate() {
+            rows.push(GattNode::Service(si));
+            if !self.is_expanded(service.handle) {
+                continue;
+            }
+            for (ci, characteristic) in service.characteristics.iter().enumerate() {
+                rows.push(GattNode::Characteristic(si, ci));
+                if self.is_expanded(characteristic.handle) {
+                    rows.extend(
+                        (0..characteristic.descriptors.len())
+                            .map(|di| GattNode::Descriptor(si, ci, di)),
+                    );
+                }
+            }
+        }
+        rows
+    }
+
+    /// Node under the cursor.
+    pub fn selected(&self) -> Option<GattNode> {
+        self.rows().get(self.cursor).copied()
+    }
+
+    /// ATT handle of a node.
+    pub fn handle(&self, node: GattNode) -> Option<u16> {
+        let services = self.services.as_ref()?;
+        Some(match node {
+            GattNode::Service(si) => services.get(si)?.handle,
+            GattNode::Characteristic(si, ci) => services.get(si)?.characteristics.get(ci)?.handle,
+            GattNode::Descriptor(si, ci, di) => {
+                services
+                    .get(si)?
+                    .characteristics
+                    .get(ci)?
+                    .descriptors
+                    .get(di)?
+                    .handle
+            }
+        })
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        let len = self.rows().len();
+        if len == 0 {
+            self.cursor = 0;
+            return;
+        }
+        self.cursor = self.cursor.saturating_add_signed(delta).min(len - 1);
+    }
+
+    pub fn jump(&mut self, to_end: bool) {
+        self.cursor = if to_end {
+            self.rows().len().saturating_sub(1)
+        } else {
+            0
+        };
+    }
+
+    /// Expand or collapse the node under the cursor.
+    pub fn toggle(&mut self) {
+        if let Some(handle) = self.selected().and_then(|n| self.handle(n)) {
+            if !self.expanded.remove(&handle) {
+                self.expanded.insert(handle);
+            }
+        }
+    }
+
+    /// Expand the node under the cursor.
+    pub fn expand(&mut self) {
+        if let Some(handle) = self.selected().and_then(|n| self.handle(n)) {
+            self.expanded.insert(handle);
+        }
+    }
+
+    /// Collapse the node under the cursor, or jump to its parent if it is
+    /// already collapsed (or a leaf).
+    pub fn collapse(&mut self) {
+        let Some(node) = self.selected() else {
+            return;
+        };
+        if let Some(handle) = self.handle(node) {
+            if self.expanded.remove(&handle) {
+                return;
+            }
+        }
+        let parent = match node {
+            GattNode::Service(_) => return,
+            GattNode::Characteristic(si, _) => GattNode::Service(si),
+            GattNode::Descriptor(si, ci, _) => GattNode::Characteristic(si, ci),
+        };
+        if let Some(pos) = self.rows().iter().position(|&n| n == parent) {
+            self.cursor = pos;
+        }
+    }
+
+    /// Expand everything, or collapse everything if already fully expanded.
+    pub fn toggle_all(&mut self) {
+        let all: HashSet<u16> = self
+            .services
+            .iter()
+            .flatten()
+            .flat_map(|s| {
+                std::iter::once(s.handle).chain(s.characteristics.iter().map(|c| c.handle))
+            })
+            .collect();
+        self.expanded = if self.expanded == all {
+            HashSet::new()
+        } else {
+            all
+        };
+        self.clamp_cursor();
+    }
+
+    fn clamp_cursor(&mut self) {
+        self.cursor = self.cursor.min(self.rows().len().saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_input_is_decoded_or_rejected() {
+        let hex = |input| WriteFormat::Hex.encode(input);
+        assert_eq!(hex("0x01 0a:FF-7f"), Ok(vec![0x01, 0x0a, 0xff, 0x7f]));
+        assert_eq!(hex(""), Ok(Vec::new()));
+        assert!(hex("aé1").is_err());
+        assert!(hex("+1").is_err());
+        assert!(hex("abc").is_err());
+    }
+}
@@ -0,0 +1,561 @@
+//! GATT database export, cache and comparison.
+//!
+//! A snapshot of a device's service → characteristic → descriptor tree, with
+//! the values of readable characteristics, is written as JSON under
+//! `<data dir>/gatt/`. The most recent enumeration of each device is also
+//! cached there so the browser can show it while BlueZ re-resolves services.
+//! Two exports can be compared attribute by attribute; attributes are matched
+//! by UUID and position rather than handle so that firmware revisions which
+//! shift handles still line up.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::bluetooth::assigned_numbers;
+use crate::bluetooth::types::{
+    short_uuid, Address, GattCharacteristic, GattDescriptor, GattProps, GattService,
+    PresentationFormat, Uuid,
+};
+use crate::history::{self, format_rfc3339, unix_now};
+
+/// Bumped when the file layout changes incompatibly.
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GattExport {
+    pub version: u32,
+    pub address: String,
+    pub name: String,
+    /// RFC 3339 UTC.
+    pub exported: String,
+    /// Every readable characteristic was read (a snapshot). A plain
+    /// enumeration only carries values read by hand, so they aren't
+    /// compared. Files from before this field are all snapshots.
+    #[serde(default = "snapshot_by_default")]
+    pub values_read: bool,
+    pub services: Vec<ServiceExport>,
+}
+
+fn snapshot_by_default() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceExport {
+    pub handle: u16,
+    pub uuid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub primary: bool,
+    #[serde(default)]
+    pub characteristics: Vec<CharacteristicExport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CharacteristicExport {
+    pub handle: u16,
+    pub uuid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub properties: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presentation: Option<PresentationFormat>,
+    /// Value as lowercase hex, when it was read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub descriptors: Vec<DescriptorExport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DescriptorExport {
+    pub handle: u16,
+    pub uuid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl GattExport {
+    pub fn from_services(
+        address: Address,
+        name: &str,
+        services: &[GattService],
+        values_read: bool,
+    ) -> Self {
+        let name_of = |uuid: &Uuid| assigned_numbers::uuid_name(uuid).map(str::to_string);
+        Self {
+            version: FORMAT_VERSION,
+            address: address.to_string(),
+            name: name.to_string(),
+            exported: format_rfc3339(unix_now()),
+            values_read,
+            services: services
+                .iter()
+                .map(|s| ServiceExport {
+                    handle: s.handle,
+                    uuid: s.uuid.to_string(),
+                    name: name_of(&s.uuid),
+                    primary: s.primary,
+                    characteristics: s
+                        .characteristics
+                        .iter()
+                        .map(|c| CharacteristicExport {
+                            handle: c.handle,
+                            uuid: c.uuid.to_string(),
+                            name: name_of(&c.uuid),
+                            properties: c.props.labels().into_iter().map(String::from).collect(),
+                            presentation: c.presentation,
+                            value: c.value.as_deref().map(to_hex),
+                            descriptors: c
+                                .descriptors
+                                .iter()
+                                .map(|d| DescriptorExport {
+                                    handle: d.handle,
+                                    uuid: d.uuid.to_string(),
+                                    name: name_of(&d.uuid),
+                                })
+                                .collect(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Rebuild the browser tree (e.g. from the cache). Attributes with an
+    /// unparsable UUID are skipped.
+    pub fn to_services(&self) -> Vec<GattService> {
+        let uuid = |s: &str| s.parse::<Uuid>().ok();
+        self.services
+            .iter()
+            .filter_map(|s| {
+                Some(GattService {
+                    handle: s.handle,
+                    uuid: uuid(&s.uuid)?,
+                    primary: s.primary,
+                    characteristics: s
+                        .characteristics
+                        .iter()
+                        .filter_map(|c| {
+                            Some(GattCharacteristic {
+                                handle: c.handle,
+                                uuid: uuid(&c.uuid)?,
+                                props: GattProps::from_labels(&c.properties),
+                                presentation: c.presentation,
+                                value: c.value.as_deref().and_then(from_hex),
+                                descriptors: c
+                                    .descriptors
+                                    .iter()
+                                    .filter_map(|d| {
+                                        Some(GattDescriptor {
+                                            handle: d.handle,
+                                            uuid: uuid(&d.uuid)?,
+                                        })
+                                    })
+                                    .collect(),
+                            })
+                        })
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        let export: Self = serde_json::from_str(&contents)
+            .wrap_err_with(|| format!("{} is not a GATT export", path.display()))?;
+        if export.version > FORMAT_VERSION {
+            return Err(eyre!(
+                "{}: export format v{} is newer than this build supports",
+                path.display(),
+                export.version
+            ));
+        }
+        Ok(export)
+    }
+
+    /// Write the export to `path`, creating parent directories.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self).wrap_err("Failed to serialise")?;
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("Failed to create {}", dir.display()))?;
+        }
+        fs::write(path, contents).wrap_err_with(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+fn to_hex(value: &[u8]) -> String {
+    value.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// ─── Files ──────────────────────────────────────────────────────────────────
+
+fn gatt_dir() -> PathBuf {
+    history::data_dir().join("gatt")
+}
+
+/// File name prefix for a device's exports (`AA-BB-CC-DD-EE-FF`).
+fn file_prefix(address: Address) -> String {
+    address.to_string().replace(':', "-")
+}
+
+/// `<data dir>/gatt/<address>-<time>.json`.
+pub fn export_path(address: Address) -> PathBuf {
+    let stamp = format_rfc3339(unix_now()).replace(':', "");
+    gatt_dir().join(format!("{}-{stamp}.json", file_prefix(address)))
+}
+
+fn cache_path(address: Address) -> PathBuf {
+    gatt_dir()
+        .join("cache")
+        .join(format!("{}.json", file_prefix(address)))
+}
+
+/// Exports of `address` in the data dir, newest first.
+pub fn list_exports(address: Address) -> Vec<PathBuf> {
+    let prefix = format!("{}-", file_prefix(address));
+    let Ok(entries) = fs::read_dir(gatt_dir()) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.extension().is_some_and(|e| e == "json")
+                && p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with(&prefix))
+        })
+        .collect();
+    // The timestamp suffix sorts chronologically.
+    paths.sort();
+    paths.reverse();
+    paths
+}
+
+/// Remember the latest enumeration of a device. Failures are only logged:
+/// the cache is an optimisation.
+pub fn store_cache(address: Address, name: &str, services: &[GattService]) {
+    let path = cache_path(address);
+    if let Err(e) = GattExport::from_services(address, name, services, false).save(&path) {
+        warn!("Cannot write GATT cache: {e:#}");
+    }
+}
+
+/// Cached tree of a device, if one was stored and still parses.
+pub fn load_cache(address: Address) -> Option<Vec<GattService>> {
+    let path = cache_path(address);
+    if !path.exists() {
+        return None;
+    }
+    match GattExport::load(&path) {
+        Ok(export) => Some(export.to_services()),
+        Err(e) => {
+            warn!("Ignoring GATT cache: {e:#}");
+            None
+        }
+    }
+}
+
+// ─── Comparison ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Same,
+    Added,
+    Removed,
+    Changed,
+}
+
+impl Change {
+    /// Marker used in the CLI and the compare view.
+    pub fn marker(self) -> char {
+        match self {
+            Self::Same => ' ',
+            Self::Added => '+',
+            Self::Removed => '-',
+            Self::Changed => '~',
+        }
+    }
+}
+
+/// One attribute in a comparison, in tree order.
+#[derive(Debug, Clone)]
+pub struct DiffRow {
+    /// 0 = service, 1 = characteristic, 2 = descriptor.
+    pub depth: usize,
+    pub change: Change,
+    pub label: String,
+    /// What changed, e.g. `value 01 → 02`.
+    pub detail: Option<String>,
+    /// Informational only and not counted as a change, e.g. a handle move.
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DiffSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+    /// Both sides were snapshots, so characteristic values were compared.
+    pub values_compared: bool,
+}
+
+impl DiffSummary {
+    pub fn is_empty(&self) -> bool {
+        self.added + self.removed + self.changed == 0
+    }
+}
+
+/// Compare two exports. Rows follow `new`'s order, with attributes that only
+/// exist in `old` listed after their surviving siblings. Values are only
+/// compared when both sides were snapshots.
+pub fn diff(old: &GattExport, new: &GattExport) -> (Vec<DiffRow>, DiffSummary) {
+    let compare_values = old.values_read && new.values_read;
+    let mut rows = Vec::new();
+    let mut summary = DiffSummary {
+        values_compared: compare_values,
+        ..DiffSummary::default()
+    };
+    let mut push = |rows: &mut Vec<DiffRow>, depth, change, label, detail, note| {
+        match change {
+            Change::Added => summary.added += 1,
+            Change::Removed => summary.removed += 1,
+            Change::Changed => summary.changed += 1,
+            Change::Same => {}
+        }
+        rows.push(DiffRow {
+            depth,
+            change,
+            label,
+            detail,
+            note,
+        });
+    };
+
+    for pair in pair_up(&old.services, &new.services, |s| &s.uuid) {
+        let (o, n) = pair;
+        let any = n.or(o).expect("pair has a side");
+        let label = attr_label(&any.uuid, any.name.as_deref());
+        let mut details = Vec::new();
+        let mut note = None;
+        if let (Some(o), Some(n)) = (o, n) {
+            note = handle_move(o.handle, n.handle);
+            if o.primary != n.primary {
+                details.push(format!(
+                    "{} → {}",
+                    primary_label(o.primary),
+                    primary_label(n.primary)
+                ));
+            }
+        }
+        push(
+            &mut rows,
+            0,
+            change_of(o, n, &details),
+            label,
+            join(details),
+            note,
+        );
+
+        let empty = Vec::new();
+        let old_chars = o.map_or(&empty, |s| &s.characteristics);
+        let new_chars = n.map_or(&empty, |s| &s.characteristics);
+        for (oc, nc) in pair_up(old_chars, new_chars, |c| &c.uuid) {
+            let any = nc.or(oc).expect("pair has a side");
+            let label = attr_label(&any.uuid, any.name.as_deref());
+            let mut details = Vec::new();
+            let mut note = None;
+            if let (Some(o), Some(n)) = (oc, nc) {
+                note = handle_move(o.handle, n.handle);
+                if o.properties != n.properties {
+                    details.push(format!(
+                        "[{}] → [{}]",
+                        o.properties.join(" "),
+                        n.properties.join(" ")
+                    ));
+                }
+                if compare_values && o.value != n.value {
+                    details.push(format!(
+                        "value {} → {}",
+                        value_label(o.value.as_deref()),
+                        value_label(n.value.as_deref())
+                    ));
+                }
+            }
+            push(
+                &mut rows,
+                1,
+                change_of(oc, nc, &details),
+                label,
+                join(details),
+                note,
+            );
+
+            let empty = Vec::new();
+            let old_descs = oc.map_or(&empty, |c| &c.descriptors);
+            let new_descs = nc.map_or(&empty, |c| &c.descriptors);
+            for (od, nd) in pair_up(old_descs, new_descs, |d| &d.uuid) {
+                let any = nd.or(od).expect("pair has a side");
+                let label = attr_label(&any.uuid, any.name.as_deref());
+                let note = od
+                    .zip(nd)
+                    .and_then(|(o, n)| handle_move(o.handle, n.handle));
+                push(&mut rows, 2, change_of(od, nd, &[]), label, None, note);
+            }
+        }
+    }
+    (rows, summary)
+}
+
+/// Match siblings by UUID and occurrence (the second Custom Service in
+/// `old` pairs with the second in `new`).
+fn pair_up<'a, T>(
+    old: &'a [T],
+    new: &'a [T],
+    key: impl Fn(&T) -> &String,
+) -> Vec<(Option<&'a T>, Option<&'a T>)> {
+    let occurrence = |items: &'a [T]| -> Vec<(String, usize)> {
+        let mut seen: HashMap<&String, usize> = HashMap::new();
+        items
+            .iter()
+            .map(|item| {
+                let n = seen.entry(key(item)).or_default();
+                *n += 1;
+                (key(item).to_ascii_lowercase(), *n)
+            })
+            .collect()
+    };
+    let old_keys = occurrence(old);
+    let new_keys = occurrence(new);
+
+    let mut pairs: Vec<_> = new
+        .iter()
+        .zip(&new_keys)
+        .map(|(item, k)| {
+            let matched = old_keys.iter().position(|ok| ok == k).map(|i| &old[i]);
+            (matched, Some(item))
+        })
+        .collect();
+    pairs.extend(
+        old.iter()
+            .zip(&old_keys)
+            .filter(|(_, k)| !new_keys.contains(k))
+            .map(|(item, _)| (Some(item), None)),
+    );
+    pairs
+}
+
+fn change_of<T>(old: Option<&T>, new: Option<&T>, details: &[String]) -> Change {
+    match (old, new) {
+        (None, _) => Change::Added,
+        (_, None) => Change::Removed,
+        _ if details.is_empty() => Change::Same,
+        _ => Change::Changed,
+    }
+}
+
+/// Handles shift between firmware revisions without the attribute itself
+/// changing, so a move is noted rather than counted.
+fn handle_move(old: u16, new: u16) -> Option<String> {
+    (old != new).then(|| format!("handle 0x{old:04x} → 0x{new:04x}"))
+}
+
+fn primary_label(primary: bool) -> &'static str {
+    if primary {
+        "primary"
+    } else {
+        "secondary"
+    }
+}
+
+fn join(details: Vec<String>) -> Option<String> {
+    (!details.is_empty()).then(|| details.join(", "))
+}
+
+/// `Battery Level (0x2A19)`, falling back to the UUID.
+fn attr_label(uuid: &str, name: Option<&str>) -> String {
+    let short = uuid
+        .parse::<Uuid>()
+        .map(|u| short_uuid(&u))
+        .unwrap_or_else(|_| uuid.to_string());
+    match name {
+        Some(name) => format!("{name} ({short})"),
+        None => short,
+    }
+}
+
+/// A characteristic value for a diff detail; `(none)` when it wasn't read.
+fn value_label(hex: Option<&str>) -> String {
+    hex.map_or_else(|| "(none)".into(), spaced)
+}
+
+/// `0a1b` → `0a 1b`.
+fn spaced(hex: &str) -> String {
+    if hex.is_empty() {
+        return "(empty)".into();
+    }
+    hex.as_bytes()
+        .chunks(2)
+        .map(|c| String::from_utf8_lossy(c).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ─── CLI ────────────────────────────────────────────────────────────────────
+
+/// `voidlink gatt-diff OLD NEW` — print the differences between two exports.
+/// Returns `true` when they are identical.
+pub fn print_diff_cli(old: &Path, new: &Path) -> Result<bool> {
+    let old_export = GattExport::load(old)?;
+    let new_export = GattExport::load(new)?;
+    println!(
+        "--- {} ({}, {})",
+        old.display(),
+        old_export.name,
+        old_export.exported
+    );
+    println!(
+        "+++ {} ({}, {})",
+        new.display(),
+        new_export.name,
+        new_export.exported
+    );
+    let (rows, summary) = diff(&old_export, &new_export);
+    for row in &rows {
+        let indent = "  ".repeat(row.depth);
+        let mut line = format!("{} {indent}{}", row.change.marker(), row.label);
+        for extra in row.detail.iter().chain(&row.note) {
+            line.push_str("  ");
+            line.push_str(extra);
+        }
+        println!("{line}");
+    }
+    println!(
+        "{} added, {} removed, {} changed",
+        summary.added, summary.removed, summary.changed
+    );
+    if !summary.values_compared {
+        println!("Values not compared: one side is not a snapshot");
+    }
+    Ok(summary.is_empty())
+}
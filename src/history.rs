@@ -0,0 +1,641 @@
+//! Persistent per-device history (first/last seen, connection statistics).
+//!
+//! Stored as JSON under the XDG data dir (`~/.local/share/voidlink/history.json`).
+//!
+//! Design:
+//! - The UI thread owns a `HistoryStore`. Worker events are folded into an
+//!   in-memory view immediately and into a *pending delta* per device.
+//! - `flush()` takes a lock file, re-reads the on-disk database, merges the
+//!   pending deltas into it, and atomically replaces the file (write temp +
+//!   rename). Merging deltas instead of overwriting means several VoidLink
+//!   instances can share one history file without losing each other's counts.
+//! - A file that fails to parse is moved aside as
+//!   `history.json.corrupt.<unix time>` and a fresh database is started;
+//!   history is never worth crashing over.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use color_eyre::eyre::WrapErr;
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::bluetooth::types::{Address, BtEvent};
+
+/// Current on-disk format version.
+const FORMAT_VERSION: u32 = 1;
+
+/// A lock file older than this is assumed to belong to a crashed instance.
+const STALE_LOCK: Duration = Duration::from_secs(10);
+
+/// How long `flush()` waits for another instance to release the lock. The
+/// periodic flush from the UI loop doesn't wait; it retries next tick.
+const LOCK_WAIT: Duration = Duration::from_secs(2);
+
+// ─── Records ────────────────────────────────────────────────────────────────
+
+/// Everything we remember about one device. Timestamps are Unix seconds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeviceHistory {
+    /// Last known display name.
+    pub name: Option<String>,
+    pub first_seen: Option<u64>,
+    pub last_seen: Option<u64>,
+    pub last_connected: Option<u64>,
+    /// Successful connect attempts made through VoidLink.
+    pub connect_ok: u32,
+    /// Failed connect attempts made through VoidLink.
+    pub connect_failed: u32,
+    /// Sum of successful connect durations, for the average.
+    pub connect_ms_total: u64,
+}
+
+impl DeviceHistory {
+    /// Average duration of a successful connect, if any succeeded.
+    pub fn avg_connect(&self) -> Option<Duration> {
+        (self.connect_ok > 0)
+            .then(|| Duration::from_millis(self.connect_ms_total / u64::from(self.connect_ok)))
+    }
+
+    /// Fold a delta recorded by this (or another) session into `self`.
+    fn merge(&mut self, delta: &DeviceHistory) {
+        if delta.name.is_some() {
+            self.name.clone_from(&delta.name);
+        }
+        self.first_seen = min_opt(self.first_seen, delta.first_seen);
+        self.last_seen = self.last_seen.max(delta.last_seen);
+        self.last_connected = self.last_connected.max(delta.last_connected);
+        self.connect_ok = self.connect_ok.saturating_add(delta.connect_ok);
+        self.connect_failed = self.connect_failed.saturating_add(delta.connect_failed);
+        self.connect_ms_total = self.connect_ms_total.saturating_add(delta.connect_ms_total);
+    }
+}
+
+fn min_opt(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// On-disk file layout. Addresses are kept as strings so a single malformed
+/// key doesn't invalidate the whole file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct HistoryFile {
+    version: u32,
+    devices: BTreeMap<String, DeviceHistory>,
+}
+
+// ─── Store ──────────────────────────────────────────────────────────────────
+
+/// In-memory history view plus not-yet-persisted changes.
+pub struct HistoryStore {
+    path: PathBuf,
+    /// Merged view: on-disk state as of the last load/flush + pending deltas.
+    records: HashMap<Address, DeviceHistory>,
+    /// Changes recorded since the last successful flush.
+    pending: HashMap<Address, DeviceHistory>,
+    last_flush: Instant,
+}
+
+impl HistoryStore {
+    /// Load the history database from the default location. Never fails —
+    /// an unreadable or corrupt file yields an empty store.
+    pub fn load() -> Self {
+        Self::load_from(history_path())
+    }
+
+    fn load_from(path: PathBuf) -> Self {
+        let file = read_file(&path).unwrap_or_else(|e| {
+            warn!("{e:#} — starting with empty history");
+            HistoryFile::default()
+        });
+        let records = file
+            .devices
+            .into_iter()
+            .filter_map(|(addr, rec)| addr.parse().ok().map(|a| (a, rec)))
+            .collect();
+        Self {
+            path,
+            records,
+            pending: HashMap::new(),
+            last_flush: Instant::now(),
+        }
+    }
+
+    /// History for one device, if it has ever been recorded.
+    pub fn get(&self, addr: &Address) -> Option<&DeviceHistory> {
+        self.records.get(addr)
+    }
+
+    /// All recorded devices, most recently seen first.
+    pub fn all(&self) -> Vec<(Address, &DeviceHistory)> {
+        let mut all: Vec<_> = self.records.iter().map(|(a, r)| (*a, r)).collect();
+        all.sort_by_key(|(_, r)| std::cmp::Reverse(r.last_seen));
+        all
+    }
+
+    /// Fold a worker event into the history.
+    pub fn observe(&mut self, event: &BtEvent) {
+        let now = unix_now();
+        match event {
+            BtEvent::DeviceFound(info) | BtEvent::DeviceUpdated(info) => {
+                if info.last_seen.is_none() && !info.connected {
+                    return;
+                }
+                let name = info.display_name().to_string();
+                self.update(info.address, |h| {
+                    h.name = Some(name.clone());
+                    h.first_seen = Some(now);
+                    h.last_seen = Some(now);
+                    if info.connected {
+                        h.last_connected = Some(now);
+                    }
+                });
+            }
+            BtEvent::ConnectionResult {
+                address,
+                success,
+                elapsed,
+                ..
+            } => {
+                let ms = elapsed.as_millis() as u64;
+                self.update(*address, |h| {
+                    if *success {
+                        h.connect_ok = 1;
+                        h.connect_ms_total = ms;
+                        h.last_connected = Some(now);
+                    } else {
+                        h.connect_failed = 1;
+                    }
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Apply the same change to both the pending delta and the merged view.
+    /// `change` fills a blank delta; it is then merged like any other delta.
+    fn update(&mut self, addr: Address, change: impl Fn(&mut DeviceHistory)) {
+        let mut delta = DeviceHistory::default();
+        change(&mut delta);
+        self.pending.entry(addr).or_default().merge(&delta);
+        self.records.entry(addr).or_default().merge(&delta);
+    }
+
+    /// Flush if there are pending changes and `interval` has elapsed.
+    /// Called from the UI loop, so it never waits for the lock: if another
+    /// instance holds it, the flush is retried on the next call.
+    pub fn flush_if_due(&mut self, interval: Duration) {
+        if self.pending.is_empty() || self.last_flush.elapsed() < interval {
+            return;
+        }
+        match self.try_flush(Duration::ZERO) {
+            Ok(true) => {}
+            Ok(false) => debug!("History file locked by another instance; retrying"),
+            Err(e) => warn!("Failed to write device history: {e:#}"),
+        }
+    }
+
+    /// Flush, logging instead of propagating errors (used on exit, where a
+    /// failed write must not get in the way of shutting down).
+    pub fn flush_logged(&mut self) {
+        if let Err(e) = self.flush() {
+            warn!("Failed to write device history: {e:#}");
+        }
+    }
+
+    /// Merge pending changes into the on-disk database, waiting up to
+    /// `LOCK_WAIT` for another instance to release the lock.
+    pub fn flush(&mut self) -> Result<()> {
+        if self.try_flush(LOCK_WAIT)? {
+            Ok(())
+        } else {
+            Err(color_eyre::eyre::eyre!(
+                "History file is locked by another instance ({})",
+                self.path.display()
+            ))
+        }
+    }
+
+    /// Flush, waiting up to `wait` for the lock. `Ok(false)` when the lock
+    /// stayed taken; pending changes are kept for the next attempt.
+    fn try_flush(&mut self, wait: Duration) -> Result<bool> {
+        if self.pending.is_empty() {
+            self.last_flush = Instant::now();
+            return Ok(true);
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+        }
+
+        let Some(_lock) = FileLock::acquire(&self.path.with_extension("json.lock"), wait)? else {
+            return Ok(false);
+        };
+        self.last_flush = Instant::now();
+
+        // Re-read under the lock so changes from other instances survive.
+        // A read error aborts the flush: writing only this session's deltas
+        // would wipe everything else.
+        let mut file = read_file(&self.path)?;
+        for (addr, delta) in &self.pending {
+            file.devices
+                .entry(addr.to_string())
+                .or_default()
+                .merge(delta);
+        }
+        file.version = FORMAT_VERSION;
+        write_atomic(&self.path, &file)?;
+        // Only forget the deltas once they are safely on disk.
+        self.pending.clear();
+        debug!("Flushed history for {} devices", file.devices.len());
+
+        self.records = file
+            .devices
+            .into_iter()
+            .filter_map(|(addr, rec)| addr.parse().ok().map(|a| (a, rec)))
+            .collect();
+        Ok(true)
+    }
+}
+
+// ─── File helpers ───────────────────────────────────────────────────────────
+
+/// VoidLink's XDG data directory (`~/.local/share/voidlink`).
+pub fn data_dir() -> PathBuf {
+    directories::ProjectDirs::from("", "", "voidlink")
+        .map(|dirs| dirs.data_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from(".local/share/voidlink"))
+}
+
+fn history_path() -> PathBuf {
+    data_dir().join("history.json")
+}
+
+/// Read the database, treating a missing file as empty and moving a corrupt
+/// one out of the way. Other I/O errors are returned.
+fn read_file(path: &Path) -> Result<HistoryFile> {
+    let contents = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HistoryFile::default()),
+        Err(e) => return Err(e).wrap_err_with(|| format!("Cannot read {}", path.display())),
+    };
+    match serde_json::from_str::<HistoryFile>(&contents) {
+        Ok(file) => Ok(file),
+        Err(e) => {
+            let aside = corrupt_path(path);
+            warn!(
+                "Corrupt history file {}: {e} — moving it to {}",
+                path.display(),
+                aside.display()
+            );
+            let _ = fs::rename(path, &aside);
+            Ok(HistoryFile::default())
+        }
+    }
+}
+
+/// `history.json.corrupt.<unix time>`, numbered when that is taken, so an
+/// earlier corrupt copy is never overwritten.
+fn corrupt_path(path: &Path) -> PathBuf {
+    let stamp = unix_now();
+    (0..)
+        .map(|n| match n {
+            0 => path.with_extension(format!("json.corrupt.{stamp}")),
+            n => path.with_extension(format!("json.corrupt.{stamp}-{n}")),
+        })
+        .find(|aside| !aside.exists())
+        .expect("unbounded")
+}
+
+/// Write to a sibling temp file and rename over the target, so readers never
+/// observe a half-written database.
+fn write_atomic(path: &Path, file: &HistoryFile) -> Result<()> {
+    let tmp = path.with_extension(format!("json.tmp.{}", std::process::id()));
+    let json = serde_json::to_vec_pretty(file).wrap_err("Failed to serialise history")?;
+    {
+        let mut f = fs::File::create(&tmp)
+            .wrap_err_with(|| format!("Failed to create {}", tmp.display()))?;
+        f.write_all(&json)?;
+        f.sync_all()?;
+    }
+    fs::rename(&tmp, path).wrap_err_with(|| format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+/// Exclusive advisory lock implemented as a `create_new` lock file, removed
+/// on drop. Portable and needs no `unsafe`.
+struct FileLock {
+    path: PathBuf,
+}
+
+impl FileLock {
+    /// Take the lock, polling for up to `wait` while another instance holds
+    /// it. `Ok(None)` when it is still held after that.
+    fn acquire(path: &Path, wait: Duration) -> Result<Option<Self>> {
+        let started = Instant::now();
+        loop {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)
+            {
+                Ok(mut f) => {
+                    let _ = write!(f, "{}", std::process::id());
+                    return Ok(Some(Self {
+                        path: path.to_path_buf(),
+                    }));
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let stale = fs::metadata(path)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .and_then(|t| t.elapsed().ok())
+                        .is_some_and(|age| age > STALE_LOCK);
+                    if stale {
+                        if let Ok(owner) = fs::read_to_string(path) {
+                            break_stale(path, &owner);
+                            continue;
+                        }
+                    }
+                    if started.elapsed() >= wait {
+                        return Ok(None);
+                    }
+                    thread::sleep(Duration::from_millis(25));
+                }
+                Err(e) => {
+                    return Err(e).wrap_err_with(|| format!("Failed to lock {}", path.display()))
+                }
+            }
+        }
+    }
+}
+
+/// Remove a stale lock, but only if it still holds `owner`: it is first
+/// renamed aside, so when two instances race to break it the loser finds
+/// the winner's fresh lock there and puts it back.
+fn break_stale(path: &Path, owner: &str) {
+    let aside = path.with_extension(format!("lock.stale.{}", std::process::id()));
+    if fs::rename(path, &aside).is_err() {
+        return;
+    }
+    if fs::read_to_string(&aside).is_ok_and(|c| c == owner) {
+        warn!("Removed stale history lock {}", path.display());
+    } else {
+        // Fails if yet another instance has locked since; theirs stands.
+        let _ = fs::hard_link(&aside, path);
+    }
+    let _ = fs::remove_file(&aside);
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+// ─── Time helpers ───────────────────────────────────────────────────────────
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Time elapsed since a Unix timestamp (zero if it's in the future).
+pub fn since(ts: u64) -> Duration {
+    Duration::from_secs(unix_now().saturating_sub(ts))
+}
+
+/// Format a Unix timestamp as `YYYY-MM-DD HH:MM UTC`.
+pub fn format_timestamp(ts: u64) -> String {
+    let days = (ts / 86_400) as i64;
+    let rem = ts % 86_400;
+    let (y, m, d) = civil_from_days(days);
+    format!(
+        "{y:04}-{m:02}-{d:02} {:02}:{:02} UTC",
+        rem / 3600,
+        (rem % 3600) / 60
+    )
+}
+
+/// Format a Unix timestamp as RFC 3339 UTC (`YYYY-MM-DDTHH:MM:SSZ`).
+pub fn format_rfc3339(ts: u64) -> String {
+    let days = (ts / 86_400) as i64;
+    let rem = ts % 86_400;
+    let (y, m, d) = civil_from_days(days);
+    format!(
+        "{y:04}-{m:02}-{d:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        (rem % 3600) / 60,
+        rem % 60
+    )
+}
+
+/// Days since 1970-01-01 → (year, month, day). Howard Hinnant's algorithm.
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    (if m <= 2 { y + 1 } else { y }, m, d)
+}
+
+// ─── CLI ────────────────────────────────────────────────────────────────────
+
+/// `voidlink history [ADDR]` — print one device's history, or a summary of
+/// every recorded device. Returns `false` if the address is unknown.
+pub fn print_cli(addr: Option<&str>) -> Result<bool> {
+    let store = HistoryStore::load();
+
+    let Some(addr) = addr else {
+        let all = store.all();
+        if all.is_empty() {
+            println!("No device history recorded yet.");
+            return Ok(true);
+        }
+        println!(
+            "{:<19} {:<28} {:<22} CONNECTS",
+            "ADDRESS", "NAME", "LAST SEEN"
+        );
+        for (addr, h) in all {
+            println!(
+                "{:<19} {:<28} {:<22} {} ok / {} failed",
+                addr.to_string(),
+                h.name.as_deref().unwrap_or("?"),
+                h.last_seen
+                    .map(format_timestamp)
+                    .unwrap_or_else(|| "-".into()),
+                h.connect_ok,
+                h.connect_failed
+            );
+        }
+        return Ok(true);
+    };
+
+    let address: Address = addr
+        .parse()
+        .map_err(|_| color_eyre::eyre::eyre!("Invalid Bluetooth address: {addr}"))?;
+    let Some(h) = store.get(&address) else {
+        eprintln!("No history recorded for {address}");
+        return Ok(false);
+    };
+
+    let ts = |t: Option<u64>| t.map(format_timestamp).unwrap_or_else(|| "never".into());
+    println!(
+        "History for {address} ({})",
+        h.name.as_deref().unwrap_or("unknown")
+    );
+    println!("  First seen       {}", ts(h.first_seen));
+    println!("  Last seen        {}", ts(h.last_seen));
+    println!("  Last connected   {}", ts(h.last_connected));
+    println!(
+        "  Connections      {} ok, {} failed",
+        h.connect_ok, h.connect_failed
+    );
+    match h.avg_connect() {
+        Some(avg) => println!("  Avg connect time {:.1}s", avg.as_secs_f64()),
+        None => println!("  Avg connect time -"),
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connected(store: &mut HistoryStore, address: Address, success: bool) {
+        store.observe(&BtEvent::ConnectionResult {
+            address,
+            success,
+            error: None,
+            elapsed: Duration::from_millis(500),
+        });
+    }
+
+    fn on_disk(path: &Path) -> HistoryFile {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    fn corrupt_copies(dir: &Path) -> usize {
+        fs::read_dir(dir)
+            .unwrap()
+            .filter(|e| {
+                let name = e.as_ref().unwrap().file_name();
+                name.to_string_lossy().starts_with("history.json.corrupt.")
+            })
+            .count()
+    }
+
+    #[test]
+    fn instances_merge_their_deltas() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        let address: Address = "AA:BB:CC:DD:EE:FF".parse().unwrap();
+        let mut first = HistoryStore::load_from(path.clone());
+        let mut second = HistoryStore::load_from(path.clone());
+
+        connected(&mut first, address, true);
+        connected(&mut second, address, true);
+        connected(&mut second, address, false);
+        first.flush().unwrap();
+        second.flush().unwrap();
+
+        let record = &on_disk(&path).devices[&address.to_string()];
+        assert_eq!((record.connect_ok, record.connect_failed), (2, 1));
+        assert_eq!(record.avg_connect(), Some(Duration::from_millis(500)));
+        assert_eq!(second.get(&address), Some(record));
+        assert!(second.pending.is_empty());
+    }
+
+    #[test]
+    fn a_held_lock_keeps_changes_pending() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        let lock = path.with_extension("json.lock");
+        let address: Address = "AA:BB:CC:DD:EE:FF".parse().unwrap();
+        let mut store = HistoryStore::load_from(path.clone());
+        connected(&mut store, address, true);
+
+        fs::write(&lock, "1").unwrap();
+        assert!(!store.try_flush(Duration::ZERO).unwrap());
+        assert!(!store.pending.is_empty());
+        assert!(!path.exists());
+
+        fs::remove_file(&lock).unwrap();
+        assert!(store.try_flush(Duration::ZERO).unwrap());
+        assert_eq!(on_disk(&path).devices[&address.to_string()].connect_ok, 1);
+        assert!(!lock.exists());
+    }
+
+    #[test]
+    fn a_stale_lock_is_broken() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        let lock = path.with_extension("json.lock");
+        let address: Address = "AA:BB:CC:DD:EE:FF".parse().unwrap();
+        let mut store = HistoryStore::load_from(path.clone());
+        connected(&mut store, address, true);
+
+        fs::write(&lock, "1").unwrap();
+        let old = SystemTime::now() - STALE_LOCK * 2;
+        fs::File::options()
+            .write(true)
+            .open(&lock)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+        assert!(store.try_flush(Duration::ZERO).unwrap());
+        assert!(path.exists());
+        assert!(!lock.exists());
+    }
+
+    #[test]
+    fn corrupt_files_are_kept_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        let address: Address = "AA:BB:CC:DD:EE:FF".parse().unwrap();
+
+        fs::write(&path, "{ not json").unwrap();
+        let mut store = HistoryStore::load_from(path.clone());
+        assert!(store.all().is_empty());
+        assert_eq!(corrupt_copies(dir.path()), 1);
+
+        // Corrupted again behind the store's back: the flush recovers too.
+        fs::write(&path, "also not json").unwrap();
+        connected(&mut store, address, true);
+        store.flush().unwrap();
+        assert_eq!(corrupt_copies(dir.path()), 2);
+        assert_eq!(on_disk(&path).devices[&address.to_string()].connect_ok, 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn an_unreadable_file_aborts_the_flush() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        let address: Address = "AA:BB:CC:DD:EE:FF".parse().unwrap();
+        let mut store = HistoryStore::load_from(path.clone());
+        connected(&mut store, address, true);
+
+        // A directory in the file's place can't be read as a file.
+        fs::create_dir(&path).unwrap();
+        assert!(store.flush().is_err());
+        assert!(!store.pending.is_empty());
+        assert!(!path.with_extension("json.lock").exists());
+    }
+}
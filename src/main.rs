@@ -8,9 +8,18 @@
 //! The UI thread never touches D-Bus. The worker thread never touches the terminal.
 
 mod app;
+mod battery;
 mod bluetooth;
+mod cli;
 mod config;
+mod dashboard;
 mod event;
+mod gatt_browser;
+mod gatt_export;
+mod history;
+mod notify;
+mod recorder;
+mod telemetry;
 mod theme;
 mod tui;
 mod ui;
@@ -22,6 +31,7 @@ use tracing_subscriber::EnvFilter;
 
 use app::{App, AppAction};
 use bluetooth::types::BtCommand;
+use cli::Command;
 use event::Event;
 
 #[tokio::main]
@@ -36,6 +46,35 @@ async fn main() -> Result<()> {
         .with_writer(std::io::stderr) // TUI owns stdout; logs go to stderr
         .init();
 
+    // ── Non-interactive subcommands ──────────────────────────────────────
+    let record_path = match cli::parse()? {
+        Command::Tui { record } => record,
+        Command::History { address } => {
+            let found = history::print_cli(address.as_deref())?;
+            std::process::exit(if found { 0 } else { 1 });
+        }
+        Command::GattDiff { old, new } => {
+            let same = gatt_export::print_diff_cli(&old, &new)?;
+            std::process::exit(if same { 0 } else { 1 });
+        }
+        Command::BlocklistExport { path } => {
+            bluetooth::blocklist::export_cli(path.as_deref()).await?;
+            return Ok(());
+        }
+        Command::BlocklistImport { path } => {
+            let ok = bluetooth::blocklist::import_cli(&path).await?;
+            std::process::exit(if ok { 0 } else { 1 });
+        }
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            return Ok(());
+        }
+    };
+    // Reject an unusable --record path before the survey starts.
+    let record = record_path
+        .map(|path| recorder::format_for(&path).map(|format| (path, format)))
+        .transpose()?;
+
     info!("VoidLink starting");
 
     // ── Configuration ────────────────────────────────────────────────────
@@ -55,6 +94,7 @@ async fn main() -> Result<()> {
 
     // ── App state ───────────────────────────────────────────────────────
     let mut app = App::new(bt_cmd_tx.clone());
+    app.record_to = record;
     let mut events = event::EventHandler::new(bt_evt_rx);
 
     // ── Auto-scan on startup (if configured) ────────────────────────────
@@ -105,14 +145,36 @@ async fn main() -> Result<()> {
     }
 
     // ── Cleanup ─────────────────────────────────────────────────────────
-    // Stop scanning before exiting so the adapter isn't left in discovery mode.
-    if app.scanning {
-        let _ = bt_cmd_tx.try_send(BtCommand::StopScan);
-        // Give the worker a moment to process the stop command.
-        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    // Stop scanning before exiting so the adapter isn't left in discovery
+    // mode, and wait for the worker to confirm BlueZ has actually stopped.
+    if app.scanning && bt_cmd_tx.send(BtCommand::StopScan).await.is_ok() {
+        let stopped = events
+            .wait_for_scan_stop(std::time::Duration::from_secs(3))
+            .await;
+        if !stopped {
+            warn!("Timed out waiting for discovery to stop");
+        }
+    }
+
+    if let Some(history) = &mut app.history {
+        history.flush_logged();
     }
 
+    // Save the survey with --record, even if it was never exported by hand.
+    let record_result = match &app.record_to {
+        Some((path, format)) => app
+            .recorder
+            .export(path, *format)
+            .map(|()| Some(path.clone())),
+        None => Ok(None),
+    };
+
     tui::restore()?;
+    match record_result {
+        Ok(Some(path)) => eprintln!("Scan session saved to {}", path.display()),
+        Ok(None) => {}
+        Err(e) => eprintln!("Failed to save scan session: {e:#}"),
+    }
     info!("VoidLink exiting");
     Ok(())
 }
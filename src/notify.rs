@@ -0,0 +1,58 @@
+//! External notification channel.
+//!
+//! Alerts are always shown as in-app toasts; when `[notifications].command`
+//! is set they are also handed to an external program (e.g. `notify-send`).
+//! The command is spawned directly — never through a shell — so device names
+//! cannot inject arguments.
+
+use std::process::{Command, Stdio};
+
+use tracing::warn;
+
+/// Urgency hint passed to the external notifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Urgency {
+    Normal,
+    Critical,
+}
+
+impl Urgency {
+    fn as_str(self) -> &'static str {
+        match self {
+            Urgency::Normal => "normal",
+            Urgency::Critical => "critical",
+        }
+    }
+}
+
+/// Run the configured notifier, if any. Returns immediately; the child is
+/// reaped on a background thread.
+pub fn send(title: &str, body: &str, urgency: Urgency) {
+    let argv = &crate::config::get().notifications.command;
+    let Some((program, args)) = argv.split_first() else {
+        return;
+    };
+
+    let expand = |arg: &String| {
+        arg.replace("{title}", title)
+            .replace("{body}", body)
+            .replace("{urgency}", urgency.as_str())
+    };
+
+    // The TUI owns the terminal: the child must not inherit stdio.
+    let spawned = Command::new(expand(program))
+        .args(args.iter().map(expand))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+
+    match spawned {
+        Ok(mut child) => {
+            std::thread::spawn(move || {
+                let _ = child.wait();
+            });
+        }
+        Err(e) => warn!("notifications: failed to run {program}: {e}"),
+    }
+}
@@ -0,0 +1,249 @@
+//! Scan-session recorder for site surveys.
+//!
+//! While discovery is running, every over-the-air sighting is folded into an
+//! in-memory record per device (RSSI samples, first/last seen, advertised
+//! UUIDs). The session can be exported on demand as JSON (full sample data)
+//! or CSV (one summary row per device).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use serde::Serialize;
+
+use crate::bluetooth::types::{short_uuid, Address, DeviceInfo, Uuid};
+use crate::history::{self, format_rfc3339, unix_now};
+
+/// RSSI samples kept per device; later samples still update the statistics
+/// but are not stored individually.
+const MAX_SAMPLES: usize = 10_000;
+
+/// Export file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Pick a format from a file extension (`.csv` / `.json`).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Everything recorded about one device during the session.
+#[derive(Debug)]
+struct DeviceRecord {
+    name: String,
+    first_seen: u64,
+    last_seen: u64,
+    sightings: u64,
+    uuids: BTreeSet<Uuid>,
+    /// `(ms since session start, dBm)`.
+    samples: Vec<(u64, i16)>,
+    stats: RssiStats,
+}
+
+/// Running RSSI statistics (independent of the sample cap).
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct RssiStats {
+    pub count: u64,
+    pub min: Option<i16>,
+    pub max: Option<i16>,
+    pub mean: Option<f64>,
+    /// Population standard deviation.
+    pub stddev: Option<f64>,
+    #[serde(skip)]
+    sum: f64,
+    #[serde(skip)]
+    sum_sq: f64,
+}
+
+impl RssiStats {
+    fn push(&mut self, rssi: i16) {
+        let v = f64::from(rssi);
+        self.count += 1;
+        self.sum += v;
+        self.sum_sq += v * v;
+        self.min = Some(self.min.map_or(rssi, |m| m.min(rssi)));
+        self.max = Some(self.max.map_or(rssi, |m| m.max(rssi)));
+        let n = self.count as f64;
+        let mean = self.sum / n;
+        self.mean = Some(mean);
+        self.stddev = Some((self.sum_sq / n - mean * mean).max(0.0).sqrt());
+    }
+}
+
+/// In-memory record of the current scan session.
+#[derive(Debug)]
+pub struct SessionRecorder {
+    started: Instant,
+    started_unix: u64,
+    devices: BTreeMap<Address, DeviceRecord>,
+}
+
+impl SessionRecorder {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            started_unix: unix_now(),
+            devices: BTreeMap::new(),
+        }
+    }
+
+    /// Number of distinct devices recorded so far.
+    pub fn device_count(&self) -> usize {
+        self.devices.len()
+    }
+
+    /// Fold one over-the-air sighting into the session.
+    pub fn record(&mut self, info: &DeviceInfo) {
+        let now = unix_now();
+        let offset_ms = self.started.elapsed().as_millis() as u64;
+        let rec = self
+            .devices
+            .entry(info.address)
+            .or_insert_with(|| DeviceRecord {
+                name: String::new(),
+                first_seen: now,
+                last_seen: now,
+                sightings: 0,
+                uuids: BTreeSet::new(),
+                samples: Vec::new(),
+                stats: RssiStats::default(),
+            });
+        rec.name = info.display_name().to_string();
+        rec.last_seen = now;
+        rec.sightings += 1;
+        rec.uuids.extend(info.uuids.iter().copied());
+        if let Some(rssi) = info.rssi {
+            rec.stats.push(rssi);
+            if rec.samples.len() < MAX_SAMPLES {
+                rec.samples.push((offset_ms, rssi));
+            }
+        }
+    }
+
+    /// Default export location: `<data dir>/sessions/session-<time>.<ext>`.
+    pub fn default_path(&self, format: ExportFormat) -> PathBuf {
+        let stamp = format_rfc3339(self.started_unix).replace(':', "");
+        history::data_dir()
+            .join("sessions")
+            .join(format!("session-{stamp}.{}", format.extension()))
+    }
+
+    /// Write the session to `path`, creating parent directories.
+    pub fn export(&self, path: &Path, format: ExportFormat) -> Result<()> {
+        let contents = match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Json => {
+                serde_json::to_string_pretty(&self.to_export()).wrap_err("Failed to serialise")?
+            }
+        };
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("Failed to create {}", dir.display()))?;
+        }
+        fs::write(path, contents).wrap_err_with(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn to_export(&self) -> SessionExport<'_> {
+        SessionExport {
+            started: format_rfc3339(self.started_unix),
+            exported: format_rfc3339(unix_now()),
+            devices: self
+                .devices
+                .iter()
+                .map(|(addr, rec)| DeviceExport {
+                    address: addr.to_string(),
+                    name: &rec.name,
+                    first_seen: format_rfc3339(rec.first_seen),
+                    last_seen: format_rfc3339(rec.last_seen),
+                    sightings: rec.sightings,
+                    uuids: rec.uuids.iter().map(Uuid::to_string).collect(),
+                    rssi: rec.stats,
+                    samples: &rec.samples,
+                })
+                .collect(),
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "address,name,first_seen,last_seen,sightings,rssi_samples,\
+             rssi_min,rssi_max,rssi_mean,rssi_stddev,uuids\n",
+        );
+        let opt = |v: Option<i16>| v.map(|v| v.to_string()).unwrap_or_default();
+        let optf = |v: Option<f64>| v.map(|v| format!("{v:.1}")).unwrap_or_default();
+        for (addr, rec) in &self.devices {
+            let uuids: Vec<String> = rec.uuids.iter().map(short_uuid).collect();
+            let _ = writeln!(
+                out,
+                "{addr},{},{},{},{},{},{},{},{},{},{}",
+                csv_field(&rec.name),
+                format_rfc3339(rec.first_seen),
+                format_rfc3339(rec.last_seen),
+                rec.sightings,
+                rec.stats.count,
+                opt(rec.stats.min),
+                opt(rec.stats.max),
+                optf(rec.stats.mean),
+                optf(rec.stats.stddev),
+                csv_field(&uuids.join(";")),
+            );
+        }
+        out
+    }
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or newline.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[derive(Serialize)]
+struct SessionExport<'a> {
+    started: String,
+    exported: String,
+    devices: Vec<DeviceExport<'a>>,
+}
+
+#[derive(Serialize)]
+struct DeviceExport<'a> {
+    address: String,
+    name: &'a str,
+    first_seen: String,
+    last_seen: String,
+    sightings: u64,
+    uuids: Vec<String>,
+    rssi: RssiStats,
+    /// `[ms since session start, dBm]` pairs.
+    samples: &'a [(u64, i16)],
+}
+
+/// Resolve the `--record` path's format, rejecting unknown extensions early
+/// so a typo doesn't cost a whole survey.
+pub fn format_for(path: &Path) -> Result<ExportFormat> {
+    ExportFormat::from_path(path)
+        .ok_or_else(|| eyre!("{}: expected a .csv or .json file", path.display()))
+}
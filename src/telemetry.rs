@@ -0,0 +1,129 @@
+//! Per-device signal and battery time series for the detail panel charts.
+//!
+//! Samples are recorded from worker snapshots on the UI thread. Each series is
+//! bounded: RSSI keeps only the configured window, battery keeps the whole
+//! session up to [`MAX_SAMPLES`] changes.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::bluetooth::types::{Address, DeviceInfo};
+
+/// Hard cap on samples per series, whatever the time window.
+const MAX_SAMPLES: usize = 1024;
+
+/// A bounded series of value changes. `None` marks a gap (e.g. the device
+/// went out of range and BlueZ cleared its RSSI).
+#[derive(Debug, Default)]
+pub struct Series<T> {
+    samples: VecDeque<(Instant, Option<T>)>,
+}
+
+impl<T: Copy + PartialEq> Series<T> {
+    /// Record `value` if it differs from the latest sample.
+    fn push(&mut self, at: Instant, value: Option<T>) {
+        match self.samples.back() {
+            Some(&(_, last)) if last == value => return,
+            None if value.is_none() => return,
+            _ => {}
+        }
+        if self.samples.len() == MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((at, value));
+    }
+
+    /// Drop samples older than `window`, keeping the one in effect at the
+    /// window start so the chart doesn't begin with a gap.
+    fn trim(&mut self, now: Instant, window: Duration) {
+        let Some(start) = now.checked_sub(window) else {
+            return;
+        };
+        while self.samples.len() > 1 && self.samples[1].0 <= start {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Time of the first recorded sample.
+    pub fn start(&self) -> Option<Instant> {
+        self.samples.front().map(|&(t, _)| t)
+    }
+
+    /// Non-gap values in the series.
+    pub fn values(&self) -> impl Iterator<Item = T> + '_ {
+        self.samples.iter().filter_map(|&(_, v)| v)
+    }
+
+    /// First non-gap sample, with its timestamp.
+    pub fn first_value(&self) -> Option<(Instant, T)> {
+        self.samples.iter().find_map(|&(t, v)| v.map(|v| (t, v)))
+    }
+
+    /// Resample into `buckets` equal slices covering `from..=now`. Each
+    /// bucket holds the value in effect at its end (step interpolation);
+    /// buckets before the first sample are `None`.
+    pub fn resample(&self, from: Instant, now: Instant, buckets: usize) -> Vec<Option<T>> {
+        let span = now.saturating_duration_since(from);
+        let mut out = Vec::with_capacity(buckets);
+        let mut iter = self.samples.iter().peekable();
+        let mut current = None;
+        for i in 1..=buckets {
+            let edge = from + span.mul_f64(i as f64 / buckets.max(1) as f64);
+            while let Some(&&(t, v)) = iter.peek() {
+                if t > edge {
+                    break;
+                }
+                current = v;
+                iter.next();
+            }
+            out.push(current);
+        }
+        out
+    }
+}
+
+/// Signal and battery history for one device.
+#[derive(Debug, Default)]
+pub struct DeviceSeries {
+    pub rssi: Series<i16>,
+    pub battery: Series<u8>,
+}
+
+/// Time series for every device seen this session.
+#[derive(Debug, Default)]
+pub struct Telemetry {
+    devices: HashMap<Address, DeviceSeries>,
+}
+
+impl Telemetry {
+    /// Record the RSSI and battery level carried by a device snapshot.
+    pub fn record(&mut self, info: &DeviceInfo) {
+        let now = Instant::now();
+        let series = self.devices.entry(info.address).or_default();
+        series.rssi.push(now, info.rssi);
+        series.battery.push(now, info.battery);
+        series.rssi.trim(now, rssi_window());
+    }
+
+    pub fn get(&self, address: &Address) -> Option<&DeviceSeries> {
+        self.devices.get(address)
+    }
+
+    pub fn forget(&mut self, address: &Address) {
+        self.devices.remove(address);
+    }
+
+    /// Drop series for devices no longer in the list.
+    pub fn retain(&mut self, mut keep: impl FnMut(&Address) -> bool) {
+        self.devices.retain(|addr, _| keep(addr));
+    }
+}
+
+/// How far back the signal chart reaches.
+pub fn rssi_window() -> Duration {
+    Duration::from_secs(crate::config::get().general.signal_history_secs)
+}
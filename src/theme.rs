@@ -11,6 +11,7 @@
 
 use ratatui::style::{Color, Modifier, Style};
 
+use crate::bluetooth::kind::DeviceKind;
 use crate::config;
 
 // ─── Config-backed palette accessors ────────────────────────────────────────
@@ -142,38 +143,30 @@ pub fn rssi_bar(rssi: Option<i16>) -> &'static str {
 
 // ─── Device type → Nerd Font icon mapping ───────────────────────────────────
 
-/// Map a BlueZ `icon` property (freedesktop icon name) to a Nerd Font glyph.
-pub fn device_icon(icon: Option<&str>, class: Option<u32>) -> &'static str {
-    if let Some(icon) = icon {
-        match icon {
-            s if s.contains("audio-headset") || s.contains("audio-headphones") => "\u{f025}",
-            s if s.contains("audio-card") || s.contains("speaker") => "󰓃",
-            s if s.contains("phone") => "\u{f095}",
-            s if s.contains("computer") => "󰍽",
-            s if s.contains("input-keyboard") => "󰌌",
-            s if s.contains("input-mouse") => "󰍽",
-            s if s.contains("input-gaming") => "󰊗",
-            s if s.contains("input-tablet") => "󰓶",
-            s if s.contains("camera") => "󰄀",
-            s if s.contains("printer") => "󰐪",
-            s if s.contains("network") => "󰈀",
-            s if s.contains("video-display") || s.contains("monitor") => "󰍹",
-            _ => "󰂯",
-        }
-    } else if let Some(cls) = class {
-        let major = (cls >> 8) & 0x1F;
-        match major {
-            1 => "󰍽",
-            2 => "\u{f095}",
-            3 => "󰈀",
-            4 => "󰓃",
-            5 => "󰌌",
-            6 => "󰄀",
-            7 => "󰌚",
-            _ => "󰂯",
-        }
-    } else {
-        "󰂯"
+/// Nerd Font glyph for a device kind.
+pub fn device_icon(kind: DeviceKind) -> &'static str {
+    match kind {
+        DeviceKind::Headset => "\u{f025}",
+        DeviceKind::Speaker => "󰓃",
+        DeviceKind::Microphone => "󰍬",
+        DeviceKind::CarAudio => "󰄋",
+        DeviceKind::Keyboard => "󰌌",
+        DeviceKind::Mouse | DeviceKind::Computer => "󰍽",
+        DeviceKind::Gamepad => "󰊗",
+        DeviceKind::Digitizer | DeviceKind::Tablet => "󰓶",
+        DeviceKind::Input => "󰌌",
+        DeviceKind::Phone => "\u{f095}",
+        DeviceKind::Laptop => "󰌢",
+        DeviceKind::Watch => "󰖉",
+        DeviceKind::Wearable => "󰌚",
+        DeviceKind::Health => "󰋑",
+        DeviceKind::Camera => "󰄀",
+        DeviceKind::Printer => "󰐪",
+        DeviceKind::Display => "󰍹",
+        DeviceKind::Network => "󰈀",
+        DeviceKind::Tag => "󰍎",
+        DeviceKind::Toy => "󰚩",
+        DeviceKind::Unknown => "󰂯",
     }
 }
 
@@ -233,5 +226,7 @@ pub fn sort_icon(mode: crate::config::SortMode) -> &'static str {
         crate::config::SortMode::Name => "󰈏",
         crate::config::SortMode::Rssi => "󰤨",
         crate::config::SortMode::Address => "󰩟",
+        crate::config::SortMode::LastSeen => "󰥔",
+        crate::config::SortMode::Type => "󰠱",
     }
 }
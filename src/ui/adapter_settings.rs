@@ -0,0 +1,143 @@
+//! Adapter settings view — alias, discoverable / pairable flags with their
+//! timeouts, and read-only controller details.
+
+use std::time::Instant;
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::{AdapterField, AdapterForm, App};
+use crate::bluetooth::assigned_numbers;
+use crate::bluetooth::types::{short_uuid, AddressType};
+use crate::theme;
+
+/// Render the adapter settings view.
+pub fn render(frame: &mut Frame, app: &App, form: &AdapterForm) {
+    let area = super::popup::centered_rect_percent(64, 20, frame.area());
+    frame.render_widget(Clear, area);
+
+    let adapter = &app.adapter;
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" 󰂯 Adapter {} ", adapter.name),
+            theme::title(),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::deep_purple()));
+
+    let mut lines = vec![Line::from("")];
+
+    for field in AdapterField::ALL {
+        let (label, value) = match field {
+            AdapterField::Alias => {
+                let mut text = form.alias_input.clone();
+                if form.field == AdapterField::Alias {
+                    text.push('█');
+                }
+                ("Alias", text)
+            }
+            AdapterField::Discoverable => (
+                "Discoverable",
+                flag(adapter.discoverable, app.discoverable_until),
+            ),
+            AdapterField::DiscoverableTimeout => {
+                ("  timeout", timeout(adapter.discoverable_timeout))
+            }
+            AdapterField::Pairable => ("Pairable", flag(adapter.pairable, app.pairable_until)),
+            AdapterField::PairableTimeout => ("  timeout", timeout(adapter.pairable_timeout)),
+        };
+
+        let focused = form.field == field;
+        let marker = if focused { "▸ " } else { "  " };
+        let value_style = if focused {
+            Style::default()
+                .fg(theme::cyan())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            theme::list_item()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {marker}"), theme::title()),
+            Span::styled(format!("{label:<16}"), theme::dim()),
+            Span::styled(value, value_style),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    let address = match (adapter.address, adapter.address_type) {
+        (Some(addr), Some(AddressType::LeRandom)) => format!("{addr} (random)"),
+        (Some(addr), _) => format!("{addr} (public)"),
+        (None, _) => "unknown".into(),
+    };
+    let uuids = adapter
+        .uuids
+        .iter()
+        .map(|u| {
+            assigned_numbers::uuid_name(u)
+                .map(str::to_string)
+                .unwrap_or_else(|| short_uuid(u))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let info = [
+        ("Address", address),
+        (
+            "Class",
+            adapter
+                .class
+                .map(|c| format!("0x{c:06x}"))
+                .unwrap_or_else(|| "—".into()),
+        ),
+        (
+            "Modalias",
+            adapter.modalias.clone().unwrap_or_else(|| "—".into()),
+        ),
+        ("Roles", or_dash(adapter.roles.join(", "))),
+        ("UUIDs", or_dash(uuids)),
+    ];
+    for (label, value) in info {
+        lines.push(Line::from(vec![
+            Span::styled(format!("    {label:<16}"), theme::dim()),
+            Span::styled(value, theme::list_item()),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  ↑/↓ field  ←/→ ␣ change  ⏎ apply alias  Esc close",
+        theme::dim(),
+    )));
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+/// `on · 2m31s left`, `on` or `off`.
+fn flag(on: bool, until: Option<Instant>) -> String {
+    match (on, until) {
+        (true, Some(until)) => format!("on · {} left", super::format_countdown(until)),
+        (true, None) => "on".into(),
+        (false, _) => "off".into(),
+    }
+}
+
+fn timeout(secs: u32) -> String {
+    match secs {
+        0 => "never".into(),
+        secs => super::format_duration(std::time::Duration::from_secs(secs.into())),
+    }
+}
+
+fn or_dash(s: String) -> String {
+    if s.is_empty() {
+        "—".into()
+    } else {
+        s
+    }
+}
@@ -0,0 +1,122 @@
+//! Admin policy view — the adapter's service allowlist, the devices it
+//! affects, and presets to apply.
+
+use std::collections::HashSet;
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::bluetooth::assigned_numbers;
+use crate::bluetooth::types::{short_uuid, Uuid};
+use crate::theme;
+
+/// Render the admin policy view with row `cursor` selected.
+pub fn render(frame: &mut Frame, app: &App, cursor: usize) {
+    let presets = &crate::config::get().admin_policy.presets;
+    let area = super::popup::centered_rect_percent(64, presets.len() as u16 + 14, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(" 󰒃 Admin Policy ", theme::title()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::deep_purple()));
+
+    let mut lines = vec![Line::from("")];
+    let allowlist = app.admin_policy.as_ref().and_then(|p| p.allowlist.as_ref());
+    let label = |text: &'static str| Span::styled(format!("  {text:<14}"), theme::dim());
+
+    match (&app.admin_policy, allowlist) {
+        (None, _) => lines.push(Line::from(Span::styled("  Reading policy…", theme::dim()))),
+        (Some(_), None) => lines.push(Line::from(Span::styled(
+            "  Not supported: bluetoothd has no admin policy plugin",
+            theme::error(),
+        ))),
+        (Some(policy), Some(allowlist)) => {
+            let allowed = if allowlist.is_empty() {
+                Span::styled("all services", theme::connected())
+            } else {
+                Span::styled(
+                    allowlist
+                        .iter()
+                        .map(uuid_label)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    Style::default().fg(theme::amber()),
+                )
+            };
+            lines.push(Line::from(vec![label("Allowed"), allowed]));
+
+            let mut affected: Vec<String> = policy
+                .affected
+                .iter()
+                .map(|addr| {
+                    app.devices
+                        .iter()
+                        .find(|d| d.address == *addr)
+                        .map(|d| d.display_name().to_string())
+                        .unwrap_or_else(|| addr.to_string())
+                })
+                .collect();
+            affected.sort();
+            let affected = if affected.is_empty() {
+                Span::styled("none", theme::dim())
+            } else {
+                Span::styled(affected.join(", "), theme::error())
+            };
+            lines.push(Line::from(vec![label("Affected"), affected]));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("  Presets", theme::title())));
+    let current: Option<HashSet<&Uuid>> = allowlist.map(|a| a.iter().collect());
+    let rows = std::iter::once(("Allow all services", &[][..])).chain(
+        presets
+            .iter()
+            .map(|p| (p.name.as_str(), p.uuids.as_slice())),
+    );
+    for (i, (name, uuids)) in rows.enumerate() {
+        let focused = i == cursor;
+        let marker = if focused { "▸ " } else { "  " };
+        let style = if focused {
+            Style::default()
+                .fg(theme::cyan())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            theme::list_item()
+        };
+        let active = current
+            .as_ref()
+            .is_some_and(|c| *c == uuids.iter().collect::<HashSet<_>>());
+        let mut spans = vec![
+            Span::styled(format!("  {marker}"), theme::title()),
+            Span::styled(format!("{name:<28}"), style),
+        ];
+        if active {
+            spans.push(Span::styled("● active", theme::connected()));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  ⏎ apply  R refresh  Esc close",
+        theme::dim(),
+    )));
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+fn uuid_label(uuid: &Uuid) -> String {
+    assigned_numbers::uuid_name(uuid)
+        .map(str::to_string)
+        .unwrap_or_else(|| short_uuid(uuid))
+}
@@ -1,12 +1,20 @@
 //! Detail panel for the currently selected device.
 
+use std::time::{Duration, Instant};
+
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::Style;
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Gauge, Paragraph};
+use ratatui::widgets::{Block, Borders, Gauge, Paragraph, Sparkline};
 use ratatui::Frame;
 
 use crate::app::App;
+use crate::bluetooth::assigned_numbers;
+use crate::bluetooth::types::{
+    short_uuid, DeviceInfo, DeviceSet, MediaPlayer, MediaTransport, PlaybackStatus, MAX_VOLUME,
+};
+use crate::dashboard::Dashboard;
+use crate::history::{self, DeviceHistory};
 use crate::theme;
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
@@ -28,28 +36,56 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    let profile_lines = app
+        .dashboards
+        .get(&device.address)
+        .map(dashboard_lines)
+        .unwrap_or_default();
+    let wake_height = u16::from(device.wake_allowed.is_some());
+    let media_lines = app
+        .media_player(&device.address)
+        .map(media_lines)
+        .unwrap_or_default();
+    let transport_lines: Vec<Line> = app
+        .audio_transports(&device.address)
+        .flat_map(transport_lines)
+        .collect();
+    let set_lines = app
+        .set_of(&device.address)
+        .map(|set| set_lines(app, set))
+        .unwrap_or_default();
+
     // Split inner area into named rows.
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // 0: name
-            Constraint::Length(1), // 1: address
-            Constraint::Length(1), // 2: spacer
-            Constraint::Length(1), // 3: status badges
-            Constraint::Length(1), // 4: spacer
-            Constraint::Length(1), // 5: RSSI label
-            Constraint::Length(1), // 6: RSSI gauge
-            Constraint::Length(1), // 7: spacer
-            Constraint::Length(1), // 8: battery label
-            Constraint::Length(1), // 9: battery gauge
-            Constraint::Length(1), // 10: spacer
-            Constraint::Length(1), // 11: device class
-            Constraint::Length(1), // 12: icon type
-            Constraint::Min(0),   // 13: rest
+            Constraint::Length(1),                            // 0: name
+            Constraint::Length(1),                            // 1: address
+            Constraint::Length(1),                            // 2: spacer
+            Constraint::Length(1),                            // 3: status badges
+            Constraint::Length(1),                            // 4: spacer
+            Constraint::Length(1),                            // 5: RSSI label
+            Constraint::Length(3),                            // 6: RSSI chart
+            Constraint::Length(1),                            // 7: spacer
+            Constraint::Length(1),                            // 8: battery label
+            Constraint::Length(3),                            // 9: battery chart
+            Constraint::Length(1),                            // 10: spacer
+            Constraint::Length(1),                            // 11: device class
+            Constraint::Length(1),                            // 12: class services
+            Constraint::Length(1),                            // 13: icon type
+            Constraint::Length(1),                            // 14: last seen
+            Constraint::Length(wake_height),                  // 15: wake from suspend
+            Constraint::Length(1),                            // 16: spacer
+            Constraint::Length(4),                            // 17: history
+            Constraint::Length(profile_lines.len() as u16),   // 18: profiles
+            Constraint::Length(media_lines.len() as u16),     // 19: media player
+            Constraint::Length(transport_lines.len() as u16), // 20: audio transports
+            Constraint::Length(set_lines.len() as u16),       // 21: coordinated set
+            Constraint::Min(0),                               // 22: advertisement (scrollable)
         ])
         .split(inner);
 
-    // Safe: we have exactly 14 constraints, so 14 rects.
+    // Safe: we have exactly 20 constraints, so 20 rects.
     // Use .get() for every access — silently skip if terminal is too small.
     macro_rules! row {
         ($idx:expr) => {
@@ -61,7 +97,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     // ── Name ────────────────────────────────────────────────────────────
-    let icon = theme::device_icon(device.icon.as_deref(), device.class);
+    let icon = theme::device_icon(device.kind);
     let name_line = Line::from(vec![
         Span::styled(format!("  {icon} "), theme::title()),
         Span::styled(device.display_name(), theme::title()),
@@ -92,25 +128,67 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     } else {
         badge_spans.push(Span::styled("  Not Trusted", theme::dim()));
     }
+    if device.blocked {
+        badge_spans.push(Span::styled("  ⊘ Blocked", theme::error()));
+    }
+    let restricted = app
+        .admin_policy
+        .as_ref()
+        .is_some_and(|p| p.affected.contains(&device.address));
+    if restricted {
+        badge_spans.push(Span::styled(
+            "  ⚠ Restricted by policy",
+            Style::default().fg(theme::amber()),
+        ));
+    }
     frame.render_widget(Paragraph::new(Line::from(badge_spans)), row!(3));
 
     // ── RSSI ────────────────────────────────────────────────────────────
+    let now = Instant::now();
+    let series = app.telemetry.get(&device.address);
     let (rssi_icon, rssi_color) = theme::rssi_display(device.rssi);
     let rssi_text = match device.rssi {
-        Some(r) => format!("  {rssi_icon} Signal: {r} dBm  {}", theme::rssi_bar(device.rssi)),
+        Some(r) => format!(
+            "  {rssi_icon} Signal: {r} dBm  {}",
+            theme::rssi_bar(device.rssi)
+        ),
         None => format!("  {rssi_icon} Signal: N/A"),
     };
-    let rssi_line = Line::from(Span::styled(rssi_text, Style::default().fg(rssi_color)));
-    frame.render_widget(Paragraph::new(rssi_line), row!(5));
+    let mut rssi_spans = vec![Span::styled(rssi_text, Style::default().fg(rssi_color))];
+    let rssi_range = series.and_then(|s| Some((s.rssi.values().min()?, s.rssi.values().max()?)));
+    if let Some((min, max)) = rssi_range {
+        rssi_spans.push(Span::styled(format!("  {min}…{max} dBm"), theme::dim()));
+    }
+    frame.render_widget(Paragraph::new(Line::from(rssi_spans)), row!(5));
 
-    // RSSI gauge (map -100..0 dBm to 0..100%).
-    if let Some(rssi) = device.rssi {
-        let pct = ((rssi.max(-100) + 100) as f64 / 100.0 * 100.0).clamp(0.0, 100.0) as u16;
-        let gauge = Gauge::default()
-            .gauge_style(Style::default().fg(rssi_color))
-            .percent(pct)
-            .label(format!("{pct}%"));
-        frame.render_widget(gauge, row!(6));
+    // RSSI chart over the configured window (map -100..0 dBm to 0..100).
+    let rssi_area = inset(row!(6));
+    match series.filter(|s| !s.rssi.is_empty()) {
+        Some(s) => {
+            let from = now
+                .checked_sub(crate::telemetry::rssi_window())
+                .unwrap_or(now);
+            let data = s
+                .rssi
+                .resample(from, now, rssi_area.width as usize)
+                .into_iter()
+                .map(|v| v.map(|r| (r.clamp(-100, 0) + 100) as u64));
+            let chart = Sparkline::default()
+                .data(data)
+                .max(100)
+                .style(Style::default().fg(rssi_color));
+            frame.render_widget(chart, rssi_area);
+        }
+        None => {
+            if let Some(rssi) = device.rssi {
+                let pct = (rssi.clamp(-100, 0) + 100) as u16;
+                let gauge = Gauge::default()
+                    .gauge_style(Style::default().fg(rssi_color))
+                    .percent(pct)
+                    .label(format!("{pct}%"));
+                frame.render_widget(gauge, first_line(rssi_area));
+            }
+        }
     }
 
     // ── Battery ─────────────────────────────────────────────────────────
@@ -119,31 +197,532 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         Some(pct) => format!("  {bat_icon} Battery: {pct}%"),
         None => format!("  {bat_icon} Battery: N/A"),
     };
-    let bat_line = Line::from(Span::styled(bat_text, Style::default().fg(bat_color)));
-    frame.render_widget(Paragraph::new(bat_line), row!(8));
+    let mut bat_spans = vec![Span::styled(bat_text, Style::default().fg(bat_color))];
+    // Net change since the first reading this session.
+    let drain = series
+        .and_then(|s| s.battery.first_value())
+        .zip(device.battery)
+        .filter(|((_, first), current)| first != current);
+    if let Some(((since, first), current)) = drain {
+        let delta = i16::from(current) - i16::from(first);
+        bat_spans.push(Span::styled(
+            format!(
+                "  {delta:+}% in {}",
+                super::format_duration(now.saturating_duration_since(since))
+            ),
+            theme::dim(),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(bat_spans)), row!(8));
 
-    if let Some(pct) = device.battery {
-        let gauge = Gauge::default()
-            .gauge_style(Style::default().fg(bat_color))
-            .percent(pct as u16)
-            .label(format!("{pct}%"));
-        frame.render_widget(gauge, row!(9));
+    // Battery chart over the whole session, once the level has changed.
+    let bat_area = inset(row!(9));
+    let bat_chart = series
+        .filter(|s| s.battery.values().nth(1).is_some())
+        .and_then(|s| Some((s, s.battery.start()?)));
+    match bat_chart {
+        Some((s, from)) => {
+            let data = s
+                .battery
+                .resample(from, now, bat_area.width as usize)
+                .into_iter()
+                .map(|v| v.map(u64::from));
+            let chart = Sparkline::default()
+                .data(data)
+                .max(100)
+                .style(Style::default().fg(bat_color));
+            frame.render_widget(chart, bat_area);
+        }
+        None => {
+            if let Some(pct) = device.battery {
+                let gauge = Gauge::default()
+                    .gauge_style(Style::default().fg(bat_color))
+                    .percent(pct as u16)
+                    .label(format!("{pct}%"));
+                frame.render_widget(gauge, first_line(bat_area));
+            }
+        }
     }
 
     // ── Device class / icon type ────────────────────────────────────────
-    if let Some(class) = device.class {
+    if let Some(class) = device.device_class() {
         let class_line = Line::from(vec![
             Span::styled("  Class:   ", theme::dim()),
-            Span::styled(format!("0x{class:06X}"), theme::list_item()),
+            Span::styled(class.to_string(), theme::list_item()),
+            Span::styled(format!(" (0x{:06X})", class.0), theme::dim()),
         ]);
         frame.render_widget(Paragraph::new(class_line), row!(11));
+
+        let services = class.services();
+        if !services.is_empty() {
+            let services_line = Line::from(vec![
+                Span::styled("  Offers:  ", theme::dim()),
+                Span::styled(services.join(", "), theme::list_item()),
+            ]);
+            frame.render_widget(Paragraph::new(services_line), row!(12));
+        }
     }
 
+    let mut type_spans = vec![
+        Span::styled("  Type:    ", theme::dim()),
+        Span::styled(device.kind.label(), theme::list_item()),
+        Span::styled(
+            format!(" · {}", device.kind.category().label()),
+            theme::dim(),
+        ),
+    ];
     if let Some(ref icon_name) = device.icon {
-        let icon_line = Line::from(vec![
-            Span::styled("  Type:    ", theme::dim()),
-            Span::styled(icon_name.as_str(), theme::list_item()),
-        ]);
-        frame.render_widget(Paragraph::new(icon_line), row!(12));
+        type_spans.push(Span::styled(format!(" ({icon_name})"), theme::dim()));
+    }
+    frame.render_widget(Paragraph::new(Line::from(type_spans)), row!(13));
+
+    // ── Last seen ───────────────────────────────────────────────────────
+    let seen_line = Line::from(vec![
+        Span::styled("  Seen:    ", theme::dim()),
+        Span::styled(super::format_age(device.age()), theme::list_item()),
+    ]);
+    frame.render_widget(Paragraph::new(seen_line), row!(14));
+
+    // ── Wake from suspend ───────────────────────────────────────────────
+    if let Some(allowed) = device.wake_allowed {
+        let (text, style) = if allowed {
+            ("allowed", theme::connected())
+        } else {
+            ("not allowed", theme::dim())
+        };
+        let mut spans = vec![
+            Span::styled("  Wake:    ", theme::dim()),
+            Span::styled(text, style),
+        ];
+        if let Some(key) = crate::config::key_label(crate::config::get().keys.wake_allowed) {
+            spans.push(Span::styled(format!(" ({key} toggles)"), theme::dim()));
+        }
+        let wake_line = Line::from(spans);
+        frame.render_widget(Paragraph::new(wake_line), row!(15));
+    }
+
+    // ── History ─────────────────────────────────────────────────────────
+    if let Some(store) = &app.history {
+        let lines = history_lines(store.get(&device.address));
+        frame.render_widget(Paragraph::new(lines), row!(17));
+    }
+
+    // ── Standard profiles ───────────────────────────────────────────────
+    if !profile_lines.is_empty() {
+        frame.render_widget(Paragraph::new(profile_lines), row!(18));
+    }
+
+    // ── Media player ────────────────────────────────────────────────────
+    if !media_lines.is_empty() {
+        frame.render_widget(Paragraph::new(media_lines), row!(19));
+    }
+
+    // ── Audio transports ────────────────────────────────────────────────
+    if !transport_lines.is_empty() {
+        frame.render_widget(Paragraph::new(transport_lines), row!(20));
+    }
+
+    // ── Coordinated set ─────────────────────────────────────────────────
+    if !set_lines.is_empty() {
+        frame.render_widget(Paragraph::new(set_lines), row!(21));
+    }
+
+    // ── Advertisement (scrollable) ──────────────────────────────────────
+    let adv_area = row!(22);
+    let lines = advertisement_lines(device);
+    // One line of the area goes to the section header.
+    let max_scroll = (lines.len() as u16).saturating_sub(adv_area.height.saturating_sub(1));
+    app.detail_scroll_max.set(max_scroll);
+    let offset = app.detail_scroll(&device.address).min(max_scroll);
+    let mut title = String::from("  ── Advertisement ──");
+    if max_scroll > 0 {
+        title.push_str(&format!(" {}/{} (J/K)", offset + 1, max_scroll + 1));
+    }
+    let mut body = vec![Line::from(Span::styled(title, theme::title()))];
+    body.extend(lines.into_iter().skip(offset as usize));
+    frame.render_widget(Paragraph::new(body), adv_area);
+}
+
+/// Track metadata and playback state of a remote player, with a leading
+/// spacer line.
+fn media_lines(player: &MediaPlayer) -> Vec<Line<'static>> {
+    let mut title = String::from("  ── Media ──");
+    if let Some(name) = &player.name {
+        title.push_str(&format!(" {name}"));
+    }
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(title, theme::title())),
+    ];
+    let track = &player.track;
+    for (label, value) in [
+        ("Title:  ", &track.title),
+        ("Artist: ", &track.artist),
+        ("Album:  ", &track.album),
+    ] {
+        if let Some(value) = value {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {label} "), theme::dim()),
+                Span::styled(value.clone(), theme::list_item()),
+            ]));
+        }
+    }
+
+    let (icon, style) = match player.status {
+        PlaybackStatus::Playing => ("▶", theme::connected()),
+        PlaybackStatus::Paused => ("⏸", theme::paired()),
+        PlaybackStatus::Seeking => ("⇄", theme::paired()),
+        PlaybackStatus::Stopped => ("■", theme::dim()),
+        PlaybackStatus::Error => ("!", theme::error()),
+    };
+    let mut spans = vec![Span::styled(
+        format!("  {icon} {:<8}", player.status.label()),
+        style,
+    )];
+    if let Some(position) = player.position_now() {
+        match track.duration {
+            Some(duration) => {
+                const WIDTH: usize = 16;
+                let done = (position.as_secs_f64() / duration.as_secs_f64() * WIDTH as f64)
+                    .round()
+                    .min(WIDTH as f64) as usize;
+                spans.push(Span::styled("━".repeat(done), style));
+                spans.push(Span::styled("─".repeat(WIDTH - done), theme::dim()));
+                spans.push(Span::styled(
+                    format!(" {} / {}", track_time(position), track_time(duration)),
+                    theme::list_item(),
+                ));
+            }
+            None => spans.push(Span::styled(track_time(position), theme::list_item())),
+        }
+    }
+    lines.push(Line::from(spans));
+    lines.push(Line::from(Span::styled(
+        "  m play/pause · . next · , previous",
+        theme::dim(),
+    )));
+    lines
+}
+
+/// Profile, state, negotiated codec and volume of one audio transport, with
+/// a leading spacer line.
+fn transport_lines(transport: &MediaTransport) -> Vec<Line<'static>> {
+    let sep = || Span::styled(" · ", theme::dim());
+    let state_style = if transport.state == "active" {
+        theme::connected()
+    } else {
+        theme::dim()
+    };
+    let mut header = vec![
+        Span::styled(format!("  ── {} ──", transport.profile()), theme::title()),
+        Span::raw(" "),
+        Span::styled(transport.state.clone(), state_style),
+    ];
+    if let Some(delay) = transport.delay {
+        header.push(sep());
+        header.push(Span::styled(
+            format!("delay {:.1} ms", delay.as_secs_f64() * 1000.0),
+            theme::dim(),
+        ));
+    }
+    let mut codec = vec![
+        Span::styled("  Codec:   ", theme::dim()),
+        Span::styled(transport.codec.name.clone(), theme::list_item()),
+    ];
+    for detail in &transport.codec.details {
+        codec.push(sep());
+        codec.push(Span::styled(detail.clone(), theme::dim()));
+    }
+    let mut lines = vec![Line::from(""), Line::from(header), Line::from(codec)];
+
+    if let Some(volume) = transport.volume {
+        const WIDTH: usize = 16;
+        let filled = usize::from(volume) * WIDTH / usize::from(MAX_VOLUME);
+        lines.push(Line::from(vec![
+            Span::styled("  Volume:  ", theme::dim()),
+            Span::styled("━".repeat(filled), theme::connected()),
+            Span::styled("─".repeat(WIDTH - filled), theme::dim()),
+            Span::styled(
+                format!(" {}%", u32::from(volume) * 100 / u32::from(MAX_VOLUME)),
+                theme::list_item(),
+            ),
+            Span::styled(" (+/-)", theme::dim()),
+        ]));
     }
+    lines
+}
+
+/// Track clock: `3:07`, or `1:02:45` past an hour.
+fn track_time(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Per-member status of a coordinated set, with a leading spacer line.
+fn set_lines(app: &App, set: &DeviceSet) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "  ── Set of {} ──",
+                set.size.map_or(set.members.len(), usize::from)
+            ),
+            theme::title(),
+        )),
+    ];
+    for addr in &set.members {
+        let member = app.devices.iter().find(|d| d.address == *addr);
+        let name = member.map_or_else(|| addr.to_string(), |d| d.display_name().to_string());
+        let (status, style) = match member {
+            Some(d) if d.connected => ("connected", theme::connected()),
+            Some(_) => ("disconnected", theme::dim()),
+            None => ("not seen", theme::dim()),
+        };
+        let mut spans = vec![
+            Span::styled(format!("  {name:<20} "), theme::list_item()),
+            Span::styled(status, style),
+        ];
+        if let Some(pct) = member.and_then(|d| d.battery) {
+            let (icon, color) = theme::battery_display(Some(pct));
+            spans.push(Span::styled(
+                format!("  {icon} {pct}%"),
+                Style::default().fg(color),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// Battery / Device Information / Heart Rate / Environmental Sensing
+/// readings, with a leading spacer line.
+fn dashboard_lines(dash: &Dashboard) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled("  ── Profiles ──", theme::title())),
+    ];
+    let sep = || Span::styled(" · ", theme::dim());
+
+    // Device information, two fields per line.
+    let fields: Vec<_> = dash.info.iter().collect();
+    for pair in fields.chunks(2) {
+        let mut spans = vec![Span::raw("  ")];
+        for (i, (field, value)) in pair.iter().enumerate() {
+            if i > 0 {
+                spans.push(sep());
+            }
+            spans.push(Span::styled(format!("{}: ", field.label()), theme::dim()));
+            spans.push(Span::styled((*value).clone(), theme::list_item()));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if let Some(hr) = &dash.heart_rate {
+        let mut spans = vec![
+            Span::styled("  ♥ ", theme::error()),
+            Span::styled(format!("{} bpm", hr.bpm), theme::title()),
+        ];
+        if let Some(&rr) = hr.rr_intervals.last() {
+            spans.push(sep());
+            spans.push(Span::styled(
+                format!("RR {} ms", u32::from(rr) * 1000 / 1024),
+                theme::list_item(),
+            ));
+        }
+        match hr.contact {
+            Some(true) => {
+                spans.push(sep());
+                spans.push(Span::styled("contact", theme::connected()));
+            }
+            Some(false) => {
+                spans.push(sep());
+                spans.push(Span::styled("no contact", theme::error()));
+            }
+            None => {}
+        }
+        if let Some(kj) = hr.energy_kj {
+            spans.push(sep());
+            spans.push(Span::styled(format!("{kj} kJ"), theme::list_item()));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if dash.has_environment() {
+        let values: Vec<String> = [
+            dash.temperature.map(|t| format!("{t:.1} °C")),
+            dash.humidity.map(|h| format!("{h:.1} %RH")),
+            dash.pressure.map(|p| format!("{:.1} hPa", p / 100.0)),
+            dash.uv_index.map(|uv| format!("UV {uv}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        lines.push(Line::from(vec![
+            Span::styled("  Env: ", theme::dim()),
+            Span::styled(values.join("  "), theme::list_item()),
+        ]));
+    }
+    lines
+}
+
+/// Bytes per hex dump line — fits the narrowest detail panel.
+const HEX_WIDTH: usize = 8;
+
+/// Raw advertisement data: address type, TX power, appearance, manufacturer
+/// and service data hex dumps, and service UUIDs.
+fn advertisement_lines(device: &DeviceInfo) -> Vec<Line<'static>> {
+    let field = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("  {label:<11}"), theme::dim()),
+            Span::styled(value, theme::list_item()),
+        ])
+    };
+    let mut lines = vec![
+        field(
+            "Addr type:",
+            device
+                .address_type
+                .map_or_else(|| "unknown".into(), |t| t.to_string()),
+        ),
+        field(
+            "TX power:",
+            device
+                .tx_power
+                .map_or_else(|| "n/a".into(), |p| format!("{p} dBm")),
+        ),
+        field(
+            "Appearance:",
+            device.appearance.map_or_else(
+                || "n/a".into(),
+                |a| match assigned_numbers::appearance_name(a) {
+                    Some(name) => format!("{name} (0x{a:04X})"),
+                    None => format!("0x{a:04X}"),
+                },
+            ),
+        ),
+    ];
+
+    for (company, data) in &device.manufacturer_data {
+        let name = assigned_numbers::company_name(*company).unwrap_or("Unknown company");
+        lines.push(Line::from(vec![
+            Span::styled(format!("  󰏗 0x{company:04X} "), theme::title()),
+            Span::styled(name, theme::list_item()),
+            Span::styled(format!(" · {} B", data.len()), theme::dim()),
+        ]));
+        lines.extend(hex_dump(data));
+    }
+
+    for (uuid, data) in &device.service_data {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  󰒓 {} ", short_uuid(uuid)), theme::title()),
+            Span::styled(
+                assigned_numbers::uuid_name(uuid).unwrap_or("service data"),
+                theme::list_item(),
+            ),
+            Span::styled(format!(" · {} B", data.len()), theme::dim()),
+        ]));
+        lines.extend(hex_dump(data));
+    }
+
+    if !device.uuids.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  Services ({})", device.uuids.len()),
+            theme::dim(),
+        )));
+        lines.extend(device.uuids.iter().map(|uuid| {
+            Line::from(Span::styled(
+                format!("    {}", assigned_numbers::describe_uuid(uuid)),
+                theme::list_item(),
+            ))
+        }));
+    }
+
+    if device.manufacturer_data.is_empty()
+        && device.service_data.is_empty()
+        && device.uuids.is_empty()
+    {
+        lines.push(Line::from(Span::styled(
+            "  No advertisement data",
+            theme::dim(),
+        )));
+    }
+    lines
+}
+
+/// `    00  4c 00 10 05 …` — offset plus up to `HEX_WIDTH` bytes per line.
+fn hex_dump(data: &[u8]) -> impl Iterator<Item = Line<'static>> + '_ {
+    data.chunks(HEX_WIDTH).enumerate().map(|(i, chunk)| {
+        let bytes: Vec<String> = chunk.iter().map(|b| format!("{b:02x}")).collect();
+        Line::from(vec![
+            Span::styled(format!("    {:02x}  ", i * HEX_WIDTH), theme::dim()),
+            Span::styled(bytes.join(" "), theme::list_item()),
+        ])
+    })
+}
+
+/// Indent a chart row to line up with the labels above it.
+fn inset(area: Rect) -> Rect {
+    Rect {
+        x: area.x + 2,
+        width: area.width.saturating_sub(4),
+        ..area
+    }
+}
+
+/// Top line of a multi-line row, for single-line fallbacks.
+fn first_line(area: Rect) -> Rect {
+    Rect {
+        height: area.height.min(1),
+        ..area
+    }
+}
+
+/// Persistent history summary: first seen, last connection, success rate.
+fn history_lines(h: Option<&DeviceHistory>) -> Vec<Line<'static>> {
+    let header = Line::from(Span::styled("  ── History ──", theme::title()));
+    let Some(h) = h else {
+        return vec![
+            header,
+            Line::from(Span::styled("  No history recorded yet", theme::dim())),
+        ];
+    };
+
+    let ago = |ts: Option<u64>| match ts {
+        Some(ts) => format!("{} ago", super::format_duration(history::since(ts))),
+        None => "never".into(),
+    };
+    let avg = h
+        .avg_connect()
+        .map(|d| format!(" · avg {:.1}s", d.as_secs_f64()))
+        .unwrap_or_default();
+
+    vec![
+        header,
+        Line::from(vec![
+            Span::styled("  First:   ", theme::dim()),
+            Span::styled(ago(h.first_seen), theme::list_item()),
+        ]),
+        Line::from(vec![
+            Span::styled("  Linked:  ", theme::dim()),
+            Span::styled(ago(h.last_connected), theme::list_item()),
+        ]),
+        Line::from(vec![
+            Span::styled("  Connects:", theme::dim()),
+            Span::styled(format!(" {} ok", h.connect_ok), theme::trusted()),
+            Span::styled(" · ", theme::dim()),
+            Span::styled(
+                format!("{} failed", h.connect_failed),
+                if h.connect_failed > 0 {
+                    theme::error()
+                } else {
+                    theme::dim()
+                },
+            ),
+            Span::styled(avg, theme::dim()),
+        ]),
+    ]
 }
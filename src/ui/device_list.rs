@@ -1,5 +1,7 @@
 //! Scrollable device list sorted by RSSI with Nerd Font icons.
 
+use std::time::Duration;
+
 use ratatui::layout::Rect;
 use ratatui::style::Style;
 use ratatui::text::{Line, Span};
@@ -7,17 +9,49 @@ use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
 use ratatui::Frame;
 
 use crate::app::App;
+use crate::battery::BatteryLevel;
+use crate::bluetooth::types::{DeviceInfo, DeviceSet};
+use crate::config;
 use crate::theme;
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let filtered = app.filtered_devices();
+    let stale_after = Duration::from_secs(config::get().general.stale_after_secs);
 
-    let items: Vec<ListItem> = filtered
-        .iter()
-        .map(|device| {
-            let icon = theme::device_icon(device.icon.as_deref(), device.class);
+    // Coordinated sets get a header row above their (adjacent) members;
+    // headers aren't selectable, so the selection shifts past them.
+    let mut items: Vec<ListItem> = Vec::with_capacity(filtered.len());
+    let mut selected_row = app.selected_index;
+    for (i, device) in filtered.iter().enumerate() {
+        let set = app.set_of(&device.address);
+        let in_same_set = |j: usize| {
+            set.is_some_and(|s| {
+                filtered
+                    .get(j)
+                    .is_some_and(|d| s.members.contains(&d.address))
+            })
+        };
+        let tree = match set {
+            Some(s) => {
+                if i == 0 || !in_same_set(i - 1) {
+                    items.push(set_header(s, &filtered[i..]));
+                    if i <= app.selected_index {
+                        selected_row += 1;
+                    }
+                }
+                if in_same_set(i + 1) {
+                    " ├─"
+                } else {
+                    " └─"
+                }
+            }
+            None => "",
+        };
+        items.push({
+            let icon = theme::device_icon(device.kind);
             let (rssi_icon, rssi_color) = theme::rssi_display(device.rssi);
             let name = device.display_name();
+            let stale = device.is_stale(stale_after);
 
             // Build status badges.
             let mut badges = Vec::new();
@@ -30,12 +64,20 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             if device.trusted {
                 badges.push(Span::styled(" 󰊕 ", theme::trusted()));
             }
+            if device.blocked {
+                badges.push(Span::styled(" ⊘ ", theme::error()));
+            }
 
-            // Battery indicator.
+            // Battery indicator, with a warning badge below the thresholds.
             let battery_span = if let Some(pct) = device.battery {
                 let (bat_icon, bat_color) = theme::battery_display(Some(pct));
+                let warn = if app.battery.level(&device.address) > BatteryLevel::Normal {
+                    "󰀦 "
+                } else {
+                    ""
+                };
                 Span::styled(
-                    format!(" {bat_icon} {pct}%"),
+                    format!(" {warn}{bat_icon} {pct}%"),
                     Style::default().fg(bat_color),
                 )
             } else {
@@ -51,27 +93,41 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             };
 
             // Compose the line.
+            let name_width = 28 - tree.chars().count();
             let mut spans = vec![
+                Span::styled(tree, theme::dim()),
                 Span::styled(format!(" {icon} "), theme::list_item()),
                 Span::styled(
-                    format!("{name:<28}"),
+                    format!("{name:<name_width$}"),
                     if device.connected {
                         theme::connected()
+                    } else if stale {
+                        theme::dim()
                     } else if device.paired {
                         theme::paired()
                     } else {
                         theme::list_item()
                     },
                 ),
+                Span::styled(
+                    format!(" {:<9}", device.kind.category().label()),
+                    theme::dim(),
+                ),
             ];
             spans.extend(badges);
             spans.push(battery_span);
             spans.push(rssi_span);
             spans.push(rssi_val);
+            if !device.connected {
+                spans.push(Span::styled(
+                    format!("󰥔 {}", super::format_age(device.age())),
+                    theme::dim(),
+                ));
+            }
 
             ListItem::new(Line::from(spans))
-        })
-        .collect();
+        });
+    }
 
     let title = if app.scanning {
         let spinner = theme::spinner_frame(app.tick_count);
@@ -92,8 +148,31 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
 
     let mut state = ListState::default();
     if !filtered.is_empty() {
-        state.select(Some(app.selected_index));
+        state.select(Some(selected_row));
     }
 
     frame.render_stateful_widget(list, area, &mut state);
 }
+
+/// Header row for a coordinated set, named after its first listed member.
+fn set_header<'a>(set: &DeviceSet, rest: &[&DeviceInfo]) -> ListItem<'a> {
+    let listed: Vec<&DeviceInfo> = rest
+        .iter()
+        .take_while(|d| set.members.contains(&d.address))
+        .copied()
+        .collect();
+    let name = listed.first().map_or("Device set", |d| d.display_name());
+    let size = set.size.map_or(set.members.len(), usize::from);
+    let connected = listed.iter().filter(|d| d.connected).count();
+    ListItem::new(Line::from(vec![
+        Span::styled(" \u{f0c1} ", theme::list_item()),
+        Span::styled(name.to_string(), theme::title()),
+        Span::styled(
+            format!(
+                " · set of {size} · {connected}/{} connected",
+                set.members.len()
+            ),
+            theme::dim(),
+        ),
+    ]))
+}
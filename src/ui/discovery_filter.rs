@@ -0,0 +1,86 @@
+//! Discovery filter dialog — edits the BlueZ scan filter at runtime.
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::{FilterField, FilterForm};
+use crate::theme;
+
+/// Render the discovery filter dialog.
+pub fn render(frame: &mut Frame, form: &FilterForm) {
+    let area = super::popup::centered_rect_percent(60, 14, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(" 󰈲 Discovery Filter ", theme::title()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::deep_purple()));
+
+    let draft = &form.draft;
+    let mut lines = vec![Line::from("")];
+
+    for field in FilterField::ALL {
+        let (label, value) = match field {
+            FilterField::Transport => ("Transport", draft.transport.label().to_string()),
+            FilterField::Rssi => (
+                "Min RSSI",
+                draft
+                    .rssi
+                    .map(|r| format!("{r} dBm"))
+                    .unwrap_or_else(|| "off".into()),
+            ),
+            FilterField::Pathloss => (
+                "Max pathloss",
+                draft
+                    .pathloss
+                    .map(|p| format!("{p} dB"))
+                    .unwrap_or_else(|| "off".into()),
+            ),
+            FilterField::Uuids => {
+                let mut text = form.uuid_input.clone();
+                if form.field == FilterField::Uuids {
+                    text.push('█');
+                } else if text.is_empty() {
+                    text = "any".into();
+                }
+                ("Service UUIDs", text)
+            }
+            FilterField::Duplicates => (
+                "Duplicate data",
+                if draft.duplicate_data { "on" } else { "off" }.to_string(),
+            ),
+        };
+
+        let focused = form.field == field;
+        let marker = if focused { "▸ " } else { "  " };
+        let value_style = if focused {
+            Style::default()
+                .fg(theme::cyan())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            theme::list_item()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {marker}"), theme::title()),
+            Span::styled(format!("{label:<16}"), theme::dim()),
+            Span::styled(value, value_style),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    if form.error.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  ←/→ adjust  ⌫ clear  ⏎ apply  Esc cancel",
+            theme::dim(),
+        )));
+    } else {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", form.error),
+            theme::error(),
+        )));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
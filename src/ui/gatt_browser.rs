@@ -0,0 +1,417 @@
+//! GATT browser pane: collapsible service → characteristic → descriptor tree,
+//! with the selected characteristic's value log and write prompt below, plus
+//! the export picker and comparison views.
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::bluetooth::assigned_numbers;
+use crate::bluetooth::types::{short_uuid, Uuid};
+use crate::gatt_browser::{ComparePicker, CompareView, GattBrowser, GattNode, GattView, LogKind};
+use crate::gatt_export::Change;
+use crate::theme;
+
+/// Height of the value log section, including its separator line.
+const LOG_HEIGHT: u16 = 10;
+
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(browser) = &app.gatt else {
+        return;
+    };
+
+    let mut title = vec![Span::styled(
+        format!(" 󰂴 GATT · {} ", browser.name),
+        theme::title(),
+    )];
+    let spinner = theme::spinner_frame(app.tick_count);
+    if browser.exporting {
+        title.push(Span::styled(format!("{spinner} exporting… "), theme::dim()));
+    } else if browser.cached {
+        title.push(Span::styled(
+            format!("{spinner} cached, refreshing… "),
+            theme::dim(),
+        ));
+    }
+    let block = Block::default()
+        .title(Line::from(title))
+        .borders(Borders::ALL)
+        .border_style(theme::border_active());
+
+    match &browser.view {
+        GattView::Tree => {}
+        GattView::Picker(picker) => {
+            let inner = block.inner(area);
+            frame.render_widget(block, area);
+            render_picker(frame, picker, inner);
+            return;
+        }
+        GattView::Compare(view) => {
+            let inner = block.inner(area);
+            frame.render_widget(block, area);
+            render_compare(frame, view, inner);
+            return;
+        }
+    }
+
+    let message = match (&browser.services, &browser.error) {
+        (_, Some(error)) => Some(Line::from(Span::styled(
+            format!("  {error}"),
+            theme::error(),
+        ))),
+        (None, None) => Some(Line::from(vec![
+            Span::styled(
+                format!("  {} ", theme::spinner_frame(app.tick_count)),
+                theme::title(),
+            ),
+            Span::styled("Resolving services…", theme::dim()),
+        ])),
+        (Some(services), None) if services.is_empty() => {
+            Some(Line::from(Span::styled("  No GATT services", theme::dim())))
+        }
+        _ => None,
+    };
+    if let Some(line) = message {
+        let paragraph = Paragraph::new(vec![Line::from(""), line])
+            .block(block)
+            .wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let show_log = browser.selected_characteristic().is_some() && inner.height > LOG_HEIGHT + 3;
+    let (tree_area, log_area) = if show_log {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(LOG_HEIGHT)])
+            .split(inner);
+        (chunks[0], Some(chunks[1]))
+    } else {
+        (inner, None)
+    };
+
+    let items: Vec<ListItem> = browser
+        .rows()
+        .into_iter()
+        .filter_map(|node| row_line(browser, node))
+        .map(ListItem::new)
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(theme::selected())
+        .highlight_symbol("▸ ");
+    let mut state = ListState::default();
+    state.select(Some(browser.cursor));
+    frame.render_stateful_widget(list, tree_area, &mut state);
+
+    if let Some(log_area) = log_area {
+        render_log(frame, browser, log_area);
+    }
+}
+
+/// Live tree and saved exports to compare.
+fn render_picker(frame: &mut Frame, picker: &ComparePicker, area: Rect) {
+    let mut lines = vec![Line::from(Span::styled(
+        " Compare GATT snapshots",
+        theme::title(),
+    ))];
+    if picker.sources.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  No exports yet: press e in the tree to export one",
+            theme::dim(),
+        )));
+        frame.render_widget(Paragraph::new(lines), area);
+        return;
+    }
+    let header_height = lines.len() as u16 + 1;
+
+    let items: Vec<ListItem> = picker
+        .sources
+        .iter()
+        .enumerate()
+        .map(|(i, source)| {
+            let (mark, style) = if picker.base == Some(i) {
+                ("base ", Style::default().fg(theme::amber()))
+            } else {
+                ("     ", theme::list_item())
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(mark, style),
+                Span::styled(source.label(), style),
+            ]))
+        })
+        .collect();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(header_height),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(area);
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let list = List::new(items)
+        .highlight_style(theme::selected())
+        .highlight_symbol("▸ ");
+    let mut state = ListState::default();
+    state.select(Some(picker.cursor));
+    frame.render_stateful_widget(list, chunks[1], &mut state);
+
+    let footer = match &picker.error {
+        Some(error) => Line::from(Span::styled(format!(" {error}"), theme::error())),
+        None if picker.base.is_some() => Line::from(Span::styled(
+            " Enter compares base → selection",
+            theme::dim(),
+        )),
+        None => Line::from(Span::styled(
+            " Enter compares selection → live · Space marks a base",
+            theme::dim(),
+        )),
+    };
+    frame.render_widget(Paragraph::new(footer), chunks[2]);
+}
+
+/// Attribute-by-attribute comparison with added / removed / changed rows
+/// highlighted.
+fn render_compare(frame: &mut Frame, view: &CompareView, area: Rect) {
+    let summary = &view.summary;
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(" - ", theme::error()),
+            Span::styled(view.old.clone(), theme::dim()),
+        ]),
+        Line::from(vec![
+            Span::styled(" + ", theme::connected()),
+            Span::styled(view.new.clone(), theme::dim()),
+        ]),
+        Line::from(if summary.is_empty() {
+            vec![Span::styled(" Identical", theme::connected())]
+        } else {
+            vec![
+                Span::styled(format!(" {} added", summary.added), theme::connected()),
+                Span::styled(" · ", theme::dim()),
+                Span::styled(format!("{} removed", summary.removed), theme::error()),
+                Span::styled(" · ", theme::dim()),
+                Span::styled(
+                    format!("{} changed", summary.changed),
+                    Style::default().fg(theme::amber()),
+                ),
+                Span::styled(
+                    if view.changes_only {
+                        "  (changes only)"
+                    } else {
+                        ""
+                    },
+                    theme::dim(),
+                ),
+            ]
+        }),
+    ];
+    if !summary.values_compared {
+        lines.push(Line::from(Span::styled(
+            " Values not compared: one side is not a snapshot",
+            theme::dim(),
+        )));
+    }
+    lines.push(Line::from(""));
+
+    for row in view.visible_rows().into_iter().skip(view.scroll) {
+        let style = match row.change {
+            Change::Same => theme::dim(),
+            Change::Added => theme::connected(),
+            Change::Removed => theme::error(),
+            Change::Changed => Style::default().fg(theme::amber()),
+        };
+        let mut spans = vec![
+            Span::styled(format!(" {} ", row.change.marker()), style),
+            Span::raw("  ".repeat(row.depth)),
+            Span::styled(row.label.clone(), style),
+        ];
+        if let Some(detail) = &row.detail {
+            spans.push(Span::styled(format!("  {detail}"), theme::dim()));
+        }
+        if let Some(note) = &row.note {
+            spans.push(Span::styled(
+                format!("  ({note})"),
+                theme::dim().add_modifier(Modifier::ITALIC),
+            ));
+        }
+        lines.push(Line::from(spans));
+        if lines.len() >= area.height as usize {
+            break;
+        }
+    }
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Value log of the selected characteristic, newest entry last, with the
+/// write prompt on the bottom line while it is open.
+fn render_log(frame: &mut Frame, browser: &GattBrowser, area: Rect) {
+    let Some((target, characteristic)) = browser.selected_characteristic() else {
+        return;
+    };
+    let name = assigned_numbers::uuid_name(&characteristic.uuid)
+        .map(str::to_string)
+        .unwrap_or_else(|| short_uuid(&characteristic.uuid));
+    let mut title = vec![Span::styled(
+        format!(" Value log · {name} "),
+        theme::title(),
+    )];
+    if browser.is_notifying(target.characteristic) {
+        title.push(Span::styled("● notifying ", theme::connected()));
+    }
+    let block = Block::default()
+        .title(Line::from(title))
+        .borders(Borders::TOP)
+        .border_style(theme::border_inactive());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut lines: Vec<Line> = Vec::new();
+    let prompt = browser.write.as_ref().map(|form| {
+        let mode = if form.with_response {
+            "request"
+        } else {
+            "command"
+        };
+        let mut spans = vec![
+            Span::styled(
+                format!(" write [{} · {mode}] ", form.format.label()),
+                Style::default().fg(theme::amber()),
+            ),
+            Span::styled(format!("{}█", form.input), theme::list_item()),
+        ];
+        if let Some(error) = &form.error {
+            spans.push(Span::styled(format!("  {error}"), theme::error()));
+        }
+        Line::from(spans)
+    });
+    let room = inner.height as usize - usize::from(prompt.is_some());
+
+    match browser.log(target.characteristic) {
+        Some(log) if !log.entries.is_empty() => {
+            let skip = log.entries.len().saturating_sub(room);
+            for entry in log.entries.iter().skip(skip) {
+                let secs = entry.at.as_secs();
+                let (label, style) = match entry.kind {
+                    LogKind::Read => ("read  ", theme::list_item()),
+                    LogKind::Notify => ("notify", Style::default().fg(theme::cyan())),
+                    LogKind::Write => ("write ", Style::default().fg(theme::amber())),
+                    LogKind::Info => ("      ", theme::dim()),
+                    LogKind::Error => ("error ", theme::error()),
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!(
+                            " {:02}:{:02}:{:02}.{:03} ",
+                            secs / 3600,
+                            secs / 60 % 60,
+                            secs % 60,
+                            entry.at.subsec_millis()
+                        ),
+                        theme::dim(),
+                    ),
+                    Span::styled(format!("{label} "), style),
+                    Span::styled(entry.text.clone(), style),
+                ]));
+            }
+        }
+        _ => lines.push(Line::from(Span::styled(
+            " r read · w write · n notify",
+            theme::dim(),
+        ))),
+    }
+    if let Some(prompt) = prompt {
+        while lines.len() < room {
+            lines.push(Line::from(""));
+        }
+        lines.push(prompt);
+    }
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// One tree row: fold marker, name, properties and handle.
+fn row_line(browser: &GattBrowser, node: GattNode) -> Option<Line<'static>> {
+    let services = browser.services.as_ref()?;
+    let indent = "  ".repeat(node.depth());
+    let fold = |handle: u16, has_children: bool| match (has_children, browser.is_expanded(handle)) {
+        (false, _) => "  ",
+        (true, true) => "▾ ",
+        (true, false) => "▸ ",
+    };
+
+    let mut spans = vec![Span::raw(indent)];
+    match node {
+        GattNode::Service(si) => {
+            let service = services.get(si)?;
+            spans.push(Span::styled(
+                fold(service.handle, !service.characteristics.is_empty()),
+                theme::dim(),
+            ));
+            spans.push(Span::styled("󰒓 ", theme::title()));
+            spans.extend(name_spans(&service.uuid, theme::title()));
+            if !service.primary {
+                spans.push(Span::styled("  secondary", theme::dim()));
+            }
+            spans.push(handle_span(service.handle));
+        }
+        GattNode::Characteristic(si, ci) => {
+            let characteristic = services.get(si)?.characteristics.get(ci)?;
+            spans.push(Span::styled(
+                fold(
+                    characteristic.handle,
+                    !characteristic.descriptors.is_empty(),
+                ),
+                theme::dim(),
+            ));
+            spans.extend(name_spans(&characteristic.uuid, theme::list_item()));
+            let props = characteristic.props.labels();
+            if !props.is_empty() {
+                spans.push(Span::styled(
+                    format!("  [{}]", props.join(" ")),
+                    ratatui::style::Style::default().fg(theme::cyan()),
+                ));
+            }
+            spans.push(handle_span(characteristic.handle));
+            if browser.is_notifying(characteristic.handle) {
+                spans.push(Span::styled("  ●", theme::connected()));
+            }
+        }
+        GattNode::Descriptor(si, ci, di) => {
+            let descriptor = services
+                .get(si)?
+                .characteristics
+                .get(ci)?
+                .descriptors
+                .get(di)?;
+            spans.push(Span::raw("  "));
+            spans.extend(name_spans(&descriptor.uuid, theme::dim()));
+            spans.push(handle_span(descriptor.handle));
+        }
+    }
+    Some(Line::from(spans))
+}
+
+/// `Battery Level 0x2A19`, or just the UUID for vendor attributes.
+fn name_spans(uuid: &Uuid, style: ratatui::style::Style) -> Vec<Span<'static>> {
+    match assigned_numbers::uuid_name(uuid) {
+        Some(name) => vec![
+            Span::styled(name, style),
+            Span::styled(format!(" {}", short_uuid(uuid)), theme::dim()),
+        ],
+        None => vec![Span::styled(short_uuid(uuid), style)],
+    }
+}
+
+fn handle_span(handle: u16) -> Span<'static> {
+    Span::styled(format!("  h:0x{handle:04x}"), theme::dim())
+}
@@ -27,6 +27,7 @@ pub fn render(frame: &mut Frame, _app: &App) {
                 ("k / ↑", "Move cursor up"),
                 ("g", "Jump to top"),
                 ("G", "Jump to bottom"),
+                ("J / K", "Scroll device details"),
             ],
         ),
         (
@@ -35,10 +36,19 @@ pub fn render(frame: &mut Frame, _app: &App) {
                 ("Enter", "Connect / Disconnect (toggle)"),
                 ("p", "Pair with device"),
                 ("t", "Toggle trusted"),
+                ("B", "Block / unblock device"),
+                ("W", "Allow / forbid waking from suspend"),
+                ("m", "Play / pause media (AVRCP)"),
+                (". / ,", "Next / previous track"),
+                ("+ / -", "Raise / lower audio volume"),
+                ("F", "Send a file (OBEX Object Push)"),
+                ("T", "File transfers"),
                 ("d", "Disconnect device"),
                 ("r", "Remove / forget device"),
                 ("R", "Refresh device info"),
                 ("A", "Rename device alias"),
+                ("b", "Browse GATT services (connected LE devices)"),
+                ("o", "Connect / disconnect individual profiles"),
             ],
         ),
         (
@@ -46,7 +56,14 @@ pub fn render(frame: &mut Frame, _app: &App) {
             vec![
                 ("a", "Toggle adapter power"),
                 ("s", "Toggle scanning"),
+                ("f", "Edit discovery filter"),
+                ("C", "Adapter settings (alias, discoverable, pairable)"),
+                ("P", "Admin policy (service allowlist presets)"),
+                ("I", "Toggle periodic background scan"),
+                ("e", "Export scan session (CSV / JSON)"),
                 ("S", "Cycle sort mode"),
+                ("c", "Cycle device category filter"),
+                ("X", "Show only blocked devices"),
             ],
         ),
         (
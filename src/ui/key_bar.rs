@@ -1,7 +1,7 @@
 //! Persistent key instruction bar at the bottom of the screen.
 //!
 //! Shows context-aware keybindings in a compact, styled row that adapts
-//! to the current input mode (Normal, Search, Dialog, Rename).
+//! to the current input mode (Normal, Search, Dialog, Rename, GATT browser).
 
 use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
@@ -10,6 +10,7 @@ use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
 use crate::app::{App, InputMode};
+use crate::gatt_browser::GattView;
 use crate::theme;
 
 /// Render the key-hint bar into the given area.
@@ -19,6 +20,20 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         InputMode::Search => search_hints(),
         InputMode::Dialog => dialog_hints(),
         InputMode::Rename => rename_hints(),
+        InputMode::FilterEdit => filter_hints(),
+        InputMode::Profiles => profile_hints(),
+        InputMode::AdapterSettings => adapter_hints(),
+        InputMode::AdminPolicy => policy_hints(),
+        InputMode::SendFile => send_file_hints(),
+        InputMode::Transfers => transfers_hints(),
+        InputMode::Gatt if app.gatt.as_ref().is_some_and(|g| g.write.is_some()) => {
+            gatt_write_hints()
+        }
+        InputMode::Gatt => match app.gatt.as_ref().map(|g| &g.view) {
+            Some(GattView::Picker(_)) => gatt_picker_hints(),
+            Some(GattView::Compare(_)) => gatt_compare_hints(),
+            _ => gatt_hints(),
+        },
     };
 
     let line = Line::from(spans);
@@ -88,6 +103,9 @@ fn normal_hints(app: &App) -> Vec<Span<'static>> {
     hints.push(key("A"));
     hints.push(desc(" Rename "));
 
+    hints.push(key("b"));
+    hints.push(desc(" GATT "));
+
     hints.push(sep());
 
     // Adapter.
@@ -107,6 +125,19 @@ fn normal_hints(app: &App) -> Vec<Span<'static>> {
         hints.push(desc(" Scan "));
     }
 
+    hints.push(key("f"));
+    hints.push(desc(" Filter "));
+
+    hints.push(key("I"));
+    if app.periodic_next.is_some() {
+        hints.push(desc(" Periodic Off "));
+    } else {
+        hints.push(desc(" Periodic "));
+    }
+
+    hints.push(key("e"));
+    hints.push(desc(" Export "));
+
     hints.push(key("S"));
     hints.push(desc(" Sort "));
 
@@ -160,3 +191,171 @@ fn rename_hints() -> Vec<Span<'static>> {
         desc("Type new alias…"),
     ]
 }
+
+fn send_file_hints() -> Vec<Span<'static>> {
+    vec![
+        Span::raw(" "),
+        key("⏎"),
+        desc(" Send "),
+        key("Tab"),
+        desc(" Complete "),
+        sep(),
+        key("Esc"),
+        desc(" Cancel "),
+        sep(),
+        desc("Type file path…"),
+    ]
+}
+
+fn transfers_hints() -> Vec<Span<'static>> {
+    vec![
+        Span::raw(" "),
+        key("j/k"),
+        desc(" Select "),
+        sep(),
+        key("x"),
+        desc(" Cancel transfer "),
+        sep(),
+        key("Esc"),
+        desc(" Close "),
+    ]
+}
+
+fn filter_hints() -> Vec<Span<'static>> {
+    vec![
+        Span::raw(" "),
+        key("↑/↓"),
+        desc(" Field "),
+        key("←/→"),
+        desc(" Adjust "),
+        key("⌫"),
+        desc(" Clear "),
+        sep(),
+        key("⏎"),
+        desc(" Apply "),
+        sep(),
+        key("Esc"),
+        desc(" Cancel "),
+    ]
+}
+
+fn adapter_hints() -> Vec<Span<'static>> {
+    vec![
+        Span::raw(" "),
+        key("↑/↓"),
+        desc(" Field "),
+        key("←/→"),
+        desc(" Change "),
+        sep(),
+        key("⏎"),
+        desc(" Apply alias "),
+        sep(),
+        key("Esc"),
+        desc(" Close "),
+    ]
+}
+
+fn policy_hints() -> Vec<Span<'static>> {
+    vec![
+        Span::raw(" "),
+        key("j/k"),
+        desc(" Preset "),
+        sep(),
+        key("⏎"),
+        desc(" Apply "),
+        key("R"),
+        desc(" Refresh "),
+        sep(),
+        key("Esc"),
+        desc(" Close "),
+    ]
+}
+
+fn profile_hints() -> Vec<Span<'static>> {
+    vec![
+        Span::raw(" "),
+        key("j/k"),
+        desc(" Navigate "),
+        sep(),
+        key("⏎"),
+        desc(" Connect/Disconnect "),
+        key("R"),
+        desc(" Refresh "),
+        sep(),
+        key("Esc"),
+        desc(" Close "),
+    ]
+}
+
+fn gatt_hints() -> Vec<Span<'static>> {
+    vec![
+        Span::raw(" "),
+        key("j/k"),
+        desc(" Navigate "),
+        key("⏎"),
+        desc(" Fold "),
+        key("E"),
+        desc(" All "),
+        sep(),
+        key("r"),
+        desc(" Read "),
+        key("w"),
+        desc(" Write "),
+        key("n"),
+        desc(" Notify "),
+        key("x"),
+        desc(" Clear "),
+        sep(),
+        key("e"),
+        desc(" Export "),
+        key("D"),
+        desc(" Compare "),
+        key("R"),
+        desc(" Reload "),
+        key("Esc"),
+        desc(" Close "),
+    ]
+}
+
+fn gatt_picker_hints() -> Vec<Span<'static>> {
+    vec![
+        Span::raw(" "),
+        key("j/k"),
+        desc(" Navigate "),
+        key("Space"),
+        desc(" Mark base "),
+        key("⏎"),
+        desc(" Compare "),
+        sep(),
+        key("Esc"),
+        desc(" Back "),
+    ]
+}
+
+fn gatt_compare_hints() -> Vec<Span<'static>> {
+    vec![
+        Span::raw(" "),
+        key("j/k"),
+        desc(" Scroll "),
+        key("c"),
+        desc(" Changes only "),
+        sep(),
+        key("Esc"),
+        desc(" Back "),
+    ]
+}
+
+fn gatt_write_hints() -> Vec<Span<'static>> {
+    vec![
+        Span::raw(" "),
+        key("⏎"),
+        desc(" Write "),
+        key("↑/↓"),
+        desc(" Format "),
+        key("Tab"),
+        desc(" Request/Command "),
+        sep(),
+        key("Esc"),
+        desc(" Cancel "),
+    ]
+}
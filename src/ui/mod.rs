@@ -2,16 +2,25 @@
 //!
 //! Splits the terminal frame into three regions:
 //! - Status bar (top, 3 lines)
-//! - Device list (left ~60%) + Detail panel (right ~40%)
+//! - Device list (left ~60%) + Detail panel or GATT browser (right ~40%)
 //! - Popup overlay (centered, on top of everything)
 
+pub mod adapter_settings;
+pub mod admin_policy;
 pub mod detail_panel;
 pub mod device_list;
+pub mod discovery_filter;
+pub mod gatt_browser;
 pub mod help;
 pub mod key_bar;
 pub mod popup;
+pub mod profile_picker;
 pub mod spinner;
 pub mod status_bar;
+pub mod toast;
+pub mod transfers;
+
+use std::time::{Duration, Instant};
 
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::Frame;
@@ -58,13 +67,45 @@ pub fn render(frame: &mut Frame, app: &App) {
     };
 
     device_list::render(frame, app, list_area);
-    detail_panel::render(frame, app, detail_area);
+    if app.gatt.is_some() {
+        gatt_browser::render(frame, app, detail_area);
+    } else {
+        detail_panel::render(frame, app, detail_area);
+    }
 
     // ── Key hints bar (bottom) ──────────────────────────────────────────
     key_bar::render(frame, app, keybar_area);
 
+    // ── Toasts (non-modal, top-right of the content area) ───────────────
+    toast::render(frame, app, content_area);
+
     // ── Popup overlay (rendered last so it's on top) ────────────────────
     if let Some(ref popup_data) = app.active_popup {
         popup::render(frame, app, popup_data);
     }
 }
+
+/// Compact human duration: `42s`, `3m05s`, `2h10m`, `4d07h`.
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        3600..=86_399 => format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60),
+        _ => format!("{}d{:02}h", secs / 86_400, (secs % 86_400) / 3600),
+    }
+}
+
+/// "Last seen" label: `now`, `3m05s ago`, or `not seen` when unknown.
+pub fn format_age(age: Option<Duration>) -> String {
+    match age {
+        None => "not seen".into(),
+        Some(age) if age < Duration::from_secs(5) => "now".into(),
+        Some(age) => format!("{} ago", format_duration(age)),
+    }
+}
+
+/// Time remaining until `deadline`, formatted with [`format_duration`].
+pub fn format_countdown(deadline: Instant) -> String {
+    format_duration(deadline.saturating_duration_since(Instant::now()))
+}
@@ -68,6 +68,21 @@ pub fn render(frame: &mut Frame, app: &App, popup: &Popup) {
         Popup::Help => {
             super::help::render(frame, app);
         }
+        Popup::DiscoveryFilter(form) => {
+            super::discovery_filter::render(frame, form);
+        }
+        Popup::AdminPolicy { cursor } => {
+            super::admin_policy::render(frame, app, *cursor);
+        }
+        Popup::Transfers { cursor } => {
+            super::transfers::render(frame, app, *cursor);
+        }
+        Popup::AdapterSettings(form) => {
+            super::adapter_settings::render(frame, app, form);
+        }
+        Popup::Profiles(picker) => {
+            super::profile_picker::render(frame, picker, app.tick_count);
+        }
     }
 }
 
@@ -135,7 +150,7 @@ fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
 }
 
 /// Compute a centered rectangle where width is a percentage of available area.
-fn centered_rect_percent(percent_x: u16, height: u16, area: Rect) -> Rect {
+pub(crate) fn centered_rect_percent(percent_x: u16, height: u16, area: Rect) -> Rect {
     let percent_x = percent_x.clamp(10, 100);
     let width = ((area.width as u32 * percent_x as u32) / 100) as u16;
     centered_rect(width, height, area)
@@ -0,0 +1,89 @@
+//! Profile picker — connect or disconnect individual profiles of a device.
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::ProfilePicker;
+use crate::bluetooth::assigned_numbers;
+use crate::bluetooth::types::{short_uuid, ProfileState};
+use crate::theme;
+
+/// Render the profile picker dialog.
+pub fn render(frame: &mut Frame, picker: &ProfilePicker, tick: u64) {
+    let rows = picker.profiles.as_ref().map_or(1, |p| p.len().max(1)) as u16;
+    let area = super::popup::centered_rect_percent(60, rows + 6, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" 󰂯 Profiles — {} ", picker.name),
+            theme::title(),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::deep_purple()));
+
+    let mut lines = vec![Line::from("")];
+    match &picker.profiles {
+        None => lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {} ", theme::spinner_frame(tick)),
+                Style::default().fg(theme::scanning_pulse()),
+            ),
+            Span::styled("Querying profiles…", theme::dim()),
+        ])),
+        Some(profiles) if profiles.is_empty() => lines.push(Line::from(Span::styled(
+            "  No connectable profiles advertised",
+            theme::dim(),
+        ))),
+        Some(profiles) => {
+            for (i, profile) in profiles.iter().enumerate() {
+                let focused = i == picker.cursor;
+                let marker = if focused { "▸ " } else { "  " };
+                let name = assigned_numbers::uuid_name(&profile.uuid)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| short_uuid(&profile.uuid));
+                let name_style = if focused {
+                    Style::default()
+                        .fg(theme::cyan())
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    theme::list_item()
+                };
+                let state = if picker.pending == Some(profile.uuid) {
+                    Span::styled(
+                        format!("{} working", theme::spinner_frame(tick)),
+                        Style::default().fg(theme::scanning_pulse()),
+                    )
+                } else {
+                    let style = match profile.state {
+                        ProfileState::Connected => theme::connected(),
+                        ProfileState::Disconnected | ProfileState::Unknown => theme::dim(),
+                    };
+                    Span::styled(profile.state.label(), style)
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {marker}"), theme::title()),
+                    Span::styled(format!("{name:<28}"), name_style),
+                    Span::styled(format!("{:<8}", short_uuid(&profile.uuid)), theme::dim()),
+                    state,
+                ]));
+            }
+        }
+    }
+
+    lines.push(Line::from(""));
+    match &picker.error {
+        Some(error) => lines.push(Line::from(Span::styled(
+            format!("  {error}"),
+            theme::error(),
+        ))),
+        None => lines.push(Line::from(Span::styled(
+            "  ⏎ connect/disconnect  R refresh  Esc close",
+            theme::dim(),
+        ))),
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -35,21 +35,80 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         spans.push(Span::styled("⏻ OFF ", theme::error()));
     }
 
+    // Discoverable window.
+    if adapter.discoverable {
+        let countdown = app
+            .discoverable_until
+            .map(|d| format!("{} ", super::format_countdown(d)))
+            .unwrap_or_default();
+        spans.push(Span::styled(
+            format!("◉ Discoverable {countdown}"),
+            ratatui::style::Style::default().fg(theme::cyan()),
+        ));
+    }
+
     spans.push(Span::styled("│ ", theme::dim()));
 
     // Scanning state with animated spinner.
     if app.scanning {
         let frame_char = theme::spinner_frame(app.tick_count);
+        let countdown = app
+            .scan_deadline
+            .map(|d| format!("{} ", super::format_countdown(d)))
+            .unwrap_or_default();
         spans.push(Span::styled(
-            format!("{frame_char} Scanning "),
+            format!("{frame_char} Scanning {countdown}"),
             ratatui::style::Style::default()
                 .fg(theme::scanning_pulse())
                 .add_modifier(ratatui::style::Modifier::BOLD),
         ));
+        if !app.discovery_filter.is_unrestricted() {
+            spans.push(Span::styled(
+                format!("󰈲 {} ", app.discovery_filter.summary()),
+                theme::dim(),
+            ));
+        }
     } else {
         spans.push(Span::styled("  Idle ", theme::dim()));
     }
 
+    // Periodic scan schedule.
+    if let Some(next) = app.periodic_next {
+        let label = if app.scanning {
+            "󰑓 periodic ".to_string()
+        } else {
+            format!("󰑓 next scan {} ", super::format_countdown(next))
+        };
+        spans.push(Span::styled(label, theme::dim()));
+    }
+
+    // Watchlist: one dot per rule, lit while any matching device is in range.
+    let rules = &crate::config::get().watchlist.rules;
+    if !rules.is_empty() {
+        spans.push(Span::styled("│ 󰈈 ", theme::dim()));
+        for (idx, rule) in rules.iter().enumerate() {
+            let present = app.watch_present.iter().any(|&(r, _)| r == idx);
+            let (dot, style) = if present {
+                ("●", theme::connected())
+            } else {
+                ("○", theme::dim())
+            };
+            spans.push(Span::styled(format!("{} {dot} ", rule.label), style));
+        }
+    }
+
+    // Low-battery warning.
+    let (low, critical) = app.battery.summary();
+    if low > 0 {
+        let style = if critical {
+            theme::error()
+        } else {
+            ratatui::style::Style::default().fg(theme::amber())
+        };
+        spans.push(Span::styled("│ ", theme::dim()));
+        spans.push(Span::styled(format!("󰁺 {low} low "), style));
+    }
+
     // Sort mode indicator.
     let sort_icon = theme::sort_icon(app.sort_mode);
     let sort_label = app.sort_mode.label();
@@ -61,6 +120,21 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             .add_modifier(ratatui::style::Modifier::BOLD),
     ));
 
+    // Category filter indicator.
+    if let Some(category) = app.category_filter {
+        spans.push(Span::styled("│ ", theme::dim()));
+        spans.push(Span::styled(
+            format!("󰈲 {} ", category.label()),
+            ratatui::style::Style::default().fg(theme::cyan()),
+        ));
+    }
+
+    // Blocked-only filter indicator.
+    if app.blocked_only {
+        spans.push(Span::styled("│ ", theme::dim()));
+        spans.push(Span::styled("⊘ blocked ", theme::error()));
+    }
+
     // Search indicator.
     if app.input_mode == InputMode::Search {
         spans.push(Span::styled("│ ", theme::dim()));
@@ -92,6 +166,37 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         ));
     }
 
+    // Send-file path input.
+    if app.input_mode == InputMode::SendFile {
+        spans.push(Span::styled("│ ", theme::dim()));
+        spans.push(Span::styled(
+            format!(" Send file: {}█", app.send_buffer),
+            ratatui::style::Style::default()
+                .fg(theme::cyan())
+                .add_modifier(ratatui::style::Modifier::ITALIC),
+        ));
+    }
+
+    // Running file transfers.
+    let running: Vec<_> = app
+        .transfers
+        .iter()
+        .filter(|t| !t.status.is_finished())
+        .collect();
+    if !running.is_empty() {
+        let sent: u64 = running.iter().map(|t| t.transferred).sum();
+        let total: u64 = running.iter().filter_map(|t| t.size).sum();
+        let percent = (sent * 100)
+            .checked_div(total)
+            .map(|p| format!(" {p}%"))
+            .unwrap_or_default();
+        spans.push(Span::styled("│ ", theme::dim()));
+        spans.push(Span::styled(
+            format!("⇪ {} sending{percent} ", running.len()),
+            ratatui::style::Style::default().fg(theme::cyan()),
+        ));
+    }
+
     // Device count (using cached value — no allocation).
     let device_count = app.filtered_count();
     spans.push(Span::styled("│ ", theme::dim()));
@@ -0,0 +1,54 @@
+//! Non-modal toast stack in the top-right corner of the content area.
+
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::{App, ToastLevel};
+use crate::theme;
+
+const TOAST_WIDTH: u16 = 44;
+const TOAST_HEIGHT: u16 = 3;
+
+/// Render active toasts, newest on top.
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let width = TOAST_WIDTH.min(area.width);
+    let x = area.x + area.width.saturating_sub(width + 1);
+    let mut y = area.y + 1;
+
+    for toast in app.toasts.iter().rev() {
+        if y + TOAST_HEIGHT > area.y + area.height {
+            break;
+        }
+        let rect = Rect {
+            x,
+            y,
+            width,
+            height: TOAST_HEIGHT,
+        };
+        y += TOAST_HEIGHT;
+
+        let (icon, color) = match toast.level {
+            ToastLevel::Info => ("󰋽", theme::cyan()),
+            ToastLevel::Warning => ("󰀦", theme::amber()),
+            ToastLevel::Critical => ("󰀨", theme::dawn_red()),
+        };
+        let block = Block::default()
+            .title(Span::styled(
+                format!(" {icon} {} ", toast.title),
+                Style::default().fg(color),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color));
+        let body = Paragraph::new(Line::from(Span::styled(
+            format!(" {}", toast.message),
+            theme::list_item(),
+        )))
+        .block(block);
+
+        frame.render_widget(Clear, rect);
+        frame.render_widget(body, rect);
+    }
+}
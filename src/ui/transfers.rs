@@ -0,0 +1,113 @@
+//! OBEX transfers list — files sent this session with progress, speed and
+//! ETA.
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::bluetooth::types::{Transfer, TransferStatus};
+use crate::theme;
+
+/// Width of the progress bar in cells.
+const BAR_WIDTH: usize = 24;
+
+/// Render the transfers list with row `cursor` selected.
+pub fn render(frame: &mut Frame, app: &App, cursor: usize) {
+    let rows = app.transfers.len().max(1) as u16;
+    let area = super::popup::centered_rect_percent(64, rows * 3 + 5, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(" ⇪ File Transfers ", theme::title()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme::deep_purple()));
+
+    let mut lines = vec![Line::from("")];
+    if app.transfers.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No transfers yet — press F on a device to send a file",
+            theme::dim(),
+        )));
+    }
+    for (i, transfer) in app.transfers.iter().enumerate() {
+        lines.extend(transfer_lines(app, transfer, i == cursor));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  x cancel  Esc close",
+        theme::dim(),
+    )));
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Name and target, progress bar, and a line of figures, for one transfer.
+fn transfer_lines(app: &App, transfer: &Transfer, focused: bool) -> Vec<Line<'static>> {
+    let marker = if focused { "▸ " } else { "  " };
+    let name_style = if focused {
+        Style::default()
+            .fg(theme::cyan())
+            .add_modifier(Modifier::BOLD)
+    } else {
+        theme::list_item()
+    };
+    let status_style = match transfer.status {
+        TransferStatus::Complete => theme::connected(),
+        TransferStatus::Error => theme::error(),
+        TransferStatus::Active => theme::paired(),
+        TransferStatus::Queued | TransferStatus::Suspended | TransferStatus::Unknown => {
+            theme::dim()
+        }
+    };
+    let header = Line::from(vec![
+        Span::styled(format!("  {marker}"), theme::title()),
+        Span::styled(transfer.name.clone(), name_style),
+        Span::styled(
+            format!(" → {}", app.device_name(&transfer.address)),
+            theme::dim(),
+        ),
+    ]);
+
+    let fraction = transfer.fraction().unwrap_or(0.0);
+    let filled = (fraction * BAR_WIDTH as f64).round() as usize;
+    let bar = Line::from(vec![
+        Span::raw("    "),
+        Span::styled("━".repeat(filled), status_style),
+        Span::styled("─".repeat(BAR_WIDTH - filled), theme::dim()),
+        Span::styled(format!(" {:>3.0}% ", fraction * 100.0), theme::list_item()),
+        Span::styled(transfer.status.label(), status_style),
+    ]);
+
+    let mut figures = match transfer.size {
+        Some(size) => format!(
+            "    {} / {}",
+            format_bytes(transfer.transferred),
+            format_bytes(size)
+        ),
+        None => format!("    {}", format_bytes(transfer.transferred)),
+    };
+    if let Some(speed) = transfer.speed() {
+        figures.push_str(&format!(" · {}/s", format_bytes(speed as u64)));
+    }
+    if let Some(eta) = transfer.eta() {
+        figures.push_str(&format!(" · {} left", super::format_duration(eta)));
+    }
+    vec![header, bar, Line::from(Span::styled(figures, theme::dim()))]
+}
+
+/// Byte count with a binary unit: `512 B`, `1.4 KiB`, `3.2 MiB`.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}